@@ -1,8 +1,12 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
 use quote::{format_ident, quote};
-use syn::{DeriveInput, Fields, GenericArgument, PathArguments, Type, TypePath, parse_macro_input};
+use syn::{
+    DeriveInput, Field, Fields, GenericArgument, GenericParam, Generics, Index, Lifetime, Member,
+    PathArguments, Type, TypeParamBound, TypePath, parse_macro_input,
+};
 
 /// Helper function to check if a type is `Signal<'a, T>` and extract the inner type `T`.
 ///
@@ -64,17 +68,232 @@ fn extract_signal_sync_inner_type(ty: &Type) -> Option<&Type> {
     None
 }
 
+/// Helper function to pair every field of a struct with the member used to access it.
+///
+/// Named fields are accessed by their identifier and tuple fields by their position, so
+/// the rest of the generator can treat both kinds of structs the same way.
+fn field_members(fields: &Fields) -> Vec<(Member, &Field)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(index)),
+            };
+            (member, field)
+        })
+        .collect()
+}
+
+/// Helper function to get the fields of the struct a `Lift` derive is applied to.
+///
+/// Enums, unions and unit structs have nothing to lift, so they are rejected with an
+/// error pointing at the type instead of panicking inside the macro.
+fn struct_fields<'i>(
+    input: &'i DeriveInput,
+    derive: &str,
+) -> syn::Result<Vec<(Member, &'i Field)>> {
+    let kind = match &input.data {
+        syn::Data::Struct(data) if !matches!(data.fields, Fields::Unit) => {
+            return Ok(field_members(&data.fields));
+        }
+        syn::Data::Struct(_) => "unit structs",
+        syn::Data::Enum(_) => "enums",
+        syn::Data::Union(_) => "unions",
+    };
+    Err(syn::Error::new_spanned(
+        &input.ident,
+        format!("{} cannot be derived for {}", derive, kind),
+    ))
+}
+
+/// Helper function to check whether a field is marked with `#[lift(skip)]`.
+///
+/// Skipped fields are copied into the inner struct as they are, even if they are signals,
+/// and no reaction is wired up for them.
+fn is_skipped(field: &Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("lift"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported lift field attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Helper function to check whether a lifetime is used anywhere in the given tokens.
+///
+/// The inner struct only declares the lifetimes its fields still use, since the lifetime
+/// of unwrapped signal fields would otherwise be an unused parameter.
+fn mentions_lifetime(tokens: TokenStream2, lifetime: &Lifetime) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) if mentions_lifetime(group.stream(), lifetime) => return true,
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(TokenTree::Ident(ident)) = tokens.peek()
+                    && *ident == lifetime.ident
+                {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Helper function to build the generics of the inner struct from those of the original one.
+///
+/// Lifetimes only used by unwrapped signal fields are dropped, along with every bound and
+/// `where` predicate mentioning them; all other parameters keep their bounds.
+fn inner_generics(generics: &Generics, field_types: &[&Type]) -> Generics {
+    let mentioned = |lifetime: &Lifetime| {
+        field_types
+            .iter()
+            .any(|ty| mentions_lifetime(quote! { #ty }, lifetime))
+    };
+    let dropped: Vec<Lifetime> = generics
+        .lifetimes()
+        .map(|lt| lt.lifetime.clone())
+        .filter(|lt| !mentioned(lt))
+        .collect();
+    let is_dropped = |tokens: TokenStream2| {
+        dropped
+            .iter()
+            .any(|lt| mentions_lifetime(tokens.clone(), lt))
+    };
+
+    let mut inner = generics.clone();
+    inner.params = generics
+        .params
+        .iter()
+        .filter(
+            |param| !matches!(param, GenericParam::Lifetime(lt) if dropped.contains(&lt.lifetime)),
+        )
+        .cloned()
+        .map(|mut param| {
+            match &mut param {
+                GenericParam::Lifetime(lt) => {
+                    lt.bounds = lt
+                        .bounds
+                        .iter()
+                        .filter(|b| !dropped.contains(b))
+                        .cloned()
+                        .collect();
+                }
+                GenericParam::Type(tp) => {
+                    tp.bounds = tp
+                        .bounds
+                        .iter()
+                        .filter(
+                            |b| !matches!(b, TypeParamBound::Lifetime(lt) if dropped.contains(lt)),
+                        )
+                        .cloned()
+                        .collect();
+                }
+                GenericParam::Const(_) => {}
+            }
+            param
+        })
+        .collect();
+    if let Some(where_clause) = &mut inner.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|pred| !is_dropped(quote! { #pred }))
+            .cloned()
+            .collect();
+    }
+    inner
+}
+
+/// Kind of signal a `Lift` derive generates the `lift` method for.
+#[derive(Clone, Copy)]
+enum Flavor {
+    /// `Signal`, built on `Rc` and `RefCell`
+    Local,
+    /// `SignalSync`, built on `Arc` and `Mutex`
+    Sync,
+}
+
+impl Flavor {
+    /// Read the flavor from a `#[lift(sync)]` attribute on the struct, if any.
+    fn from_attrs(input: &DeriveInput, default: Flavor) -> syn::Result<Flavor> {
+        let mut flavor = default;
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("lift"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("sync") {
+                    flavor = Flavor::Sync;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported lift attribute, expected `sync`"))
+                }
+            })?;
+        }
+        Ok(flavor)
+    }
+
+    /// Extract `T` if `ty` is the signal type of this flavor.
+    fn signal_inner_type(self, ty: &Type) -> Option<&Type> {
+        match self {
+            Flavor::Local => extract_signal_inner_type(ty),
+            Flavor::Sync => extract_signal_sync_inner_type(ty),
+        }
+    }
+
+    /// Path to the signal type of this flavor.
+    fn signal_path(self) -> TokenStream2 {
+        match self {
+            Flavor::Local => quote! { ::agility::Signal },
+            Flavor::Sync => quote! { ::agility::signal_sync::SignalSync },
+        }
+    }
+
+    /// Bounds the unwrapped values of signal fields need.
+    fn inner_bounds(self) -> TokenStream2 {
+        match self {
+            Flavor::Local => quote! { Clone },
+            Flavor::Sync => quote! { Clone + Send + Sync },
+        }
+    }
+}
+
 /// Derive macro to lift a struct into a reactive `Signal`.
 ///
 /// Applying `#[derive(Lift)]` to a struct generates an inner unwrapped struct and
-/// a `lift(self)` method that produces a `crate::signal::Signal<'a, _Inner>` where
+/// a `lift(self)` method that produces a `Signal<'a, _Inner>` where
 /// any fields of type `Signal<'a, T>` are replaced by their inner `T` in the
 /// generated inner struct. The generated `lift` method wires up reactions so that
 /// changes to any signal fields propagate into the resulting lifted `Signal`.
+/// Tuple structs are supported as well and lift into an inner tuple struct.
+///
+/// Adding `#[lift(sync)]` to the struct generates the same code as `#[derive(LiftSync)]`,
+/// lifting `SignalSync` fields into a `SignalSync` instead.
+/// A `lift_bidirectional(self)` method is generated as well, which additionally writes the
+/// fields of values sent to the lifted signal back into the original signal fields, for
+/// two-way bindings like forms.
+///
+/// A field marked with `#[lift(skip)]` is stored in the inner struct as is, so a signal
+/// field can be kept without making the lifted signal react to it.
 ///
 /// Example:
-/// ```rust
-/// use crate::signal::Signal;
+/// ```rust,ignore
+/// use agility::{Lift, Signal};
 ///
 /// #[derive(Lift)]
 /// struct Example<'a> {
@@ -86,181 +305,28 @@ fn extract_signal_sync_inner_type(ty: &Type) -> Option<&Type> {
 /// let lifted = example.lift(); // Signal<'a, _Example>
 /// lifted.with(|inner| println!("a = {}", inner.a));
 /// ```
-#[proc_macro_derive(Lift)]
+#[proc_macro_derive(Lift, attributes(lift))]
 pub fn derive_lift(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
-    let generics = &input.generics;
-    let vis = &input.vis;
-
-    // Get the fields
-    let fields = match &input.data {
-        syn::Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => &fields.named,
-            _ => panic!("Lift only supports structs with named fields"),
-        },
-        _ => panic!("Lift can only be derived for structs"),
-    };
-
-    // Separate signal fields from regular fields by checking the type
-    let mut signal_fields = Vec::new();
-    let mut regular_fields = Vec::new();
-
-    for field in fields {
-        if extract_signal_inner_type(&field.ty).is_some() {
-            signal_fields.push(field);
-        } else {
-            regular_fields.push(field);
-        }
-    }
-
-    // Generate the inner struct name (prefixed with underscore)
-    let inner_name = format_ident!("_{}", name);
-
-    // Generate fields for the inner struct (unwrapped types)
-    let inner_struct_fields = fields.iter().map(|field| {
-        let field_name = &field.ident;
-        let field_vis = &field.vis;
-
-        // If it's a Signal<'a, T>, use T; otherwise use the original type
-        let field_ty = if let Some(inner_ty) = extract_signal_inner_type(&field.ty) {
-            inner_ty
-        } else {
-            &field.ty
-        };
-
-        quote! {
-            #field_vis #field_name: #field_ty
-        }
-    });
-
-    // Generate the reactive setup code for signal fields
-    let reactive_setup = signal_fields.iter().map(|field| {
-        let field_name = &field.ident;
-
-        quote! {
-            {
-                let result_signal_weak = std::rc::Rc::downgrade(&result_signal.0);
-                let source_for_closure = std::rc::Rc::downgrade(&instance.#field_name.0);
-                let react_fn = Box::new(move || {
-                    if let Some(result_sig) = result_signal_weak.upgrade() {
-                        if !*result_sig.explicitly_modified.borrow() {
-                            if let Some(source) = source_for_closure.upgrade() {
-                                // Swap values instead of cloning
-                                std::mem::swap(
-                                    &mut *source.value.borrow_mut(),
-                                    &mut result_sig.value.borrow_mut().#field_name,
-                                );
-                            }
-                        }
-                    }
-                });
-                let cloned_signal = instance.#field_name.clone();
-                cloned_signal.0.react_fns.borrow_mut().push(react_fn);
-                cloned_signal.0.successors.borrow_mut().push(crate::signal::WeakSignalRef::new(&result_signal));
-            }
-        }
-    });
-
-    // Generate the inner struct initialization from main struct (using swap - no clone!)
-    let inner_from_main = signal_fields.iter().map(|field| {
-        let field_name = &field.ident;
-        quote! {
-            #field_name: {
-                let mut temp = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-                std::mem::swap(&mut *instance.#field_name.0.value.borrow_mut(), &mut temp);
-                temp
-            }
-        }
-    });
-
-    let regular_from_main = regular_fields.iter().map(|field| {
-        let field_name = &field.ident;
-        quote! {
-            #field_name: instance.#field_name.clone()
-        }
-    });
-
-    // Generate restore code for signal fields (swap back after creating result_signal)
-    let restore_values = signal_fields.iter().map(|field| {
-        let field_name = &field.ident;
-        quote! {
-            std::mem::swap(
-                &mut *instance.#field_name.0.value.borrow_mut(),
-                &mut result_signal.0.value.borrow_mut().#field_name,
-            );
-        }
-    });
-
-    // Generate Clone trait bounds for signal fields (using the unwrapped inner type)
-    let signal_clone_bounds = signal_fields.iter().filter_map(|field| {
-        extract_signal_inner_type(&field.ty).map(|inner_ty| {
-            quote! { #inner_ty: Clone }
-        })
-    });
-
-    // Generate Clone trait bounds for regular fields
-    let regular_clone_bounds = regular_fields.iter().map(|field| {
-        let field_ty = &field.ty;
-        quote! { #field_ty: Clone }
-    });
-
-    // Extract generics for impl block
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-    // Create a version of generics without lifetimes for the inner struct
-    let type_params = generics.type_params().map(|tp| &tp.ident);
-    let inner_ty_generics = if generics.type_params().count() > 0 {
-        quote! { <#(#type_params),*> }
-    } else {
-        quote! {}
-    };
-
-    let expanded = quote! {
-        // Inner struct (unwrapped types)
-        #[derive(Clone)]
-        #vis struct #inner_name #inner_ty_generics {
-            #(#inner_struct_fields),*
-        }
-
-        impl #impl_generics #name #ty_generics #where_clause {
-            pub fn lift(self) -> crate::signal::Signal<'a, #inner_name #inner_ty_generics>
-            where
-                #(#signal_clone_bounds,)*
-                #(#regular_clone_bounds,)*
-            {
-                let instance = self;
-                let initial_inner = #inner_name {
-                    #(#inner_from_main,)*
-                    #(#regular_from_main),*
-                };
-
-                let result_signal = crate::signal::Signal::new(initial_inner);
-
-                // Restore original values by swapping back
-                #(#restore_values)*
-
-                #(#reactive_setup)*
-
-                result_signal
-            }
-        }
-    };
-
-    TokenStream::from(expanded)
+    expand_lift(&input, "Lift", Flavor::Local)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 /// Derive macro to lift a struct into a thread-safe reactive `SignalSync`.
 ///
 /// Applying `#[derive(LiftSync)]` to a struct generates an inner unwrapped struct and
-/// a `lift(self)` method that produces a `crate::signal_sync::SignalSync<'a, _Inner>` where
+/// a `lift(self)` method that produces a `SignalSync<'a, _Inner>` where
 /// any fields of type `SignalSync<'a, T>` are replaced by their inner `T` in the
 /// generated inner struct. The generated `lift` method wires up thread-safe reactions
 /// so that changes to any signal fields propagate into the resulting lifted `SignalSync`.
+/// Like with `#[derive(Lift)]`, tuple structs are supported, fields marked with
+/// `#[lift(skip)]` are stored as is and a `lift_bidirectional(self)` method writing
+/// changes back into the signal fields is generated as well.
 ///
 /// Example:
-/// ```rust
-/// use crate::signal_sync::SignalSync;
+/// ```rust,ignore
+/// use agility::{LiftSync, signal_sync::SignalSync};
 ///
 /// #[derive(LiftSync)]
 /// struct ExampleSync<'a> {
@@ -272,166 +338,163 @@ pub fn derive_lift(input: TokenStream) -> TokenStream {
 /// let lifted = example.lift(); // SignalSync<'a, _ExampleSync>
 /// lifted.with(|inner| println!("a = {}", inner.a));
 /// ```
-#[proc_macro_derive(LiftSync)]
+#[proc_macro_derive(LiftSync, attributes(lift))]
 pub fn derive_lift_sync(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    expand_lift(&input, "LiftSync", Flavor::Sync)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate the inner struct and the `lift` method shared by both derives.
+fn expand_lift(input: &DeriveInput, derive: &str, default: Flavor) -> syn::Result<TokenStream2> {
+    let flavor = Flavor::from_attrs(input, default)?;
     let name = &input.ident;
     let generics = &input.generics;
     let vis = &input.vis;
 
     // Get the fields
-    let fields = match &input.data {
-        syn::Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => &fields.named,
-            _ => panic!("LiftSync only supports structs with named fields"),
-        },
-        _ => panic!("LiftSync can only be derived for structs"),
-    };
+    let fields = struct_fields(input, derive)?;
+    let is_tuple = matches!(fields.first(), Some((Member::Unnamed(_), _)));
 
-    // Separate signal fields from regular fields by checking the type
+    // Separate signal fields from regular fields by checking the type, skipped fields are
+    // regular fields whatever their type
     let mut signal_fields = Vec::new();
     let mut regular_fields = Vec::new();
+    let mut inner_struct_fields = Vec::new();
+    let mut inner_field_types = Vec::new();
 
-    for field in fields {
-        if extract_signal_sync_inner_type(&field.ty).is_some() {
-            signal_fields.push(field);
+    for (member, field) in &fields {
+        let inner_ty = if is_skipped(field)? {
+            None
         } else {
-            regular_fields.push(field);
-        }
-    }
-
-    // Generate the inner struct name (prefixed with underscore)
-    let inner_name = format_ident!("_{}", name);
-
-    // Generate fields for the inner struct (unwrapped types)
-    let inner_struct_fields = fields.iter().map(|field| {
-        let field_name = &field.ident;
-        let field_vis = &field.vis;
+            flavor.signal_inner_type(&field.ty)
+        };
 
-        // If it's a SignalSync<'a, T>, use T; otherwise use the original type
-        let field_ty = if let Some(inner_ty) = extract_signal_sync_inner_type(&field.ty) {
-            inner_ty
-        } else {
-            &field.ty
+        // If it's a lifted signal, use its inner type; otherwise use the original type
+        let field_ty = match inner_ty {
+            Some(inner_ty) => {
+                signal_fields.push((member, inner_ty));
+                inner_ty
+            }
+            None => {
+                regular_fields.push((member, &field.ty));
+                &field.ty
+            }
         };
 
-        quote! {
-            #field_vis #field_name: #field_ty
-        }
-    });
+        inner_field_types.push(field_ty);
+
+        // Generate the field for the inner struct (unwrapped type)
+        let field_vis = &field.vis;
+        inner_struct_fields.push(match &field.ident {
+            Some(field_name) => quote! { #field_vis #field_name: #field_ty },
+            None => quote! { #field_vis #field_ty },
+        });
+    }
 
-    // Generate the reactive setup code for signal fields (thread-safe version)
-    let reactive_setup = signal_fields.iter().map(|field| {
-        let field_name = &field.ident;
+    // Generate the inner struct name (prefixed with underscore)
+    let inner_name = format_ident!("_{}", name);
 
+    // Generate the reactive setup code for signal fields
+    let reactive_setup = signal_fields.iter().map(|(member, _)| {
         quote! {
-            {
-                let result_signal_weak = std::sync::Arc::downgrade(&result_signal.0);
-                let source_for_closure = std::sync::Arc::downgrade(&instance.#field_name.0);
-                let react_fn = Box::new(move || {
-                    if let Some(result_sig) = result_signal_weak.upgrade() {
-                        if !result_sig.explicitly_modified.load(std::sync::atomic::Ordering::Acquire) {
-                            if let Some(source) = source_for_closure.upgrade() {
-                                // Swap values instead of cloning
-                                std::mem::swap(
-                                    &mut *source.value.lock().unwrap(),
-                                    &mut result_sig.value.lock().unwrap().#field_name,
-                                );
-                            }
-                        }
-                    }
-                });
-                let cloned_signal = instance.#field_name.clone();
-                cloned_signal.0.react_fns.write().unwrap().push(react_fn);
-                cloned_signal.0.successors.write().unwrap().push(crate::signal_sync::WeakSignalRefSync::new(&result_signal));
-            }
+            instance.#member.lift_field(&result_signal, |inner| &mut inner.#member);
         }
     });
 
-    // Generate the inner struct initialization from main struct (using swap - no clone!)
-    let inner_from_main = signal_fields.iter().map(|field| {
-        let field_name = &field.ident;
+    // Generate the two-way setup code for signal fields, used by `lift_bidirectional`
+    let bidirectional_setup = signal_fields.iter().map(|(member, _)| {
         quote! {
-            #field_name: {
-                let mut temp = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-                std::mem::swap(&mut *instance.#field_name.0.value.lock().unwrap(), &mut temp);
-                temp
-            }
+            instance.#member.bind_field(&result_signal, |inner| &mut inner.#member);
         }
     });
 
-    let regular_from_main = regular_fields.iter().map(|field| {
-        let field_name = &field.ident;
+    // Generate the inner struct initialization from main struct (signal fields are `Clone` anyway)
+    let inner_from_main = signal_fields.iter().map(|(member, _)| {
         quote! {
-            #field_name: instance.#field_name.clone()
+            #member: instance.#member.get()
         }
     });
 
-    // Generate restore code for signal fields (swap back after creating result_signal)
-    let restore_values = signal_fields.iter().map(|field| {
-        let field_name = &field.ident;
+    let regular_from_main = regular_fields.iter().map(|(member, _)| {
         quote! {
-            std::mem::swap(
-                &mut *instance.#field_name.0.value.lock().unwrap(),
-                &mut result_signal.0.value.lock().unwrap().#field_name,
-            );
+            #member: instance.#member.clone()
         }
     });
 
-    // Generate Clone + Send + Sync trait bounds for signal fields (using the unwrapped inner type)
-    let signal_clone_bounds = signal_fields.iter().filter_map(|field| {
-        extract_signal_sync_inner_type(&field.ty).map(|inner_ty| {
-            quote! { #inner_ty: Clone + Send + Sync }
-        })
-    });
+    // Generate trait bounds for signal fields (using the unwrapped inner type)
+    let inner_bounds = flavor.inner_bounds();
+    let signal_clone_bounds: Vec<_> = signal_fields
+        .iter()
+        .map(|(_, inner_ty)| quote! { #inner_ty: #inner_bounds })
+        .collect();
 
     // Generate Clone trait bounds for regular fields
-    let regular_clone_bounds = regular_fields.iter().map(|field| {
-        let field_ty = &field.ty;
-        quote! { #field_ty: Clone }
-    });
+    let regular_clone_bounds: Vec<_> = regular_fields
+        .iter()
+        .map(|(_, field_ty)| quote! { #field_ty: Clone })
+        .collect();
 
     // Extract generics for impl block
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    // Create a version of generics without lifetimes for the inner struct
-    let type_params = generics.type_params().map(|tp| &tp.ident);
-    let inner_ty_generics = if generics.type_params().count() > 0 {
-        quote! { <#(#type_params),*> }
+    // Create a version of generics for the inner struct, keeping only the lifetimes still in use
+    let inner_generics = inner_generics(generics, &inner_field_types);
+    let (_, inner_ty_generics, inner_where_clause) = inner_generics.split_for_impl();
+
+    let inner_struct = if is_tuple {
+        quote! {
+            #vis struct #inner_name #inner_generics(#(#inner_struct_fields),*) #inner_where_clause;
+        }
     } else {
-        quote! {}
+        quote! {
+            #vis struct #inner_name #inner_generics #inner_where_clause { #(#inner_struct_fields),* }
+        }
     };
 
-    let expanded = quote! {
+    let signal = flavor.signal_path();
+
+    // Build the lifted signal from the current field values, shared by both lift methods
+    let construct = quote! {
+        let instance = self;
+        let initial_inner = #inner_name {
+            #(#inner_from_main,)*
+            #(#regular_from_main),*
+        };
+
+        let result_signal = #signal::new(initial_inner);
+    };
+
+    Ok(quote! {
         // Inner struct (unwrapped types)
         #[derive(Clone)]
-        #vis struct #inner_name #inner_ty_generics {
-            #(#inner_struct_fields),*
-        }
+        #inner_struct
 
         impl #impl_generics #name #ty_generics #where_clause {
-            pub fn lift(self) -> crate::signal_sync::SignalSync<'a, #inner_name #inner_ty_generics>
+            pub fn lift(self) -> #signal<'a, #inner_name #inner_ty_generics>
             where
                 #(#signal_clone_bounds,)*
                 #(#regular_clone_bounds,)*
             {
-                let instance = self;
-                let initial_inner = #inner_name {
-                    #(#inner_from_main,)*
-                    #(#regular_from_main),*
-                };
+                #construct
 
-                let result_signal = crate::signal_sync::SignalSync::new(initial_inner);
+                #(#reactive_setup)*
 
-                // Restore original values by swapping back
-                #(#restore_values)*
+                result_signal
+            }
 
-                #(#reactive_setup)*
+            pub fn lift_bidirectional(self) -> #signal<'a, #inner_name #inner_ty_generics>
+            where
+                #(#signal_clone_bounds,)*
+                #(#regular_clone_bounds,)*
+            {
+                #construct
+
+                #(#bidirectional_setup)*
 
                 result_signal
             }
         }
-    };
-
-    TokenStream::from(expanded)
+    })
 }
@@ -0,0 +1,32 @@
+//! Compare `map` against its `Copy` specialization `map_copied` on a chain of numeric signals.
+//!
+//! Run with `cargo run --release --example copy_map`.
+
+use std::time::{Duration, Instant};
+
+use agility::Signal;
+
+const DEPTH: usize = 64;
+const SENDS: i64 = 20_000;
+
+fn run(build: impl Fn(&Signal<'static, i64>) -> Signal<'static, i64>) -> Duration {
+    let source = Signal::new(0i64);
+    let mut chain = vec![build(&source)];
+    for _ in 1..DEPTH {
+        let next = build(chain.last().unwrap());
+        chain.push(next);
+    }
+
+    let start = Instant::now();
+    for i in 0..SENDS {
+        source.send(i);
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let generic = run(|s| s.map(|x| x + 1));
+    let copied = run(|s| s.map_copied(|x| x + 1));
+    println!("map:        {:?}", generic);
+    println!("map_copied: {:?}", copied);
+}
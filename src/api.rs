@@ -5,14 +5,22 @@ trait Mutable {}
 
 /// Trait for lifting collections of signals into a single signal.
 /// For example, lifting (Signal<A>, Signal<B>) into Signal<(A, B)>
+/// Tuples of up to four signals, arrays and vectors can be lifted.
+///
+/// The lifted signal starts from the current values of the sources and each change of a source
+/// is cloned into it, so the inner types must be `Clone`; the sources keep their own values.
+/// A value that can't be cloned, such as one owning a `File`, is lifted through a shared
+/// handle to it, like `Rc` or `Arc`: both the source and the lifted signal hold one, and only
+/// the handle is cloned.
 pub trait LiftInto<T> {
     /// Lift the collection into a single signal
     ///
     /// # Examples
     /// ```rust
+    /// # use agility::{Signal, api::LiftInto};
     /// let a = Signal::new(1);
     /// let b = Signal::new(2);
-    /// let lifted = [a, b].lift();
+    /// let lifted = [&a, &b].lift();
     /// lifted.with(|[x, y]| println!("Lifted: {}, {}", x, y));
     /// a.send(10); // prints "Lifted: 10, 2"
     /// b.send(20); // prints "Lifted: 10, 20"
@@ -22,6 +30,13 @@ pub trait LiftInto<T> {
 
 /// Trait for lifting collections of thread-safe signals into a single thread-safe signal.
 /// For example, lifting (SignalSync<A>, SignalSync<B>) into SignalSync<(A, B)>
+/// Tuples of up to four signals, arrays and vectors can be lifted.
+///
+/// The lifted signal starts from the current values of the sources and each change of a source
+/// is cloned into it, so the inner types must be `Clone`; the sources keep their own values.
+/// A value that can't be cloned, such as one owning a `File`, is lifted through a shared
+/// handle to it, like `Rc` or `Arc`: both the source and the lifted signal hold one, and only
+/// the handle is cloned.
 pub trait LiftIntoSync<T> {
     /// Lift the collection into a single thread-safe signal
     fn lift(self) -> T;
@@ -46,28 +61,28 @@ impl<T> Mutable for Vec<T> {}
 impl<'a, T> Liftable<'a> for Signal<'a, T> {
     type Inner = T;
     fn as_ref(&self) -> &Signal<'a, Self::Inner> {
-        &self
+        self
     }
 }
 
-impl<'a, T> Liftable<'a> for &Signal<'a, T> {
-    type Inner = T;
+impl<'a, L: Liftable<'a> + ?Sized> Liftable<'a> for &L {
+    type Inner = L::Inner;
     fn as_ref(&self) -> &Signal<'a, Self::Inner> {
-        self
+        (**self).as_ref()
     }
 }
 
 impl<'a, T: Send + Sync + 'a> LiftableSync<'a> for SignalSync<'a, T> {
     type Inner = T;
     fn as_ref(&self) -> &SignalSync<'a, Self::Inner> {
-        &self
+        self
     }
 }
 
-impl<'a, T: Send + Sync + 'a> LiftableSync<'a> for &SignalSync<'a, T> {
-    type Inner = T;
+impl<'a, L: LiftableSync<'a> + ?Sized> LiftableSync<'a> for &L {
+    type Inner = L::Inner;
     fn as_ref(&self) -> &SignalSync<'a, Self::Inner> {
-        self
+        (**self).as_ref()
     }
 }
 
@@ -75,19 +90,39 @@ impl<'a, S1, S2> LiftInto<Signal<'a, (S1::Inner, S2::Inner)>> for (S1, S2)
 where
     S1: Liftable<'a>,
     S2: Liftable<'a>,
-    S1::Inner: 'a,
-    S2::Inner: 'a,
+    S1::Inner: Clone + 'a,
+    S2::Inner: Clone + 'a,
 {
     fn lift(self) -> Signal<'a, (S1::Inner, S2::Inner)> {
         self.0.as_ref().combine(self.1.as_ref())
     }
 }
 
+/// Implement `LiftInto` for tuples of more than two signals
+///
+/// Each source feeds its position of the lifted tuple, the same way `combine` feeds a pair.
+macro_rules! impl_lift_into_tuple {
+    ($(($($S:ident . $idx:tt),+)),+ $(,)?) => {$(
+        impl<'a, $($S),+> LiftInto<Signal<'a, ($($S::Inner,)+)>> for ($($S,)+)
+        where
+            $($S: Liftable<'a>, $S::Inner: Clone + 'a,)+
+        {
+            fn lift(self) -> Signal<'a, ($($S::Inner,)+)> {
+                let lifted = Signal::new(($(self.$idx.as_ref().get(),)+));
+                $(self.$idx.as_ref().lift_field(&lifted, |values| &mut values.$idx);)+
+                lifted
+            }
+        }
+    )+};
+}
+
+impl_lift_into_tuple!((S1.0, S2.1, S3.2), (S1.0, S2.1, S3.2, S4.3));
+
 impl<'a, S, I> LiftInto<Signal<'a, Vec<S::Inner>>> for I
 where
     S: Liftable<'a>,
     I: IntoIterator<Item = S> + Mutable,
-    S::Inner: 'a,
+    S::Inner: Clone + 'a,
 {
     fn lift(self) -> Signal<'a, Vec<S::Inner>> {
         let mut items: Vec<S> = self.into_iter().collect();
@@ -95,7 +130,7 @@ where
             Signal::new(Vec::new())
         } else {
             let first = items.remove(0);
-            first.as_ref().extend(items.into_iter())
+            first.as_ref().extend(items)
         }
     }
 }
@@ -103,7 +138,7 @@ where
 impl<'a, const N: usize, S> LiftInto<Signal<'a, [S::Inner; N]>> for [S; N]
 where
     S: Liftable<'a>,
-    S::Inner: 'a,
+    S::Inner: Clone + 'a,
 {
     fn lift(self) -> Signal<'a, [S::Inner; N]> {
         Signal::<S::Inner>::lift_from_array::<S, N>(self)
@@ -115,19 +150,39 @@ impl<'a, S1, S2> LiftIntoSync<SignalSync<'a, (S1::Inner, S2::Inner)>> for (S1, S
 where
     S1: LiftableSync<'a>,
     S2: LiftableSync<'a>,
-    S1::Inner: Send + Sync + 'a,
-    S2::Inner: Send + Sync + 'a,
+    S1::Inner: Send + Sync + Clone + 'a,
+    S2::Inner: Send + Sync + Clone + 'a,
 {
     fn lift(self) -> SignalSync<'a, (S1::Inner, S2::Inner)> {
         self.0.as_ref().combine(self.1.as_ref())
     }
 }
 
+/// Implement `LiftIntoSync` for tuples of more than two signals
+///
+/// Each source feeds its position of the lifted tuple, the same way `combine` feeds a pair.
+macro_rules! impl_lift_into_sync_tuple {
+    ($(($($S:ident . $idx:tt),+)),+ $(,)?) => {$(
+        impl<'a, $($S),+> LiftIntoSync<SignalSync<'a, ($($S::Inner,)+)>> for ($($S,)+)
+        where
+            $($S: LiftableSync<'a>, $S::Inner: Send + Sync + Clone + 'a,)+
+        {
+            fn lift(self) -> SignalSync<'a, ($($S::Inner,)+)> {
+                let lifted = SignalSync::new(($(self.$idx.as_ref().get(),)+));
+                $(self.$idx.as_ref().lift_field(&lifted, |values| &mut values.$idx);)+
+                lifted
+            }
+        }
+    )+};
+}
+
+impl_lift_into_sync_tuple!((S1.0, S2.1, S3.2), (S1.0, S2.1, S3.2, S4.3));
+
 impl<'a, S, I> LiftIntoSync<SignalSync<'a, Vec<S::Inner>>> for I
 where
     S: LiftableSync<'a>,
     I: IntoIterator<Item = S> + Mutable,
-    S::Inner: Send + Sync + 'a,
+    S::Inner: Send + Sync + Clone + 'a,
 {
     fn lift(self) -> SignalSync<'a, Vec<S::Inner>> {
         let mut items: Vec<S> = self.into_iter().collect();
@@ -135,7 +190,7 @@ where
             SignalSync::new(Vec::new())
         } else {
             let first = items.remove(0);
-            first.as_ref().extend(items.into_iter())
+            first.as_ref().extend(items)
         }
     }
 }
@@ -143,7 +198,7 @@ where
 impl<'a, const N: usize, S> LiftIntoSync<SignalSync<'a, [S::Inner; N]>> for [S; N]
 where
     S: LiftableSync<'a>,
-    S::Inner: Send + Sync + 'a,
+    S::Inner: Send + Sync + Clone + 'a,
 {
     fn lift(self) -> SignalSync<'a, [S::Inner; N]> {
         SignalSync::<S::Inner>::lift_from_array::<S, N>(self)
@@ -0,0 +1,139 @@
+//! Helpers to measure the cost of propagating updates through a graph of signals
+//!
+//! This module is only available with the `bench` feature. `bench_propagation` sends to a root
+//! signal repeatedly and reports how long it took and how many reactions ran. To also count
+//! allocations, install `CountingAllocator` as the global allocator of the benchmark binary.
+//!
+//! # Example
+//! ```rust
+//! # use agility::{Signal, bench::{CountingAllocator, bench_propagation}};
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! let root = Signal::new(0);
+//! let doubled = root.map(|x| x * 2);
+//! let _observer = doubled.map(|x| x + 1);
+//! let stats = bench_propagation(&root, 1_000);
+//! println!("{:?} per send, {} reactions", stats.per_send(), stats.reactions);
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::signal::Signal;
+use crate::signal_sync::SignalSync;
+
+static REACTIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Statistics collected by `bench_propagation`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropagationStats {
+    /// The number of sends performed
+    pub sends: usize,
+    /// The total time spent sending and propagating
+    pub elapsed: Duration,
+    /// The number of reactions that ran
+    pub reactions: u64,
+    /// The number of allocations made, always zero unless `CountingAllocator` is installed
+    pub allocations: u64,
+}
+
+impl PropagationStats {
+    /// Get the average time of a single send
+    pub fn per_send(&self) -> Duration {
+        self.elapsed / self.sends.max(1) as u32
+    }
+}
+
+/// Global allocator that counts allocations and forwards them to the system allocator
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Get the number of reactions that ran since the program started
+pub fn reactions_fired() -> u64 {
+    REACTIONS.load(Ordering::Relaxed)
+}
+
+/// Get the number of allocations counted by `CountingAllocator` since the program started
+pub fn allocations() -> u64 {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+pub(crate) fn record_reaction() {
+    REACTIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Send to `root` `sends` times and measure the propagation
+///
+/// Each send leaves the value unchanged and propagates through the whole graph below `root`,
+/// so the stats only reflect the cost of the graph. The counters are global, so other
+/// signals updated concurrently are counted as well.
+pub fn bench_propagation<'a, T: 'a>(root: &Signal<'a, T>, sends: usize) -> PropagationStats {
+    measure(sends, || {
+        root.send_with(|_| {});
+    })
+}
+
+/// Send to `root` `sends` times and measure the propagation (thread-safe version)
+pub fn bench_propagation_sync<'a, T: Send + Sync + 'a>(
+    root: &SignalSync<'a, T>,
+    sends: usize,
+) -> PropagationStats {
+    measure(sends, || {
+        root.send_with(|_| {});
+    })
+}
+
+fn measure(sends: usize, send: impl Fn()) -> PropagationStats {
+    let reactions = reactions_fired();
+    let allocations = allocations();
+    let start = Instant::now();
+    for _ in 0..sends {
+        send();
+    }
+    PropagationStats {
+        sends,
+        elapsed: start.elapsed(),
+        reactions: reactions_fired() - reactions,
+        allocations: self::allocations() - allocations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_propagation() {
+        let root = Signal::new(0);
+        let doubled = root.map(|x| x * 2);
+        let _observer = doubled.map(|x| x + 1);
+        let stats = bench_propagation(&root, 10);
+        assert_eq!(stats.sends, 10);
+        assert!(stats.reactions >= 20);
+
+        let root = SignalSync::new(0);
+        let _observer = root.map(|x| x + 1);
+        let stats = bench_propagation_sync(&root, 5);
+        assert!(stats.reactions >= 5);
+    }
+}
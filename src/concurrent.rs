@@ -1,19 +1,22 @@
 use std::sync::{
     Arc, Mutex,
     atomic::{AtomicBool, Ordering},
-    mpsc::{self, Receiver, SendError, Sender, TryRecvError},
+    mpsc::{self, Receiver, SendError, Sender, SyncSender, TryRecvError, TrySendError},
 };
-use std::thread;
+use std::thread::{self, JoinHandle};
 
+/// A stage of a pipeline, processing each input on the pipeline's own thread
+///
+/// Any `Fn(Input) -> Output` closure is a participant. A participant returning a `Result` can
+/// be run with `Pipeline::try_new`, which passes on the `Ok` values only.
 pub trait Participant<Input> {
-    type Output: Clone;
+    type Output;
     fn process(&self, input: Input) -> Self::Output;
 }
 
 impl<F, Input, Output> Participant<Input> for F
 where
     F: Fn(Input) -> Output,
-    Output: Clone,
 {
     type Output = Output;
     fn process(&self, input: Input) -> Self::Output {
@@ -21,86 +24,319 @@ where
     }
 }
 
-struct NextStage<O> {
-    sender: Sender<Option<O>>,
-    thread: thread::Thread,
+/// The sending half of the input channel of a stage
+enum InputSender<I> {
+    Unbounded(Sender<Option<I>>),
+    Bounded(SyncSender<Option<I>>),
+}
+
+impl<I> InputSender<I> {
+    /// Send a value, blocking while a bounded channel is full
+    fn send(&self, value: Option<I>) -> Result<(), SendError<Option<I>>> {
+        match self {
+            InputSender::Unbounded(sender) => sender.send(value),
+            InputSender::Bounded(sender) => sender.send(value),
+        }
+    }
+
+    /// Send a value, failing instead of blocking while a bounded channel is full
+    fn try_send(&self, value: Option<I>) -> Result<(), TrySendError<Option<I>>> {
+        match self {
+            InputSender::Unbounded(sender) => sender
+                .send(value)
+                .map_err(|SendError(value)| TrySendError::Disconnected(value)),
+            InputSender::Bounded(sender) => sender.try_send(value),
+        }
+    }
+}
+
+impl<I> Clone for InputSender<I> {
+    fn clone(&self) -> Self {
+        match self {
+            InputSender::Unbounded(sender) => InputSender::Unbounded(sender.clone()),
+            InputSender::Bounded(sender) => InputSender::Bounded(sender.clone()),
+        }
+    }
+}
+
+/// Where a stage passes its outputs on to
+enum NextStage<O> {
+    /// The input of another stage, running on `thread`
+    Stage {
+        sender: InputSender<O>,
+        thread: thread::Thread,
+    },
+    /// A channel owned by the caller, see `Pipeline::sink`
+    Sink(Sender<O>),
 }
 
 impl<O> NextStage<O> {
     fn send(&self, value: Option<O>) {
-        let _ = self.sender.send(value);
-        self.thread.unpark();
+        match self {
+            NextStage::Stage { sender, thread } => {
+                let _ = sender.send(value);
+                thread.unpark();
+            }
+            NextStage::Sink(sender) => {
+                if let Some(value) = value {
+                    let _ = sender.send(value);
+                }
+            }
+        }
     }
 }
 
+/// A chain of participants, each running on a dedicated thread
+///
+/// Inputs sent to the pipeline are processed in order by its first stage, and every output is
+/// passed on to the stages connected after it. A stage may be connected to several downstream
+/// stages: each of them receives its own clone of every output, which is why outputs must be
+/// `Clone`; the last downstream stage receives the output itself.
 pub struct Pipeline<I, O: Clone> {
-    sender: Sender<Option<I>>,
+    sender: InputSender<I>,
     next_stages: Arc<Mutex<Vec<NextStage<O>>>>,
     processing_thread: thread::Thread,
     busy: Arc<AtomicBool>,
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
 
 impl<I, O: Clone> Pipeline<I, O> {
-    pub fn new<T: Participant<I, Output = O>>(participant: T) -> Self
+    /// Create a single stage pipeline running `participant` on a new thread
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::concurrent::Pipeline;
+    /// let doubler = Pipeline::new(|x: u64| x * 2);
+    /// doubler.send(21).unwrap();
+    /// doubler.shutdown().unwrap();
+    /// ```
+    pub fn new<T>(participant: T) -> Self
     where
         I: Send + 'static,
-        T: Send + 'static,
+        T: Participant<I, Output = O> + Send + 'static,
         O: Send + 'static,
     {
         let (sender, receiver) = mpsc::channel();
-        let (thread_tx, thread_rx) = mpsc::channel();
+        Self::spawn(
+            move |input| Some(participant.process(input)),
+            InputSender::Unbounded(sender),
+            receiver,
+        )
+    }
 
+    /// Create a single stage pipeline whose input queue holds at most `capacity` inputs
+    ///
+    /// This gives backpressure: once the queue is full, `send` blocks until the stage takes
+    /// the next input, so a fast producer is throttled to the pace of a slow stage instead of
+    /// growing the queue without limit. `try_send` fails with `TrySendError::Full` instead of
+    /// blocking. A stage connected after a bounded one throttles the stage feeding it the same
+    /// way. With a capacity of 0, every send waits for the stage to take the input.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::concurrent::Pipeline;
+    /// let slow = Pipeline::bounded(|x: u64| std::thread::sleep(std::time::Duration::from_millis(x)), 1);
+    /// for _ in 0..3 {
+    ///     slow.send(10).unwrap(); // waits while an input is already queued
+    /// }
+    /// slow.shutdown().unwrap();
+    /// ```
+    pub fn bounded<T>(participant: T, capacity: usize) -> Self
+    where
+        I: Send + 'static,
+        T: Participant<I, Output = O> + Send + 'static,
+        O: Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        Self::spawn(
+            move |input| Some(participant.process(input)),
+            InputSender::Bounded(sender),
+            receiver,
+        )
+    }
+
+    /// Create a single stage pipeline running a fallible `participant` on a new thread
+    ///
+    /// Only the `Ok` outputs are passed on to the next stages; every `Err` is handed to
+    /// `on_error`, on the thread of the stage, and the stage goes on with the next input.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::concurrent::Pipeline;
+    /// let parser = Pipeline::try_new(
+    ///     |s: String| s.parse::<u64>(),
+    ///     |e| println!("skipped: {}", e),
+    /// );
+    /// let printer = Pipeline::new(|x: u64| println!("parsed: {}", x));
+    /// let pipeline = parser.connect(printer);
+    /// pipeline.send("x".to_string()).unwrap(); // prints "skipped: invalid digit found in string"
+    /// pipeline.send("1".to_string()).unwrap(); // prints "parsed: 1"
+    /// pipeline.shutdown().unwrap();
+    /// ```
+    pub fn try_new<T, E, H>(participant: T, on_error: H) -> Self
+    where
+        I: Send + 'static,
+        T: Participant<I, Output = Result<O, E>> + Send + 'static,
+        H: Fn(E) + Send + 'static,
+        O: Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        Self::spawn(
+            move |input| participant.process(input).map_err(&on_error).ok(),
+            InputSender::Unbounded(sender),
+            receiver,
+        )
+    }
+
+    /// Start the thread of a stage, passing on the outputs `process` returns
+    fn spawn<P>(process: P, sender: InputSender<I>, receiver: Receiver<Option<I>>) -> Self
+    where
+        I: Send + 'static,
+        P: Fn(I) -> Option<O> + Send + 'static,
+        O: Send + 'static,
+    {
         let next_stages = Arc::new(Mutex::new(Vec::new()));
         let next_stages_clone = next_stages.clone();
 
         let busy = Arc::new(AtomicBool::new(false));
         let busy_clone = busy.clone();
 
-        thread::spawn(move || {
-            thread_tx.send(thread::current()).unwrap();
-            Self::process_loop(participant, receiver, next_stages_clone, busy_clone);
+        let handle = thread::spawn(move || {
+            Self::process_loop(process, receiver, next_stages_clone, busy_clone);
         });
 
         Self {
             sender,
             next_stages,
-            processing_thread: thread_rx.recv().unwrap(),
+            processing_thread: handle.thread().clone(),
             busy,
+            handles: Arc::new(Mutex::new(vec![handle])),
         }
     }
 
+    /// Feed the outputs of this pipeline into `next`
+    ///
+    /// This returns a pipeline taking the inputs of this one and ending with `next`. This
+    /// pipeline may be connected again to fan its outputs out to several stages; the returned
+    /// pipeline owns the threads of both, so joining it waits for all of them.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::concurrent::Pipeline;
+    /// let adder = Pipeline::new(|x: u64| x + 1);
+    /// let printer = Pipeline::new(|x: u64| println!("{}", x));
+    /// let combined = adder.connect(printer);
+    /// combined.send(1).unwrap(); // prints "2"
+    /// combined.shutdown().unwrap();
+    /// ```
     pub fn connect<U: Clone>(&self, next: Pipeline<O, U>) -> Pipeline<I, U> {
-        self.next_stages.lock().unwrap().push(NextStage {
+        self.next_stages.lock().unwrap().push(NextStage::Stage {
             sender: next.sender.clone(),
             thread: next.processing_thread.clone(),
         });
+        self.handles
+            .lock()
+            .unwrap()
+            .append(&mut next.handles.lock().unwrap());
 
         Pipeline {
             sender: self.sender.clone(),
             next_stages: next.next_stages,
             processing_thread: self.processing_thread.clone(),
             busy: self.busy.clone(),
+            handles: self.handles.clone(),
         }
     }
 
+    /// Collect the outputs of the pipeline into a channel
+    ///
+    /// Every output of the last stage is also sent to the returned receiver, in order, so the
+    /// results can be read back on the calling thread. The receiver is closed once the
+    /// pipeline is stopped and has passed on its last output, so iterating it ends then.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::concurrent::Pipeline;
+    /// let doubler = Pipeline::new(|x: u64| x * 2);
+    /// let results = doubler.sink();
+    /// doubler.send(1).unwrap();
+    /// doubler.send(2).unwrap();
+    /// doubler.stop().unwrap();
+    /// assert_eq!(results.iter().collect::<Vec<_>>(), vec![2, 4]);
+    /// ```
+    pub fn sink(&self) -> Receiver<O> {
+        let (sender, receiver) = mpsc::channel();
+        self.next_stages
+            .lock()
+            .unwrap()
+            .push(NextStage::Sink(sender));
+        receiver
+    }
+
+    /// Send an input to the first stage of the pipeline
+    ///
+    /// If the first stage is `bounded` and its queue is full, this blocks until there is room.
     pub fn send(&self, input: I) -> Result<(), SendError<Option<I>>> {
         let res = self.sender.send(Some(input));
         self.processing_thread.unpark();
         res
     }
 
+    /// Send an input to the first stage of the pipeline without blocking
+    ///
+    /// This fails with `TrySendError::Full` if the first stage is `bounded` and its queue is full.
+    pub fn try_send(&self, input: I) -> Result<(), TrySendError<Option<I>>> {
+        let res = self.sender.try_send(Some(input));
+        self.processing_thread.unpark();
+        res
+    }
+
+    /// Stop the pipeline once the inputs already sent are processed
+    ///
+    /// The stop is passed on to the downstream stages after the last output, so every stage
+    /// finishes its pending work. This does not wait for the threads, see `shutdown`.
     pub fn stop(&self) -> Result<(), SendError<Option<I>>> {
         let res = self.sender.send(None);
         self.processing_thread.unpark();
         res
     }
 
+    /// Block until the threads of every stage of the pipeline have finished
+    ///
+    /// The threads only finish after `stop`, so this is usually called through `shutdown`.
+    /// If a stage panicked, the first panic is returned once all the threads are joined.
+    pub fn join(&self) -> thread::Result<()> {
+        let handles = std::mem::take(&mut *self.handles.lock().unwrap());
+        let mut result = Ok(());
+        // Join every thread even after a panic, so none is left running
+        for handle in handles {
+            let joined = handle.join();
+            if result.is_ok() {
+                result = joined;
+            }
+        }
+        result
+    }
+
+    /// Stop the pipeline and block until every stage has finished its pending work
+    pub fn shutdown(&self) -> thread::Result<()> {
+        // A pipeline already stopped has no receiver left; joining is all that is left to do
+        let _ = self.stop();
+        self.join()
+    }
+
+    /// Check whether the first stage is processing an input
+    pub fn is_busy(&self) -> bool {
+        self.busy.load(Ordering::SeqCst)
+    }
+
+    /// Get the flag set while the first stage is processing an input, to poll from elsewhere
     pub fn get_busy_flag(&self) -> Arc<AtomicBool> {
         self.busy.clone()
     }
 
-    fn process_loop<T: Participant<I, Output = O>>(
-        participant: T,
+    fn process_loop<P: Fn(I) -> Option<O>>(
+        process: P,
         receiver: Receiver<Option<I>>,
         next_stages: Arc<Mutex<Vec<NextStage<O>>>>,
         busy: Arc<AtomicBool>,
@@ -109,17 +345,29 @@ impl<I, O: Clone> Pipeline<I, O> {
             match receiver.try_recv() {
                 Ok(Some(input)) => {
                     busy.store(true, Ordering::SeqCst);
-                    let output = participant.process(input);
+                    let output = process(input);
                     busy.store(false, Ordering::SeqCst);
+                    // A failed input has no output to pass on
+                    let Some(output) = output else {
+                        continue;
+                    };
 
-                    for stage in next_stages.lock().unwrap().iter() {
-                        stage.send(Some(output.clone()));
+                    // Clone for all but the last stage, which takes the output itself
+                    let stages = next_stages.lock().unwrap();
+                    if let Some((last, others)) = stages.split_last() {
+                        for stage in others {
+                            stage.send(Some(output.clone()));
+                        }
+                        last.send(Some(output));
                     }
                 }
                 Ok(None) => {
-                    for stage in next_stages.lock().unwrap().iter() {
+                    let mut stages = next_stages.lock().unwrap();
+                    for stage in stages.iter() {
                         stage.send(None);
                     }
+                    // Dropping the senders closes the sinks
+                    stages.clear();
                     break;
                 }
                 Err(TryRecvError::Empty) => thread::park(),
@@ -149,8 +397,127 @@ mod tests {
             x * 2
         });
         let combined = adder.connect(multiplier);
+        let results = combined.sink();
         combined.send(5).unwrap();
         combined.send(10).unwrap();
-        thread::sleep(std::time::Duration::from_secs(3));
+        combined.stop().unwrap();
+        assert_eq!(results.iter().collect::<Vec<_>>(), vec![12, 22]);
+    }
+
+    #[test]
+    fn test_three_stage_pipeline_shutdown() {
+        let outputs = Arc::new(Mutex::new(Vec::new()));
+        let outputs_clone = outputs.clone();
+        let adder = Pipeline::new(|x: u64| x + 1);
+        let multiplier = Pipeline::new(|x: u64| x * 2);
+        let collector = Pipeline::new(move |x: u64| outputs_clone.lock().unwrap().push(x));
+        let pipeline = adder.connect(multiplier).connect(collector);
+        for i in 0..5 {
+            pipeline.send(i).unwrap();
+        }
+        pipeline.shutdown().unwrap();
+        assert_eq!(*outputs.lock().unwrap(), vec![2, 4, 6, 8, 10]);
+        assert!(!pipeline.is_busy());
+    }
+
+    #[test]
+    fn test_pipeline_fan_out() {
+        let left = Arc::new(Mutex::new(Vec::new()));
+        let right = Arc::new(Mutex::new(Vec::new()));
+        let (left_clone, right_clone) = (left.clone(), right.clone());
+        let source = Pipeline::new(|x: u64| x * 10);
+        let _left = source.connect(Pipeline::new(move |x: u64| {
+            left_clone.lock().unwrap().push(x)
+        }));
+        let _right = source.connect(Pipeline::new(move |x: u64| {
+            right_clone.lock().unwrap().push(x + 1)
+        }));
+        source.send(1).unwrap();
+        source.send(2).unwrap();
+        source.shutdown().unwrap();
+        assert_eq!(*left.lock().unwrap(), vec![10, 20]);
+        assert_eq!(*right.lock().unwrap(), vec![11, 21]);
+    }
+
+    #[test]
+    fn test_pipeline_routes_errors() {
+        let outputs = Arc::new(Mutex::new(Vec::new()));
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let (outputs_clone, errors_clone) = (outputs.clone(), errors.clone());
+        let adder = Pipeline::new(|x: u64| x + 1);
+        let checked = Pipeline::try_new(
+            |x: u64| {
+                if x.is_multiple_of(3) {
+                    Err(format!("{} is a multiple of 3", x))
+                } else {
+                    Ok(x * 10)
+                }
+            },
+            move |e| errors_clone.lock().unwrap().push(e),
+        );
+        let collector = Pipeline::new(move |x: u64| outputs_clone.lock().unwrap().push(x));
+        let pipeline = adder.connect(checked).connect(collector);
+        for i in 0..6 {
+            pipeline.send(i).unwrap();
+        }
+        pipeline.shutdown().unwrap();
+        assert_eq!(*outputs.lock().unwrap(), vec![10, 20, 40, 50]);
+        assert_eq!(
+            *errors.lock().unwrap(),
+            vec!["3 is a multiple of 3", "6 is a multiple of 3"]
+        );
+    }
+
+    #[test]
+    fn test_bounded_pipeline_throttles_producer() {
+        let outputs = Arc::new(Mutex::new(Vec::new()));
+        let outputs_clone = outputs.clone();
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let gate_rx = Mutex::new(gate_rx);
+        let slow = Pipeline::bounded(
+            move |x: u64| {
+                gate_rx.lock().unwrap().recv().unwrap();
+                outputs_clone.lock().unwrap().push(x);
+            },
+            1,
+        );
+        slow.send(1).unwrap();
+        // Returns once the stage took the first input, which leaves the second one queued
+        slow.send(2).unwrap();
+        while !slow.is_busy() {
+            thread::yield_now();
+        }
+        assert!(matches!(slow.try_send(3), Err(TrySendError::Full(Some(3)))));
+
+        let sent = AtomicBool::new(false);
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                slow.send(3).unwrap();
+                sent.store(true, Ordering::SeqCst);
+            });
+            thread::sleep(std::time::Duration::from_millis(50));
+            assert!(!sent.load(Ordering::SeqCst));
+            gate_tx.send(()).unwrap();
+        });
+        assert!(sent.load(Ordering::SeqCst));
+        for _ in 0..2 {
+            gate_tx.send(()).unwrap();
+        }
+        slow.shutdown().unwrap();
+        assert_eq!(*outputs.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_pipeline_sink() {
+        let squarer = Pipeline::new(|x: u64| x * x);
+        let pipeline = Pipeline::new(|x: u64| x + 1).connect(squarer);
+        let results = pipeline.sink();
+        for i in 0..10 {
+            pipeline.send(i).unwrap();
+        }
+        pipeline.stop().unwrap();
+        let expected: Vec<u64> = (1..=10).map(|x| x * x).collect();
+        assert_eq!(results.iter().collect::<Vec<_>>(), expected);
+        pipeline.join().unwrap();
     }
 }
@@ -1,13 +1,19 @@
 pub mod api;
-//pub mod concurrent;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod concurrent;
 pub mod signal;
 pub mod signal_sync;
-// pub mod signals;
+pub mod signals;
+
+// Lets the paths emitted by the derive macros resolve inside this crate as well
+extern crate self as agility;
 
 pub use agility_macros::*;
 pub use signal::*;
 
 #[test]
+#[allow(clippy::unnecessary_operation)]
 fn it_works() {
     #[derive(Lift)]
     struct Point<'a> {
@@ -30,3 +36,65 @@ fn it_works() {
         lifted.send_with(|p| p.y = 20),
     );
 }
+
+#[test]
+fn lift_bidirectional_writes_back() {
+    #[derive(Lift)]
+    struct Form<'a> {
+        name: Signal<'a, String>,
+        age: Signal<'a, u32>,
+    }
+
+    let name = Signal::new("Ann".to_string());
+    let age = Signal::new(30);
+    let form = Form {
+        name: name.clone(),
+        age: age.clone(),
+    }
+    .lift_bidirectional();
+
+    let greeting = name.map(|name| format!("Hello, {}", name));
+    form.send(_Form {
+        name: "Bob".to_string(),
+        age: 31,
+    });
+    assert_eq!(name.get(), "Bob");
+    assert_eq!(age.get(), 31);
+    assert_eq!(greeting.get(), "Hello, Bob");
+
+    age.send(32);
+    assert_eq!(age.get(), 32);
+    form.with(|form| assert_eq!(form.age, 32));
+}
+
+#[test]
+fn lift_bidirectional_writes_back_sync() {
+    use signal_sync::SignalSync;
+
+    #[derive(LiftSync)]
+    struct Form<'a> {
+        name: SignalSync<'a, String>,
+        age: SignalSync<'a, u32>,
+    }
+
+    let name = SignalSync::new("Ann".to_string());
+    let age = SignalSync::new(30);
+    let form = Form {
+        name: name.clone(),
+        age: age.clone(),
+    }
+    .lift_bidirectional();
+
+    let greeting = name.map(|name| format!("Hello, {}", name));
+    form.send(_Form {
+        name: "Bob".to_string(),
+        age: 31,
+    });
+    assert_eq!(name.get(), "Bob");
+    assert_eq!(age.get(), 31);
+    assert_eq!(greeting.get(), "Hello, Bob");
+
+    age.send(32);
+    assert_eq!(age.get(), 32);
+    form.with(|form| assert_eq!(form.age, 32));
+}
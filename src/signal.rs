@@ -1,16 +1,43 @@
-use std::{cell::RefCell, iter, rc::Rc};
+use std::{
+    any::{Any, TypeId, type_name},
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    hash::Hash,
+    iter, ops,
+    panic::{self, AssertUnwindSafe},
+    rc::{Rc, Weak},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
 
 use crate::api::Liftable;
+use crate::signal_sync::SignalSync;
 
 pub(crate) trait SignalExt<'a> {
-    fn react(&self);
+    fn react(&self) -> Vec<SignalGuardInner<'a>>;
     fn guard(&self) -> SignalGuard<'a>;
     fn decrease_dirty(&self);
     fn get_dirty(&self) -> isize;
     fn clone_box(&self) -> Box<dyn SignalExt<'a> + 'a>;
-    fn collect_guards_recursive(&self, result: &mut Vec<SignalGuardInner<'a>>);
-    fn collect_predecessors_recursive(&self, result: &mut Vec<SignalGuardInner<'a>>);
+    fn collect_guards_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInner<'a>>,
+        visited: &mut HashSet<usize>,
+    );
+    fn collect_predecessors_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInner<'a>>,
+        visited: &mut HashSet<usize>,
+    );
     fn reset_explicitly_modified(&self);
+    fn push_reaction(&self, react_fn: Box<dyn Fn() + 'a>, owner: WeakSignalRef<'a>) -> ReactionId;
+    fn push_successor(&self, successor: WeakSignalRef<'a>);
+    fn return_guard_buffer(&self, buffer: Vec<SignalGuardInner<'a>>);
+    fn write_dot(&self, graph: &mut DotGraph) -> usize;
 }
 
 pub(crate) trait RefStrategy<'a> {
@@ -48,6 +75,9 @@ impl<'a> RefStrategy<'a> for StrongRefStrategy {
 }
 
 pub(crate) struct WeakSignalRef<'a> {
+    addr: usize,
+    /// Whether the signal is only reached through the guard its reaction forwards
+    forwarded: bool,
     upgrade: Box<dyn Fn() -> Option<Box<dyn SignalExt<'a> + 'a>> + 'a>,
 }
 
@@ -55,6 +85,8 @@ impl<'a> WeakSignalRef<'a> {
     pub fn new<T: 'a>(signal: &Signal<'a, T>) -> Self {
         let weak = Rc::downgrade(&signal.0);
         WeakSignalRef {
+            addr: Rc::as_ptr(&signal.0) as *const () as usize,
+            forwarded: false,
             upgrade: Box::new(move || {
                 weak.upgrade()
                     .map(|rc| Box::new(Signal(rc)) as Box<dyn SignalExt<'a> + 'a>)
@@ -62,6 +94,18 @@ impl<'a> WeakSignalRef<'a> {
         }
     }
 
+    /// Refer to a signal that propagation does not mark dirty directly
+    ///
+    /// The signal is still a successor for introspection and pruning, but it is only marked
+    /// dirty by the guard that the reaction feeding it forwards, when that reaction decides
+    /// the signal changed.
+    pub fn forwarded<T: 'a>(signal: &Signal<'a, T>) -> Self {
+        WeakSignalRef {
+            forwarded: true,
+            ..Self::new(signal)
+        }
+    }
+
     pub fn upgrade(&self) -> Option<Box<dyn SignalExt<'a> + 'a>> {
         (self.upgrade)()
     }
@@ -69,49 +113,831 @@ impl<'a> WeakSignalRef<'a> {
     pub fn is_alive(&self) -> bool {
         self.upgrade().is_some()
     }
+
+    /// Check whether this refers to the given signal
+    pub fn points_to<T: 'a>(&self, signal: &Signal<'a, T>) -> bool {
+        self.addr == Rc::as_ptr(&signal.0) as *const () as usize
+    }
+}
+
+/// Identifier of a reaction registered on a signal, used to remove it later
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ReactionId(usize);
+
+impl ReactionId {
+    pub(crate) fn new(id: usize) -> Self {
+        ReactionId(id)
+    }
+}
+
+/// A reaction registered on a signal
+#[derive(Clone)]
+pub(crate) enum Reaction<'a> {
+    /// A reaction that only updates the graph
+    Plain(Rc<dyn Fn() + 'a>),
+    /// A reaction that may forward a guard into the batch it runs in
+    Forwarding(Rc<dyn Fn() -> Option<SignalGuard<'a>> + 'a>),
+}
+
+impl<'a> Reaction<'a> {
+    /// Run the reaction, returning the guard it forwards, if any
+    pub fn run(&self) -> Option<SignalGuard<'a>> {
+        #[cfg(feature = "bench")]
+        crate::bench::record_reaction();
+        match self {
+            Reaction::Plain(react_fn) => {
+                react_fn();
+                None
+            }
+            Reaction::Forwarding(react_fn) => react_fn(),
+        }
+    }
+}
+
+/// The reactions registered on a signal
+pub(crate) struct Reactions<'a> {
+    next_id: usize,
+    entries: Vec<(ReactionId, Reaction<'a>, Option<WeakSignalRef<'a>>)>,
+}
+
+impl<'a> Reactions<'a> {
+    pub fn new() -> Self {
+        Reactions {
+            next_id: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Register a reaction and return its identifier
+    pub fn push(&mut self, react_fn: Box<dyn Fn() + 'a>) -> ReactionId {
+        self.insert(Reaction::Plain(Rc::from(react_fn)), None)
+    }
+
+    /// Register a reaction that feeds `owner` and return its identifier
+    ///
+    /// The reaction is removed by `retain_alive` once its owner has been dropped.
+    pub fn push_owned(
+        &mut self,
+        react_fn: Box<dyn Fn() + 'a>,
+        owner: WeakSignalRef<'a>,
+    ) -> ReactionId {
+        self.insert(Reaction::Plain(Rc::from(react_fn)), Some(owner))
+    }
+
+    /// Register a reaction that forwards a guard and return its identifier
+    pub fn push_forwarding(
+        &mut self,
+        react_fn: Box<dyn Fn() -> Option<SignalGuard<'a>> + 'a>,
+    ) -> ReactionId {
+        self.insert(Reaction::Forwarding(Rc::from(react_fn)), None)
+    }
+
+    /// Register a reaction that forwards a guard to `owner` and return its identifier
+    ///
+    /// The reaction is removed by `retain_alive` once its owner has been dropped.
+    pub fn push_forwarding_owned(
+        &mut self,
+        react_fn: Box<dyn Fn() -> Option<SignalGuard<'a>> + 'a>,
+        owner: WeakSignalRef<'a>,
+    ) -> ReactionId {
+        self.insert(Reaction::Forwarding(Rc::from(react_fn)), Some(owner))
+    }
+
+    fn insert(&mut self, reaction: Reaction<'a>, owner: Option<WeakSignalRef<'a>>) -> ReactionId {
+        let id = ReactionId::new(self.next_id);
+        self.next_id += 1;
+        self.entries.push((id, reaction, owner));
+        id
+    }
+
+    /// Remove a reaction, returning whether it was still registered
+    pub fn remove(&mut self, id: ReactionId) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(entry_id, _, _)| *entry_id != id);
+        self.entries.len() != len
+    }
+
+    /// Remove the reactions whose owner has been dropped
+    pub fn retain_alive(&mut self) {
+        self.entries
+            .retain(|(_, _, owner)| owner.as_ref().is_none_or(|owner| owner.is_alive()));
+    }
+
+    /// Remove the reactions feeding `owner`, returning whether any was registered
+    pub fn remove_owned_by<U: 'a>(&mut self, owner: &Signal<'a, U>) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(_, _, entry_owner)| {
+            !entry_owner
+                .as_ref()
+                .is_some_and(|entry_owner| entry_owner.points_to(owner))
+        });
+        self.entries.len() != len
+    }
+
+    /// Remove every reaction
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Take a snapshot of the reactions so they can run without holding the borrow,
+    /// which lets a reaction register or remove reactions on the same signal
+    pub fn snapshot(&self) -> Vec<Reaction<'a>> {
+        self.entries
+            .iter()
+            .map(|(_, reaction, _)| reaction.clone())
+            .collect()
+    }
 }
 
-/// The inner part of a signal guard
-pub struct SignalGuardInner<'a>(Box<dyn SignalExt<'a> + 'a>);
+/// The inner part of a signal guard, and whether it was reached backwards as a predecessor
+pub struct SignalGuardInner<'a>(Box<dyn SignalExt<'a> + 'a>, bool);
 
 /// Signal guard that triggers reactions on drop
 #[allow(dead_code)]
 #[allow(unused_must_use)]
-pub struct SignalGuard<'a>(Vec<SignalGuardInner<'a>>);
+pub struct SignalGuard<'a>(
+    Vec<SignalGuardInner<'a>>,
+    Option<Box<dyn SignalExt<'a> + 'a>>,
+);
 
 impl<'a> SignalGuard<'a> {
     /// Combine two signal guards into one
+    ///
+    /// The combined guard reacts as if both sends were made at once: each signal reacts once,
+    /// after all the signals it depends on. The signals reached forwards, the ones sent to and
+    /// those derived from them, react first, in the order the guards were combined; the
+    /// predecessors that they write back to, like the source of a `contramap`, react last, again
+    /// in the order the guards were combined. This is the order of a single guard, so combining
+    /// guards never lets a write-back overtake a forward update.
     pub fn and(mut self, mut other: SignalGuard<'a>) -> SignalGuard<'a> {
         self.0.append(&mut other.0);
         self
     }
 }
 
-impl<'a> Drop for SignalGuardInner<'a> {
-    fn drop(&mut self) {
+impl SignalGuard<'static> {
+    /// Settle the guard once the current propagation has settled, instead of right away
+    ///
+    /// A send made by a reaction normally reacts on the spot, in the middle of the propagation
+    /// running that reaction. Deferring its guard queues it instead: the deferred guards settle,
+    /// in the order they were deferred, after every signal of the current propagation has
+    /// reacted. Outside of a propagation, this is the same as dropping the guard.
+    ///
+    /// Only guards of `'static` signals can be deferred, as they are still held after the
+    /// reaction that sent to them has returned. This is also why sends made by reactions are not
+    /// queued by default: a signal created inside a reaction may have observers borrowing the
+    /// locals of that reaction, which are gone by the time the propagation settles.
+    pub fn defer(mut self) {
+        if PROPAGATING.with(Cell::get) {
+            let mut deferred = std::mem::take(&mut self.0);
+            deferred.sort_by_key(|inner| inner.1);
+            DEFERRED.with(|queue| queue.borrow_mut().push_back(deferred));
+        }
+    }
+}
+
+impl<'a> SignalGuardInner<'a> {
+    /// Release the hold on the signal and react once it is no longer dirty
+    ///
+    /// Returns the guards forwarded by the reactions.
+    fn release(&self) -> Vec<SignalGuardInner<'a>> {
         self.0.decrease_dirty();
         if self.0.get_dirty() == 0 {
-            self.0.react();
+            let forwarded = self.0.react();
             self.0.reset_explicitly_modified();
+            forwarded
+        } else {
+            Vec::new()
         }
     }
 }
 
 impl<'a> Drop for SignalGuard<'a> {
     fn drop(&mut self) {
-        // First drop all inner guards (triggers immediate reactions)
-        drop(std::mem::take(&mut self.0));
+        let mut pending: VecDeque<_> = std::mem::take(&mut self.0).into();
+        // Predecessors react last, even when they come from a guard combined before others;
+        // the sort is stable, so the order within both parts is kept
+        pending.make_contiguous().sort_by_key(|inner| inner.1);
+        // A send made by a reaction settles right away, inside the propagation running it
+        let outermost = !PROPAGATING.with(Cell::get);
+        let _propagation = outermost.then(Propagation::start);
+        settle(&mut pending);
+        if outermost {
+            // Settle the deferred sends of the reactions in the order they were made
+            while let Some(deferred) = DEFERRED.with(|queue| queue.borrow_mut().pop_front()) {
+                settle(&mut deferred.into());
+            }
+        }
+        // Hand the buffer back to the signal that caches it for its next send
+        if let Some(home) = self.1.take() {
+            home.return_guard_buffer(pending.into());
+        }
+        // With nothing to report it to, the panic of a reaction surfaces once the graph has settled
+        if outermost
+            && !thread::panicking()
+            && let Some(payload) = UNREPORTED_PANIC.with(|slot| slot.borrow_mut().take())
+        {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+thread_local! {
+    /// Whether a guard is settling on this thread
+    static PROPAGATING: Cell<bool> = const { Cell::new(false) };
+    /// Guards deferred by reactions, waiting for the current propagation to settle
+    static DEFERRED: RefCell<VecDeque<Vec<SignalGuardInner<'static>>>> = const { RefCell::new(VecDeque::new()) };
+    /// The first panic of a reaction with no error sink to report it to
+    static UNREPORTED_PANIC: RefCell<Option<Box<dyn Any + Send>>> = const { RefCell::new(None) };
+}
+
+/// An event of the propagation machinery, reported to the hook installed with `set_trace_hook`
+///
+/// Each event carries the `trace_id` of the signal it concerns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// A send started propagating from the signal
+    Propagate(usize),
+    /// The signal was marked dirty, to react once the propagation settles
+    MarkDirty(usize),
+    /// The signal ran its reactions
+    React(usize),
+}
+
+/// Hook receiving the trace events of the current thread
+type TraceHook = Rc<dyn Fn(TraceEvent)>;
+
+/// The number of threads with a trace hook installed, so tracing costs a single load otherwise
+static TRACE_HOOKS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The trace hook of this thread, if one is installed
+    static TRACE_HOOK: RefCell<Option<TraceHook>> = const { RefCell::new(None) };
+}
+
+/// Install a hook observing the propagation of the signals used on this thread
+///
+/// The hook is called with a `TraceEvent` whenever a send starts propagating, a signal is marked
+/// dirty and a signal reacts, which shows how an update flows through the graph without
+/// wrapping the closures of every signal. It replaces the hook previously installed on this
+/// thread, if any, and stays installed until `clear_trace_hook`. While no thread has a hook,
+/// tracing is a single atomic load per event.
+///
+/// # Example
+/// ```rust
+/// # use agility::{Signal, TraceEvent, clear_trace_hook, set_trace_hook};
+/// let a = Signal::new(1);
+/// let _b = a.map(|x| x * 2);
+/// set_trace_hook(|event| {
+///     if let TraceEvent::React(id) = event {
+///         println!("signal {:x} reacted", id);
+///     }
+/// });
+/// a.send(2); // prints two lines, for `a` then `_b`
+/// clear_trace_hook();
+/// ```
+pub fn set_trace_hook(hook: impl Fn(TraceEvent) + 'static) {
+    let previous = TRACE_HOOK.with(|current| current.replace(Some(Rc::new(hook))));
+    if previous.is_none() {
+        TRACE_HOOKS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Remove the trace hook installed on this thread with `set_trace_hook`, if any
+pub fn clear_trace_hook() {
+    if TRACE_HOOK.with(RefCell::take).is_some() {
+        TRACE_HOOKS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Report an event to the trace hook of this thread, if one is installed
+fn trace(event: TraceEvent) {
+    if TRACE_HOOKS.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    // Take the hook out of the cell first, so it may itself send or replace the hook
+    if let Some(hook) = TRACE_HOOK.with(|current| current.borrow().clone()) {
+        hook(event);
+    }
+}
+
+/// Marks the thread as propagating until dropped, including when a reaction panics
+struct Propagation;
+
+impl Propagation {
+    fn start() -> Self {
+        PROPAGATING.with(|propagating| propagating.set(true));
+        Propagation
+    }
+}
+
+impl Drop for Propagation {
+    fn drop(&mut self) {
+        PROPAGATING.with(|propagating| propagating.set(false));
+        if thread::panicking() {
+            DEFERRED.with(|queue| queue.borrow_mut().clear());
+        }
+    }
+}
+
+/// Release the inner guards in order (triggers immediate reactions);
+/// guards forwarded by reactions are queued so they settle in the same batch
+fn settle(pending: &mut VecDeque<SignalGuardInner<'_>>) {
+    while let Some(inner) = pending.pop_front() {
+        pending.extend(inner.release());
+    }
+}
+
+/// A batch held open across a block, flushed when the scope is dropped
+///
+/// Every send made through the scope keeps its guard in the scope, so the reactions of all the
+/// signals sent to run once, together, when the scope is dropped or `flush`ed. Unlike the tuple
+/// idiom `(a.send(1), b.send(2))`, which only batches the sends written in a single expression,
+/// a scope is a value that can be filled over several statements and passed to other functions.
+/// `batch` opens one for the duration of a closure.
+///
+/// # Example
+/// ```rust
+/// # use agility::{Signal, BatchScope};
+/// let a = Signal::new(1);
+/// let b = Signal::new(2);
+/// let pair = a.combine(&b);
+/// let sum = pair.map(|(x, y)| x + y);
+/// let _observer = sum.map(|s| println!("sum: {}", s));
+/// let mut scope = BatchScope::new();
+/// scope.send(&a, 10);
+/// scope.send(&b, 20);
+/// drop(scope); // prints "sum: 30" once
+/// ```
+#[derive(Default)]
+pub struct BatchScope<'a> {
+    guard: Option<SignalGuard<'a>>,
+}
+
+impl<'a> BatchScope<'a> {
+    /// Create an empty batch scope
+    pub fn new() -> Self {
+        BatchScope { guard: None }
+    }
+
+    /// Send a new value to a signal, deferring its reactions to the end of the scope
+    pub fn send<T: 'a>(&mut self, signal: &Signal<'a, T>, new_value: T) -> &mut Self {
+        self.hold(signal.send(new_value))
+    }
+
+    /// Send a modification to a signal, deferring its reactions to the end of the scope
+    pub fn send_with<T: 'a, F>(&mut self, signal: &Signal<'a, T>, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut T),
+    {
+        self.hold(signal.send_with(f))
+    }
+
+    /// Hold an existing guard in the scope until it ends
+    pub fn hold(&mut self, guard: SignalGuard<'a>) -> &mut Self {
+        self.guard = Some(match self.guard.take() {
+            Some(held) => held.and(guard),
+            None => guard,
+        });
+        self
+    }
+
+    /// End the scope now, running the reactions of every signal sent through it
+    pub fn flush(self) {}
+}
+
+/// Run a closure as a single batch, so every signal sent to in it reacts once, at the end
+///
+/// The closure receives a `BatchScope` and every send made through it is held until `f`
+/// returns, like the tuple idiom `(a.send(1), b.send(2))` but spanning any number of statements;
+/// a stray `;` can't fire reactions early. The held guards live in the scope rather than in a
+/// thread-local, so they can't outlive the signals sent through it. To nest, pass the scope on
+/// rather than opening another batch.
+///
+/// # Example
+/// ```rust
+/// # use agility::{Signal, batch};
+/// let a = Signal::new(1);
+/// let b = Signal::new(2);
+/// let sum = a.combine_map(&b, |x, y| x + y);
+/// let _observer = sum.map(|s| println!("sum: {}", s));
+/// batch(|scope| {
+///     scope.send(&a, 10);
+///     scope.send(&b, 20);
+/// }); // prints "sum: 30" once
+/// ```
+pub fn batch<'a, R>(f: impl FnOnce(&mut BatchScope<'a>) -> R) -> R {
+    let mut scope = BatchScope::new();
+    let result = f(&mut scope);
+    scope.flush();
+    result
+}
+
+/// Observer that does not keep its source alive and unregisters itself when dropped
+pub struct WeakObserver<'a> {
+    detach: Option<Box<dyn FnOnce() + 'a>>,
+}
+
+impl<'a> Drop for WeakObserver<'a> {
+    fn drop(&mut self) {
+        if let Some(detach) = self.detach.take() {
+            detach();
+        }
+    }
+}
+
+/// Handle of a subscription made with `subscribe`, which unsubscribes when dropped
+pub struct Subscription<'a> {
+    detach: Option<Box<dyn FnOnce() + 'a>>,
+}
+
+impl<'a> Drop for Subscription<'a> {
+    fn drop(&mut self) {
+        if let Some(detach) = self.detach.take() {
+            detach();
+        }
+    }
+}
+
+/// Guard returned by `freeze`, which unfreezes the signal when dropped
+pub struct FreezeGuard<'a> {
+    thaw: Option<Box<dyn FnOnce() + 'a>>,
+}
+
+impl<'a> Drop for FreezeGuard<'a> {
+    fn drop(&mut self) {
+        if let Some(thaw) = self.thaw.take() {
+            thaw();
+        }
+    }
+}
+
+type Cleanup<'a> = Box<dyn FnOnce() + 'a>;
+
+/// Value returned by each run of an effect, cleaning up after it
+///
+/// An effect returning `()` has nothing to clean up. An effect returning a closure has it run
+/// once, right before the effect runs again and when its `Effect` is dropped.
+pub trait EffectCleanup<'a> {
+    /// Turn the value into the closure to run, if any
+    fn into_cleanup(self) -> Option<Cleanup<'a>>;
+}
+
+impl<'a> EffectCleanup<'a> for () {
+    fn into_cleanup(self) -> Option<Cleanup<'a>> {
+        None
+    }
+}
+
+impl<'a, F: FnOnce() + 'a> EffectCleanup<'a> for F {
+    fn into_cleanup(self) -> Option<Cleanup<'a>> {
+        Some(Box::new(self))
+    }
+}
+
+/// Handle of an effect made with `effect`, which stops it and runs its last cleanup when dropped
+pub struct Effect<'a> {
+    subscription: Option<Subscription<'a>>,
+    cleanup: Rc<RefCell<Option<Cleanup<'a>>>>,
+}
+
+impl<'a> Drop for Effect<'a> {
+    fn drop(&mut self) {
+        // Unsubscribe first, so the effect can't run again after its last cleanup
+        drop(self.subscription.take());
+        let cleanup = self.cleanup.borrow_mut().take();
+        if let Some(cleanup) = cleanup {
+            cleanup();
+        }
+    }
+}
+
+/// Signal whose value type has been erased
+///
+/// This allows storing differently-typed signals in the same collection. The concrete signal
+/// can be recovered with `downcast` or `downcast_ref`.
+pub struct BoxedSignal<'a> {
+    type_id: TypeId,
+    type_name: &'static str,
+    signal: Box<dyn SignalExt<'a> + 'a>,
+}
+
+/// Type alias for signals that live for the whole program
+pub type StaticSignal<T> = Signal<'static, T>;
+
+/// Type alias for erased signals that live for the whole program
+pub type StaticBoxedSignal = BoxedSignal<'static>;
+
+impl<'a> BoxedSignal<'a> {
+    /// Get the name of the erased value type
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Check whether the erased value type is `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Get a reference to the concrete signal if the value type is `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&Signal<'a, T>> {
+        if self.is::<T>() {
+            // SAFETY: the box was created by `erase` from a `Signal<'a, T>` with the same `TypeId`
+            let ptr =
+                self.signal.as_ref() as *const (dyn SignalExt<'a> + 'a) as *const Signal<'a, T>;
+            Some(unsafe { &*ptr })
+        } else {
+            None
+        }
+    }
+
+    /// Recover the concrete signal if the value type is `T`, or give back the erased signal
+    pub fn downcast<T: 'static>(self) -> Result<Signal<'a, T>, Self> {
+        if self.is::<T>() {
+            // SAFETY: the box was created by `erase` from a `Signal<'a, T>` with the same `TypeId`
+            let ptr = Box::into_raw(self.signal) as *mut Signal<'a, T>;
+            Ok(*unsafe { Box::from_raw(ptr) })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Clone for BoxedSignal<'_> {
+    fn clone(&self) -> Self {
+        BoxedSignal {
+            type_id: self.type_id,
+            type_name: self.type_name,
+            signal: self.signal.clone_box(),
+        }
+    }
+}
+
+/// Signal of any value type, used to declare the dependencies of a computed signal
+///
+/// It is implemented by `Signal` and `BoxedSignal`.
+pub trait ErasedSignal<'a> {
+    /// Get a type-erased handle to the signal
+    fn handle(&self) -> SignalHandle<'a>;
+}
+
+/// Type-erased handle to a signal
+pub struct SignalHandle<'a>(Box<dyn SignalExt<'a> + 'a>);
+
+impl<'a, T: 'a> ErasedSignal<'a> for Signal<'a, T> {
+    fn handle(&self) -> SignalHandle<'a> {
+        SignalHandle(self.clone_box())
+    }
+}
+
+impl<'a> ErasedSignal<'a> for BoxedSignal<'a> {
+    fn handle(&self) -> SignalHandle<'a> {
+        SignalHandle(self.signal.clone_box())
+    }
+}
+
+/// Error reported by a reaction to the error sink of its graph
+///
+/// Unlike a panic, this is an expected, recoverable error that a reaction chose to report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReactionError {
+    message: String,
+}
+
+impl ReactionError {
+    /// Create a new reaction error with the given message
+    pub fn new(message: impl Into<String>) -> Self {
+        ReactionError {
+            message: message.into(),
+        }
+    }
+
+    /// Get the message of the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ReactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reaction error: {}", self.message)
+    }
+}
+
+impl std::error::Error for ReactionError {}
+
+/// A Graphviz rendering of a signal graph in progress, built by `to_dot`
+///
+/// Signals are identified by the address of their shared state, so clones of a signal are a
+/// single node, and numbered in the order they are first reached.
+#[derive(Default)]
+pub(crate) struct DotGraph {
+    ids: HashMap<usize, usize>,
+    nodes: String,
+    edges: String,
+}
+
+impl DotGraph {
+    /// Get the id of the signal at `addr`, adding its node if it is new
+    ///
+    /// Returns the id and whether the node was new, in which case its edges still have to be
+    /// added.
+    pub fn node(
+        &mut self,
+        addr: usize,
+        label: Option<&str>,
+        dirty: bool,
+        explicitly_modified: bool,
+    ) -> (usize, bool) {
+        if let Some(&id) = self.ids.get(&addr) {
+            return (id, false);
+        }
+        let id = self.ids.len();
+        self.ids.insert(addr, id);
+        let mut text = format!("#{}", id);
+        if let Some(label) = label {
+            text.push_str(&format!(" {}", label));
+        }
+        if dirty {
+            text.push_str(" (dirty)");
+        }
+        if explicitly_modified {
+            text.push_str(" (modified)");
+        }
+        self.nodes
+            .push_str(&format!("    n{} [label={:?}];\n", id, text));
+        (id, true)
+    }
+
+    /// Add an edge from a signal to one it derives, or to one it writes back to if `backward`
+    pub fn edge(&mut self, from: usize, to: usize, backward: bool) {
+        let style = if backward { " [style=dashed]" } else { "" };
+        self.edges
+            .push_str(&format!("    n{} -> n{}{};\n", from, to, style));
+    }
+
+    /// Get the DOT source of the graph
+    pub fn finish(self) -> String {
+        format!("digraph {{\n{}{}}}\n", self.nodes, self.edges)
+    }
+}
+
+/// Get the message a panic was raised with, if it has one
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// A change of a keyed list, as emitted by `diff_keyed`
+///
+/// Positions of removed elements refer to the previous list; all other positions refer to
+/// the new list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListChange<K, T> {
+    /// An element with a new key was inserted at `index`
+    Insert { key: K, index: usize, value: T },
+    /// The element with `key` was removed from `index`
+    Remove { key: K, index: usize },
+    /// The element with `key` moved from `from` to `to`
+    Move { key: K, from: usize, to: usize },
+    /// The element with `key` at `index` got a new value
+    Update { key: K, index: usize, value: T },
+}
+
+/// Compute the changes turning the `prev` keyed list into the `next` one
+///
+/// Only the elements that left the longest run of elements keeping their relative order are
+/// reported as moved, so a single element moving produces a single `Move`.
+pub(crate) fn diff_keyed_changes<K, T>(prev: &[(K, T)], next: &[(K, T)]) -> Vec<ListChange<K, T>>
+where
+    K: Eq + Hash + Clone,
+    T: Clone + PartialEq,
+{
+    let prev_index: HashMap<&K, usize> =
+        prev.iter().enumerate().map(|(i, (k, _))| (k, i)).collect();
+    let next_keys: HashSet<&K> = next.iter().map(|(k, _)| k).collect();
+    let mut changes = Vec::new();
+
+    for (index, (key, _)) in prev.iter().enumerate().rev() {
+        if !next_keys.contains(key) {
+            changes.push(ListChange::Remove {
+                key: key.clone(),
+                index,
+            });
+        }
+    }
+
+    // Old positions of the kept elements, in their new order
+    let kept: Vec<usize> = next
+        .iter()
+        .filter_map(|(k, _)| prev_index.get(k).copied())
+        .collect();
+    let in_place = longest_increasing_run(&kept);
+    let mut kept_seen = 0;
+    for (index, (key, value)) in next.iter().enumerate() {
+        let Some(&from) = prev_index.get(key) else {
+            changes.push(ListChange::Insert {
+                key: key.clone(),
+                index,
+                value: value.clone(),
+            });
+            continue;
+        };
+        if !in_place[kept_seen] {
+            changes.push(ListChange::Move {
+                key: key.clone(),
+                from,
+                to: index,
+            });
+        }
+        kept_seen += 1;
+        if prev[from].1 != *value {
+            changes.push(ListChange::Update {
+                key: key.clone(),
+                index,
+                value: value.clone(),
+            });
+        }
+    }
+    changes
+}
+
+/// Mark the members of one longest strictly increasing subsequence of `seq`
+fn longest_increasing_run(seq: &[usize]) -> Vec<bool> {
+    // `tails[l]` is the position in `seq` of the smallest tail of a run of length `l + 1`
+    let mut tails: Vec<usize> = Vec::new();
+    let mut parent: Vec<Option<usize>> = vec![None; seq.len()];
+    for (i, &x) in seq.iter().enumerate() {
+        let len = tails.partition_point(|&t| seq[t] < x);
+        parent[i] = len.checked_sub(1).map(|l| tails[l]);
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut members = vec![false; seq.len()];
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        members[i] = true;
+        current = parent[i];
+    }
+    members
+}
+
+/// Handler for the errors reported to an error sink
+type ErrorHandler<'a> = Box<dyn Fn(&ReactionError) + 'a>;
+
+/// The error handlers shared by a graph of signals
+pub(crate) struct ErrorSink<'a> {
+    handlers: RefCell<Vec<ErrorHandler<'a>>>,
+}
+
+impl<'a> ErrorSink<'a> {
+    pub fn new() -> Self {
+        ErrorSink {
+            handlers: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Register a handler for the errors reported to the sink
+    pub fn subscribe(&self, handler: ErrorHandler<'a>) {
+        self.handlers.borrow_mut().push(handler);
+    }
+
+    /// Report an error to every registered handler, returning whether there was any
+    pub fn report(&self, error: &ReactionError) -> bool {
+        let handlers = self.handlers.borrow();
+        handlers.iter().for_each(|handler| handler(error));
+        !handlers.is_empty()
     }
 }
 
+/// Custom equality predicate used for change detection
+type EqFn<'a, T> = Box<dyn Fn(&T, &T) -> bool + 'a>;
+
 /// The inner data of a signal
 pub struct SignalInner<'a, T> {
     pub(crate) value: RefCell<T>,
-    pub(crate) react_fns: RefCell<Vec<Box<dyn Fn() + 'a>>>,
+    pub(crate) react_fns: RefCell<Reactions<'a>>,
+    pub(crate) eager_react_fns: RefCell<Reactions<'a>>,
     pub(crate) successors: RefCell<Vec<WeakSignalRef<'a>>>,
     pub(crate) predecessors: RefCell<Vec<WeakSignalRef<'a>>>,
     pub(crate) dirty: RefCell<isize>,
     pub(crate) explicitly_modified: RefCell<bool>,
+    pub(crate) eagerly_reacted: RefCell<bool>,
+    pub(crate) frozen: RefCell<usize>,
+    pub(crate) missed_while_frozen: RefCell<bool>,
+    pub(crate) sent_while_frozen: RefCell<bool>,
+    pub(crate) label: RefCell<Option<&'static str>>,
+    pub(crate) eq: RefCell<Option<EqFn<'a, T>>>,
+    pub(crate) guard_scratch: RefCell<Option<Vec<SignalGuardInner<'a>>>>,
+    #[cfg(test)]
+    pub(crate) reactions: std::sync::atomic::AtomicUsize,
+    pub(crate) error_sink: RefCell<Option<Rc<ErrorSink<'a>>>>,
 }
 
 /// Signal representing a reactive value
@@ -122,849 +948,5799 @@ impl<'a, T: 'a> Signal<'a, T> {
     pub fn new(initial: T) -> Self {
         let inner = Rc::new(SignalInner {
             value: RefCell::new(initial),
-            react_fns: RefCell::new(Vec::new()),
+            react_fns: RefCell::new(Reactions::new()),
+            eager_react_fns: RefCell::new(Reactions::new()),
             successors: RefCell::new(Vec::new()),
             predecessors: RefCell::new(Vec::new()),
             dirty: RefCell::new(0),
             explicitly_modified: RefCell::new(false),
+            eagerly_reacted: RefCell::new(false),
+            frozen: RefCell::new(0),
+            missed_while_frozen: RefCell::new(false),
+            sent_while_frozen: RefCell::new(false),
+            label: RefCell::new(None),
+            eq: RefCell::new(None),
+            guard_scratch: RefCell::new(None),
+            #[cfg(test)]
+            reactions: std::sync::atomic::AtomicUsize::new(0),
+            error_sink: RefCell::new(None),
         });
         Signal(inner)
     }
 
-    /// Helper: Temporarily take value without cloning using MaybeUninit
-    #[inline]
-    fn take_value<U>(cell: &RefCell<U>) -> U {
-        let mut temp = unsafe { std::mem::MaybeUninit::<U>::uninit().assume_init() };
-        std::mem::swap(&mut *cell.borrow_mut(), &mut temp);
-        temp
-    }
-
-    /// Send a new value to the signal
+    /// Attach a human readable label to the signal
     ///
-    /// This will replace the current value of the signal with the new value.
-    /// The signals that depend on this signal will be notified and updated accordingly.
+    /// The label is shared by all clones of the signal and is included in diagnostics
+    /// such as debug output, so large graphs can be inspected without relying on addresses.
     ///
-    /// It returns a `SignalGuard` that ensures reactions are processed when dropped and
-    /// prevents premature reactions during multiple sends. (Batch updates)
     /// # Example
     /// ```rust
-    /// let signal = Signal::new(0);
-    /// signal.send(42); // sets the signal's value to 42
-    ///
-    /// signal.with(|v| println!("Signal value: {}", v));
-    /// (signal.send(66), signal.send(100));
-    /// // sets the signal's value to 100 and prints "Signal value: 100" only once
+    /// # use agility::Signal;
+    /// let temperature = Signal::new(20).with_label("temperature");
+    /// assert_eq!(temperature.label(), Some("temperature"));
     /// ```
-    pub fn send(&self, new_value: T) -> SignalGuard<'a> {
-        self.modify(|v| *v = new_value);
-        *self.0.explicitly_modified.borrow_mut() = true;
-        self.guard()
+    pub fn with_label(self, label: &'static str) -> Self {
+        *self.0.label.borrow_mut() = Some(label);
+        self
     }
 
-    /// Send a modification to the signal
+    /// Get the label attached with `with_label`, if any
+    pub fn label(&self) -> Option<&'static str> {
+        *self.0.label.borrow()
+    }
+
+    /// Get the id identifying the signal in the events of `set_trace_hook`
     ///
-    /// This will apply the provided function to modify the current value of the signal.
-    /// The signals that depend on this signal will be notified and updated accordingly.
+    /// This is the address of the state shared by all clones of the signal, so it is unique among
+    /// the live signals but may be reused once the signal is dropped.
+    pub fn trace_id(&self) -> usize {
+        self.addr()
+    }
+
+    /// Get a clone of the current value of the signal
     ///
-    /// It returns a `SignalGuard` that ensures reactions are processed when dropped and
-    /// prevents premature reactions during multiple sends. (Batch updates)
-    pub fn send_with<F>(&self, f: F) -> SignalGuard<'a>
+    /// Unlike `map` or `with`, this does not create a derived signal or register a reaction,
+    /// so no graph edge is added. This is useful in imperative code, like an event handler,
+    /// that only needs the latest value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let count = Signal::new(1);
+    /// count.send(2);
+    /// assert_eq!(count.get(), 2);
+    /// ```
+    pub fn get(&self) -> T
     where
-        F: FnOnce(&mut T),
+        T: Clone,
     {
-        self.modify(f);
-        self.guard()
+        self.0.value.borrow().clone()
     }
 
-    pub fn set(&mut self, signal: Signal<'a, T>) {
-        self.0 = signal.0;
+    /// Read the current value of the signal through a closure
+    ///
+    /// This borrows the value, runs `f` on it and returns its result, without cloning the value,
+    /// creating a derived signal or registering a reaction. The borrow is released before
+    /// `peek` returns, so the signal can be sent to right after; sending to it from inside `f`
+    /// panics, since the value is still borrowed there.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let names = Signal::new(vec![String::from("ada"), String::from("grace")]);
+    /// let count = names.peek(|n| n.len());
+    /// names.send(vec![]); // fine, the borrow has been released
+    /// assert_eq!(count, 2);
+    /// ```
+    pub fn peek<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        let value = self.0.value.borrow();
+        f(&value)
     }
 
-    /// Map the signal to a new signal
+    /// Get how many times the signal has reacted, for tests
+    #[cfg(test)]
+    pub(crate) fn reaction_count(&self) -> usize {
+        self.0.reactions.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Install a custom equality predicate used for change detection
     ///
-    /// This creates a new signal that depends on the current signal.
-    /// Changes to the source signal will propagate to the new signal.
+    /// The predicate decides whether a new value is the same as the current one for
+    /// `send_if_changed` and `dedupe`, so change detection works for types that can't or
+    /// shouldn't implement `PartialEq`, like floats compared with a tolerance or structs with
+    /// ignored fields. Types that implement `PartialEq` can simply use `PartialEq::eq`.
+    /// Without a predicate, every value is considered a change.
     ///
     /// # Example
     /// ```rust
-    /// let a = Signal::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// let _observer = b.map(|x| println!("b changed: {}", x));
-    /// a.send(5); // prints "b changed: 10"
+    /// # use agility::Signal;
+    /// let a = Signal::new(1.0_f64).with_eq(|x, y| (x - y).abs() < 1e-9);
+    /// let _observer = a.map(|x| println!("a changed: {}", x));
+    /// a.send_if_changed(1.0 + 1e-12); // prints nothing
+    /// a.send_if_changed(2.0); // prints "a changed: 2"
     /// ```
-    pub fn map<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    pub fn with_eq<F>(self, eq: F) -> Self
     where
-        F: Fn(&T) -> U + 'a,
+        F: Fn(&T, &T) -> bool + 'a,
     {
-        self.map_ref::<U, F, WeakRefStrategy>(f)
+        *self.0.eq.borrow_mut() = Some(Box::new(eq));
+        self
     }
 
-    /// Map the signal to a new signal with strong references
+    /// Reuse the guard buffer of the signal across sends
     ///
-    /// This creates a new signal that depends on the current signal.
-    /// Changes to the source signal will propagate to the new signal.
-    /// This mapping uses strong references.
+    /// Every `send` collects the signals to update into a freshly allocated buffer. With this
+    /// enabled the signal keeps the buffer of its last send and reuses it, cleared, for the
+    /// next one, so a signal sent in a tight loop with a stable graph doesn't allocate it on
+    /// every send. A send made while the buffer is in use, e.g. from a reaction to the same
+    /// signal, falls back to a fresh buffer.
     ///
     /// # Example
     /// ```rust
-    /// let a = Signal::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// b.with(|x| println!("b changed: {}", x));
-    /// a.send(5); // prints "b changed: 10"
+    /// # use agility::Signal;
+    /// let a = Signal::new(0).cache_last_guard();
+    /// let _observer = a.map(|x| x + 1);
+    /// for i in 0..1000 {
+    ///     a.send(i); // allocates the guard buffer only on the first send
+    /// }
     /// ```
-    pub fn with<U: 'a, F>(&self, f: F) -> Signal<'a, U>
-    where
-        F: Fn(&T) -> U + 'a,
-    {
-        self.map_ref::<U, F, StrongRefStrategy>(f)
+    pub fn cache_last_guard(self) -> Self {
+        self.0
+            .guard_scratch
+            .borrow_mut()
+            .get_or_insert_with(Vec::new);
+        self
     }
 
-    fn map_ref<U: 'a, F, S: RefStrategy<'a>>(&self, f: F) -> Signal<'a, U>
+    /// Send a new value only if it differs from the current one
+    ///
+    /// The values are compared with the predicate installed by `with_eq`. If the new value is
+    /// the same, nothing is sent and `None` is returned, otherwise this behaves like `send`.
+    pub fn send_if_changed(&self, new_value: T) -> Option<SignalGuard<'a>> {
+        if self.is_unchanged(&self.0.value.borrow(), &new_value) {
+            return None;
+        }
+        Some(self.send(new_value))
+    }
+
+    /// Create a signal that only propagates actual changes of the current signal
+    ///
+    /// The new signal is updated, and its observers notified, only when the value of the
+    /// current signal differs from the last propagated one according to the predicate
+    /// installed by `with_eq` on the current signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1).with_eq(PartialEq::eq);
+    /// let deduped = a.dedupe();
+    /// let _observer = deduped.map(|x| println!("deduped: {}", x));
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints "deduped: 2"
+    /// ```
+    pub fn dedupe(&self) -> Signal<'a, T>
     where
-        F: Fn(&T) -> U + 'a,
+        T: Clone,
     {
-        let new_signal = Signal::new(f(&self.0.value.borrow()));
-        let result_new_signal = new_signal.clone();
-
-        let new_signal_ref = S::new_ref(&new_signal.0);
-        let source_ref = S::new_ref(&self.0);
+        let new_signal = Signal::new(self.0.value.borrow().clone());
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
 
         let react_fn = Box::new(move || {
-            if let Some(new_sig_inner) = S::upgrade(&new_signal_ref) {
-                if !*new_sig_inner.explicitly_modified.borrow() {
-                    if let Some(src_inner) = S::upgrade(&source_ref) {
-                        let new_value = f(&src_inner.value.borrow());
-                        *new_sig_inner.value.borrow_mut() = new_value;
-                    }
-                }
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
             }
+            let source = Signal(src);
+            let value = source.0.value.borrow().clone();
+            if source.is_unchanged(&new_sig.value.borrow(), &value) {
+                return None;
+            }
+            *new_sig.value.borrow_mut() = value;
+            Some(Signal(new_sig).guard())
         });
 
-        self.0.react_fns.borrow_mut().push(react_fn);
-        self.0
-            .successors
-            .borrow_mut()
-            .push(WeakSignalRef::new(&new_signal));
-
-        result_new_signal
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
     }
 
-    /// Map the signal contravariantly to a new signal
+    /// Map each value to an inner signal and mirror the latest one
     ///
-    /// This creates a new signal that the current signal depends on.
-    /// Changes to the new signal will propagate back to the original signal.
-    /// It is inspired by the concept of contravariant functors in category theory.
+    /// This creates a new signal that mirrors the inner signal returned by `f` for the current
+    /// value. Each change of the current signal calls `f` again and switches over to the new
+    /// inner signal, unsubscribing from the previous one so its later changes are ignored. The
+    /// inner signal in use is kept alive by the new signal. This is `map` followed by `flatten`,
+    /// without the intermediate signal of signals.
     ///
     /// # Example
     /// ```rust
-    /// let result = Signal::new(42);
-    /// let source = result.contramap(|x| x * 2);
-    /// result.with(|x| println!("result changed: {}", x));
-    /// source.with(|x| println!("source changed: {}", x));
-    /// source.send(100);
-    /// // prints "source changed: 100" and "result changed: 50"
+    /// # use agility::Signal;
+    /// let rooms = vec![Signal::new("lobby: empty"), Signal::new("kitchen: empty")];
+    /// let selected = Signal::new(0);
+    /// let rooms_clone = rooms.clone();
+    /// let shown = selected.switch_map(move |i| rooms_clone[*i].clone());
+    /// let _observer = shown.map(|s| println!("{}", s)); // prints "lobby: empty"
+    /// selected.send(1); // prints "kitchen: empty"
+    /// rooms[0].send("lobby: busy"); // prints nothing, the lobby is no longer shown
+    /// rooms[1].send("kitchen: busy"); // prints "kitchen: busy"
     /// ```
-    pub fn contramap<F, U>(&self, f: F) -> Signal<'a, U>
+    pub fn switch_map<U, F>(&self, f: F) -> Signal<'a, U>
     where
-        F: Fn(&U) -> T + 'a,
-        U: Default + 'a,
+        U: Clone + 'a,
+        F: Fn(&T) -> Signal<'a, U> + 'a,
     {
-        let new_signal = Signal::new(U::default());
-        let result_new_signal = new_signal.clone();
-        let source_inner = Rc::downgrade(&self.0);
-        let new_signal_rc = Rc::downgrade(&new_signal.0);
+        let inner = f(&self.0.value.borrow());
+        let new_signal = Signal::new(inner.0.value.borrow().clone());
+        let mut current = None;
+        Signal::switch_inner(&mut current, &new_signal, inner);
+        let subscription = RefCell::new(current);
 
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
         let react_fn = Box::new(move || {
-            if let Some(new_sig) = new_signal_rc.upgrade() {
-                if *new_sig.explicitly_modified.borrow() {
-                    let u_value_ref = new_sig.value.borrow();
-                    let t_value = f(&u_value_ref);
-                    drop(u_value_ref);
-
-                    if let Some(source) = source_inner.upgrade() {
-                        *source.value.borrow_mut() = t_value;
-                        *source.explicitly_modified.borrow_mut() = true;
-                    }
-                }
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let inner = f(&src.value.borrow());
+                Signal::switch_inner(&mut subscription.borrow_mut(), &Signal(new_sig), inner);
             }
         });
-        new_signal.0.react_fns.borrow_mut().push(react_fn);
+        self.push_derived(react_fn, &new_signal);
 
         new_signal
-            .0
-            .predecessors
-            .borrow_mut()
-            .push(WeakSignalRef::new(self));
-
-        result_new_signal
     }
 
-    /// Map the signal bidirectionally to a new signal
+    /// Create a signal holding the previous and the current value of the current signal
     ///
-    /// This creates a new signal that depends on the current signal,
-    /// and the current signal also depends on the new signal.
-    /// Changes to either signal will propagate to the other signal.
-    /// It is inspired by the concept of profunctors in category theory.
+    /// The new signal holds `(previous, current)` and is updated on each change of the current
+    /// signal, which makes it easy to react to transitions. It starts with the current value in
+    /// both slots, so the first change is reported as a transition from the initial value.
     ///
     /// # Example
     /// ```rust
-    /// let a = Signal::new(10);
-    /// let b = a.promap(|x| x * 2, |y| y / 2);
-    /// a.with(|x| println!("a changed: {}", x));
-    /// b.with(|x| println!("b changed: {}", x));
-    /// a.send(5); // prints "a changed: 5" and "b changed: 10"
-    /// b.send(50); // prints "b changed: 50" and "a changed: 25"
+    /// # use agility::Signal;
+    /// let position = Signal::new(0);
+    /// let moves = position.pairwise();
+    /// let _observer = moves.map(|(from, to)| println!("moved from {} to {}", from, to));
+    /// position.send(3); // prints "moved from 0 to 3"
+    /// position.send(5); // prints "moved from 3 to 5"
     /// ```
-    pub fn promap<F, G, U>(&self, f: F, g: G) -> Signal<'a, U>
+    pub fn pairwise(&self) -> Signal<'a, (T, T)>
     where
-        F: Fn(&T) -> U + 'a,
-        G: Fn(&U) -> T + 'a,
-        U: Default + 'a,
+        T: Clone,
     {
-        let new_signal = Signal::new(U::default());
-        let result_new_signal = new_signal.clone();
-        let source_weak = Rc::downgrade(&self.0);
+        let initial = self.0.value.borrow().clone();
+        let new_signal = Signal::new((initial.clone(), initial));
         let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
 
-        // Forward reaction: T -> U (covariant)
-        let source_inner = source_weak.clone();
-        let new_signal_rc = new_signal_weak.clone();
-        let forward_react_fn = Box::new(move || {
-            if let Some(new_sig) = new_signal_rc.upgrade() {
-                if !*new_sig.explicitly_modified.borrow() {
-                    if let Some(source) = source_inner.upgrade() {
-                        let t_value = source.value.borrow();
-                        let u_value = f(&t_value);
-                        drop(t_value);
-                        *new_sig.value.borrow_mut() = u_value;
-                    }
-                }
-            }
-        });
-
-        self.0.react_fns.borrow_mut().push(forward_react_fn);
-        self.0
-            .successors
-            .borrow_mut()
-            .push(WeakSignalRef::new(&new_signal));
-
-        // Backward reaction: U -> T (contravariant)
-        let new_signal_rc_back = new_signal_weak.clone();
-        let source_inner_back = source_weak.clone();
-
-        let backward_react_fn = Box::new(move || {
-            if let Some(new_sig) = new_signal_rc_back.upgrade() {
-                if *new_sig.explicitly_modified.borrow() {
-                    let u_value_ref = new_sig.value.borrow();
-                    let t_value = g(&u_value_ref);
-                    drop(u_value_ref);
-
-                    if let Some(source) = source_inner_back.upgrade() {
-                        *source.value.borrow_mut() = t_value;
-                        *source.explicitly_modified.borrow_mut() = true;
-                    }
-                }
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let current = src.value.borrow().clone();
+                let mut pair = new_sig.value.borrow_mut();
+                pair.0 = std::mem::replace(&mut pair.1, current);
             }
         });
-        new_signal.0.react_fns.borrow_mut().push(backward_react_fn);
+        self.push_derived(react_fn, &new_signal);
 
-        // Register self (source) as a predecessor of new_signal (backward dependency)
-        // When new_signal changes, it propagates backward to self
         new_signal
-            .0
-            .predecessors
-            .borrow_mut()
-            .push(WeakSignalRef::new(self));
-
-        result_new_signal
     }
 
-    /// Combine two signals into one
+    /// Create a signal collecting every value of the current signal
     ///
-    /// This combines two signals into a new signal that holds a tuple of their values.
-    /// Changes to either signal will propagate to the new combined signal.
+    /// The new signal holds a vector that starts with the current value, and each change of the
+    /// current signal pushes the new value onto it. The vector grows without bound; see
+    /// `accumulate_bounded` to keep only the most recent values.
     ///
     /// # Example
     /// ```rust
-    /// let a = Signal::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// let ab = a.combine(&b);
-    /// ab.with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let history = a.accumulate();
+    /// a.send(2);
+    /// a.send(3);
+    /// assert_eq!(history.get(), vec![1, 2, 3]);
     /// ```
-    pub fn combine<S>(&self, another: S) -> Signal<'a, (T, S::Inner)>
+    pub fn accumulate(&self) -> Signal<'a, Vec<T>>
     where
-        S: Liftable<'a>,
-        S::Inner: 'a,
-        T: 'a,
+        T: Clone,
     {
-        self.combine_ref::<S, WeakRefStrategy>(another)
+        self.accumulate_with(vec![self.0.value.borrow().clone()], Vec::push)
     }
 
-    /// Combine two signals into one with strong references
+    /// Create a signal collecting the last `cap` values of the current signal
     ///
-    /// This combines two signals into a new signal that holds a tuple of their values.
-    /// Changes to either signal will propagate to the new combined signal.
-    /// This combination uses strong references.
+    /// This works like `accumulate`, but the values are kept in a `VecDeque`: once it holds
+    /// `cap` values, each new value pushes out the oldest one in constant time, so the queue
+    /// always holds the most recent values in order.
+    ///
+    /// # Panics
+    /// Panics if `cap` is zero.
     ///
     /// # Example
     /// ```rust
-    /// let a = Signal::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// a.and(&b).with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let recent = a.accumulate_bounded(2);
+    /// a.send(2);
+    /// a.send(3);
+    /// assert_eq!(recent.get(), vec![2, 3]);
     /// ```
-    pub fn and<S>(&self, another: S) -> Signal<'a, (T, S::Inner)>
+    pub fn accumulate_bounded(&self, cap: usize) -> Signal<'a, VecDeque<T>>
     where
-        S: Liftable<'a>,
-        S::Inner: 'a,
-        T: 'a,
+        T: Clone,
     {
-        self.combine_ref::<S, StrongRefStrategy>(another)
+        assert!(cap > 0, "the accumulate capacity must not be zero");
+        let initial = VecDeque::from([self.0.value.borrow().clone()]);
+        self.accumulate_with(initial, move |values, current| {
+            if values.len() == cap {
+                values.pop_front();
+            }
+            values.push_back(current);
+        })
     }
 
-    fn combine_ref<S: Liftable<'a>, Strat: RefStrategy<'a>>(
-        &self,
-        another: S,
-    ) -> Signal<'a, (T, S::Inner)>
+    /// Create a signal from `initial`, adding each new value of the current signal with `push`
+    fn accumulate_with<C: 'a>(&self, initial: C, push: impl Fn(&mut C, T) + 'a) -> Signal<'a, C>
     where
-        S::Inner: 'a,
-        T: 'a,
+        T: Clone,
     {
-        let another = another.as_ref();
-
-        // Take values temporarily, create signal, restore values
-        let temp_val_0 = Self::take_value(&self.0.value);
-        let temp_val_1 = Self::take_value(&another.0.value);
-        let new_signal = Signal::new((temp_val_0, temp_val_1));
-        std::mem::swap(
-            &mut *self.0.value.borrow_mut(),
-            &mut new_signal.0.value.borrow_mut().0,
-        );
-        std::mem::swap(
-            &mut *another.0.value.borrow_mut(),
-            &mut new_signal.0.value.borrow_mut().1,
-        );
-
-        let result_new_signal = new_signal.clone();
+        let new_signal = Signal::new(initial);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
 
-        // Register reaction for first source
-        let new_signal_ref = Strat::new_ref(&new_signal.0);
-        let self_ref = Strat::new_ref(&self.0);
-        let react_fn_self = Box::new(move || {
-            if let (Some(new_sig), Some(src)) =
-                (Strat::upgrade(&new_signal_ref), Strat::upgrade(&self_ref))
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
             {
-                if !*new_sig.explicitly_modified.borrow() {
-                    std::mem::swap(
-                        &mut *src.value.borrow_mut(),
-                        &mut new_sig.value.borrow_mut().0,
-                    );
-                }
-            }
-        });
-        self.0.react_fns.borrow_mut().push(react_fn_self);
-        self.0
-            .successors
-            .borrow_mut()
-            .push(WeakSignalRef::new(&result_new_signal));
-
-        // Register reaction for second source
-        let new_signal_ref_2 = Strat::new_ref(&new_signal.0);
-        let another_ref = Strat::new_ref(&another.0);
-        let react_fn_another = Box::new(move || {
-            if let (Some(new_sig), Some(src)) = (
-                Strat::upgrade(&new_signal_ref_2),
-                Strat::upgrade(&another_ref),
-            ) {
-                if !*new_sig.explicitly_modified.borrow() {
-                    std::mem::swap(
-                        &mut *src.value.borrow_mut(),
-                        &mut new_sig.value.borrow_mut().1,
-                    );
-                }
+                let current = src.value.borrow().clone();
+                push(&mut new_sig.value.borrow_mut(), current);
             }
         });
-        another.0.react_fns.borrow_mut().push(react_fn_another);
-        another
-            .0
-            .successors
-            .borrow_mut()
-            .push(WeakSignalRef::new(&result_new_signal));
+        self.push_derived(react_fn, &new_signal);
 
-        result_new_signal
+        new_signal
     }
 
-    /// Extend the signal with a vector of signals
+    /// Create a signal that only propagates the values differing from the last propagated one
     ///
-    /// This creates a new signal that depends on the current signal and the provided signals.
-    /// Changes to any of the source signals will propagate to the new signal.
+    /// The new signal is updated, and its observers notified, only when the new value of the
+    /// current signal is not equal to the value it holds, so sending the same value twice
+    /// does not trigger downstream reactions again. See `distinct_by` for a custom comparison.
     ///
     /// # Example
     /// ```rust
+    /// # use agility::Signal;
     /// let a = Signal::new(1);
-    /// let b = Signal::new(2);
-    /// let c = Signal::new(3);
-    /// let d = a.extend(vec![b, c]);
-    /// d.with(|values| println!("d changed: {:?}", values));
-    /// a.send(10); // prints "d changed: [10, 2, 3]"
-    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// let distinct = a.distinct();
+    /// let _observer = distinct.map(|x| println!("distinct: {}", x));
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints "distinct: 2"
     /// ```
-    pub fn extend<S>(&self, others: impl IntoIterator<Item = S>) -> Signal<'a, Vec<T>>
+    pub fn distinct(&self) -> Signal<'a, T>
     where
-        S: Liftable<'a, Inner = T>,
-        T: 'a,
+        T: Clone + PartialEq,
     {
-        self.extend_ref::<S, WeakRefStrategy>(others)
+        self.distinct_by(PartialEq::eq)
     }
 
-    /// Extend the signal with a vector of signals with strong references
+    /// Create a signal that only propagates the values differing from the last propagated one
     ///
-    /// This creates a new signal that depends on the current signal and the provided signals.
-    /// Changes to any of the source signals will propagate to the new signal.
-    /// It uses strong references.
+    /// Like `distinct`, but two values are considered the same when `eq` returns true for them.
+    /// The suppressed updates never mark the new signal or its successors dirty, so they leave
+    /// nothing pending in the batch.
     ///
     /// # Example
     /// ```rust
-    /// let a = Signal::new(1);
-    /// let b = Signal::new(2);
-    /// let c = Signal::new(3);
-    /// a.follow(vec![b, c]).with(|values| println!("d changed: {:?}", values));
-    /// a.send(10); // prints "d changed: [10, 2, 3]"
-    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// # use agility::Signal;
+    /// let a = Signal::new(1.0_f64);
+    /// let rounded = a.distinct_by(|x, y| x.round() == y.round());
+    /// let _observer = rounded.map(|x| println!("rounded: {}", x));
+    /// a.send(1.2); // prints nothing
+    /// a.send(2.0); // prints "rounded: 2"
     /// ```
-    pub fn follow<S>(&self, others: impl IntoIterator<Item = S>) -> Signal<'a, Vec<T>>
+    pub fn distinct_by<F>(&self, eq: F) -> Signal<'a, T>
     where
-        S: Liftable<'a, Inner = T>,
-        T: 'a,
+        T: Clone,
+        F: Fn(&T, &T) -> bool + 'a,
     {
-        self.extend_ref::<S, StrongRefStrategy>(others)
+        let new_signal = Signal::new(self.0.value.borrow().clone());
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
+            }
+            let value = src.value.borrow().clone();
+            if eq(&new_sig.value.borrow(), &value) {
+                return None;
+            }
+            *new_sig.value.borrow_mut() = value;
+            Some(Signal(new_sig).guard())
+        });
+
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
     }
 
-    fn extend_ref<S, Strat: RefStrategy<'a>>(
-        &self,
-        others: impl IntoIterator<Item = S>,
-    ) -> Signal<'a, Vec<T>>
+    /// Create a signal that only takes the values of the current signal passing a predicate
+    ///
+    /// The new signal is updated, and its observers notified, only when `pred` returns true
+    /// for the new value of the current signal; otherwise it keeps its previous value and
+    /// does not propagate. The new signal starts with the current value, whether or not it
+    /// passes `pred`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let even = a.filter(|x| x % 2 == 0);
+    /// let _observer = even.map(|x| println!("even: {}", x));
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints "even: 2"
+    /// ```
+    pub fn filter<F>(&self, pred: F) -> Signal<'a, T>
     where
-        S: Liftable<'a, Inner = T>,
-        T: 'a,
+        T: Clone,
+        F: Fn(&T) -> bool + 'a,
     {
-        let others_signals: Vec<Signal<'a, T>> =
-            others.into_iter().map(|s| s.as_ref().clone()).collect();
+        let new_signal = Signal::new(self.0.value.borrow().clone());
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
 
-        // Collect values using take_value helper - no cloning!
-        let all_signals: Vec<&Signal<'a, T>> =
-            iter::once(self).chain(others_signals.iter()).collect();
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
+            }
+            let value = src.value.borrow().clone();
+            if !pred(&value) {
+                return None;
+            }
+            *new_sig.value.borrow_mut() = value;
+            Some(Signal(new_sig).guard())
+        });
 
-        let temp_values: Vec<T> = all_signals
-            .iter()
-            .map(|s| Self::take_value(&s.0.value))
-            .collect();
-        let new_signal: Signal<'a, Vec<T>> = Signal::new(temp_values);
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
+    }
 
-        // Restore original values by swapping back
-        for (index, signal) in all_signals.iter().enumerate() {
-            std::mem::swap(
-                &mut *signal.0.value.borrow_mut(),
-                &mut new_signal.0.value.borrow_mut()[index],
-            );
+    /// Create a signal that only takes the valid values of the current signal
+    ///
+    /// The new signal holds the last known good value: a value for which `valid` returns false
+    /// is not propagated, so a single bad reading such as a `NaN` can't poison the graph
+    /// downstream. Rejected values are reported to the error sink of the graph if one is
+    /// installed, see `on_reaction_error`. The new signal starts with the current value; if that
+    /// value is invalid it is reported as well, and the guarantee starts with the first valid one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let reading = Signal::new(1.0_f64);
+    /// let valid = reading.validated(|x| x.is_finite());
+    /// let _observer = valid.map(|x| println!("valid: {}", x)); // prints "valid: 1"
+    /// reading.send(f64::NAN); // prints nothing
+    /// reading.send(2.0); // prints "valid: 2"
+    /// ```
+    pub fn validated<F>(&self, valid: F) -> Signal<'a, T>
+    where
+        T: Clone + fmt::Debug,
+        F: Fn(&T) -> bool + 'a,
+    {
+        let initial = self.0.value.borrow().clone();
+        if !valid(&initial) {
+            self.report_rejected(&initial);
         }
+        let new_signal = self.new_mapped(initial);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
 
-        let result_new_signal = new_signal.clone();
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let source = Signal(source_weak.upgrade()?);
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
+            }
+            let value = source.0.value.borrow().clone();
+            if !valid(&value) {
+                source.report_rejected(&value);
+                return None;
+            }
+            *new_sig.value.borrow_mut() = value;
+            Some(Signal(new_sig).guard())
+        });
 
-        iter::once(self)
-            .chain(others_signals.iter())
-            .enumerate()
-            .for_each(|(index, signal)| {
-                let new_signal_ref = Strat::new_ref(&new_signal.0);
-                let source_ref = Strat::new_ref(&signal.0);
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
+    }
 
-                let react_fn = Box::new(move || {
-                    if let Some(new_sig) = Strat::upgrade(&new_signal_ref) {
-                        if !*new_sig.explicitly_modified.borrow() {
-                            if let Some(src) = Strat::upgrade(&source_ref) {
-                                // Swap values instead of cloning (during reaction only)
-                                std::mem::swap(
-                                    &mut new_sig.value.borrow_mut()[index],
-                                    &mut *src.value.borrow_mut(),
-                                );
-                            }
-                        }
-                    }
-                });
+    /// Handle the errors reported by reactions of the graph
+    ///
+    /// Signals derived with `map`, `with` and `try_map` share one error sink with the signal
+    /// they are derived from, so a handler registered on any of them sees the errors reported
+    /// anywhere in that subgraph, no matter whether it was registered before or after the
+    /// derivation. This centralizes error handling instead of having every observer deal
+    /// with `Result` values individually.
+    ///
+    /// A reaction that panics is skipped and its panic is reported here as well, so a failing
+    /// observer does not keep the rest of the graph from updating.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let input = Signal::new(String::from("1"));
+    /// let parsed = input.try_map(|s| s.parse::<i32>());
+    /// input.on_reaction_error(|e| println!("{}", e));
+    /// input.send(String::from("x")); // prints "reaction error: invalid digit found in string"
+    /// ```
+    pub fn on_reaction_error<F>(&self, f: F)
+    where
+        F: Fn(&ReactionError) + 'a,
+    {
+        self.error_sink().subscribe(Box::new(f));
+    }
 
-                signal.0.react_fns.borrow_mut().push(react_fn);
-                signal
-                    .0
-                    .successors
-                    .borrow_mut()
-                    .push(WeakSignalRef::new(&new_signal));
-            });
+    /// Map the signal with a fallible function
+    ///
+    /// This works like `map`, but the new signal holds the `Result` of `f`. Every `Err` is
+    /// passed downstream as a value and also reported to the error sink of the graph, see
+    /// `on_reaction_error`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let input = Signal::new(String::from("1"));
+    /// let parsed = input.try_map(|s| s.parse::<i32>());
+    /// let _observer = parsed.map(|r| println!("parsed: {:?}", r));
+    /// input.send(String::from("2")); // prints "parsed: Ok(2)"
+    /// ```
+    pub fn try_map<U: 'a, E, F>(&self, f: F) -> Signal<'a, Result<U, E>>
+    where
+        E: fmt::Display + 'a,
+        F: Fn(&T) -> Result<U, E> + 'a,
+    {
+        let sink = self.error_sink();
+        self.map(move |value| {
+            let result = f(value);
+            if let Err(e) = &result {
+                sink.report(&ReactionError::new(e.to_string()));
+            }
+            result
+        })
+    }
 
-        result_new_signal
+    /// Send a new value to the signal
+    ///
+    /// This will replace the current value of the signal with the new value.
+    /// The signals that depend on this signal will be notified and updated accordingly.
+    ///
+    /// It returns a `SignalGuard` that ensures reactions are processed when dropped and
+    /// prevents premature reactions during multiple sends. (Batch updates)
+    ///
+    /// A send made by a reaction reacts right away, within the current propagation. For a
+    /// `'static` signal, `SignalGuard::defer` holds its reactions until the propagation has settled.
+    ///
+    /// Signals react in topological order: a signal only reacts once every signal it depends on
+    /// has settled, so observers never see a mix of updated and stale inputs (no glitches),
+    /// however uneven the paths from the signal that changed.
+    ///
+    /// Each signal reacts at most once per propagation. When signals depend on each other in a
+    /// cycle, propagation stops at the first signal it would enter a second time, so the cycle
+    /// settles after one pass instead of recursing forever.
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let signal = Signal::new(0);
+    /// signal.send(42); // sets the signal's value to 42
+    ///
+    /// signal.with(|v| println!("Signal value: {}", v));
+    /// (signal.send(66), signal.send(100));
+    /// // sets the signal's value to 100 and prints "Signal value: 100" only once
+    /// ```
+    pub fn send(&self, new_value: T) -> SignalGuard<'a> {
+        self.modify(|v| *v = new_value);
+        *self.0.explicitly_modified.borrow_mut() = true;
+        self.react_eager();
+        self.guard()
     }
 
-    /// Let this signal depend on another signal
+    /// Send a modification to the signal
     ///
-    /// This synchronizes the value of this signal with the value of the dependency signal.
-    /// Whenever the dependency signal changes, this signal will be updated to match its value.
+    /// This will apply the provided function to modify the current value of the signal.
+    /// The signals that depend on this signal will be notified and updated accordingly.
     ///
-    /// The returned signal is exactly the argument `dependency`. You can break the dependency chain
-    /// by dropping the returned signal if `dependency` is weakly referenced.
+    /// It returns a `SignalGuard` that ensures reactions are processed when dropped and
+    /// prevents premature reactions during multiple sends. (Batch updates)
+    pub fn send_with<F>(&self, f: F) -> SignalGuard<'a>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.modify(f);
+        self.react_eager();
+        self.guard()
+    }
+
+    /// Send a new value to the signal, returning the previous one
+    ///
+    /// This works like `send`, including the returned guard, but hands back the value it
+    /// replaced, to diff it against the new one or to reuse its allocation.
     ///
     /// # Example
     /// ```rust
+    /// # use agility::Signal;
     /// let a = Signal::new(1);
-    /// let mut b = Signal::new(2);
-    /// b = a.depend(b);
-    /// a.with(|v| println!("a changed: {}", v));
-    /// b.send(3); // prints "a changed: 3"
+    /// let (previous, _guard) = a.replace(2);
+    /// assert_eq!(previous, 1);
     /// ```
+    pub fn replace(&self, new_value: T) -> (T, SignalGuard<'a>) {
+        let previous = std::mem::replace(&mut *self.0.value.borrow_mut(), new_value);
+        *self.0.explicitly_modified.borrow_mut() = true;
+        self.react_eager();
+        (previous, self.guard())
+    }
+
+    /// Take the value out of the signal, leaving the default value in its place
     ///
-    /// The example above is analogous to:
+    /// This is `replace` with `T::default()`: the signals that depend on this signal are updated
+    /// with the default value.
+    ///
+    /// # Example
     /// ```rust
-    /// let a = Signal::new(1);
-    /// let b = a.map(|v| *v);
-    /// b.with(|v| println!("b changed: {}", v));
-    /// a.send(3); // prints "b changed: 3"
+    /// # use agility::Signal;
+    /// let log = Signal::new(vec![String::from("started")]);
+    /// let (lines, _guard) = log.take();
+    /// assert_eq!(lines, vec![String::from("started")]);
+    /// assert!(log.get().is_empty());
     /// ```
-    pub fn depend(&self, dependency: Signal<'a, T>) -> Signal<'a, T>
+    pub fn take(&self) -> (T, SignalGuard<'a>)
     where
-        T: Clone,
+        T: Default,
     {
-        let self_weak = Rc::downgrade(&self.0);
-        let dependency_weak = Rc::downgrade(&dependency.0);
+        self.replace(T::default())
+    }
 
-        let react_fn = Box::new(move || {
-            if let Some(dep) = dependency_weak.upgrade() {
-                if let Some(target) = self_weak.upgrade() {
-                    if !*target.explicitly_modified.borrow() {
-                        // Swap values instead of cloning (during reaction only)
-                        std::mem::swap(
-                            &mut *target.value.borrow_mut(),
-                            &mut *dep.value.borrow_mut(),
-                        );
-                    }
+    pub fn set(&mut self, signal: Signal<'a, T>) {
+        self.0 = signal.0;
+    }
+
+    /// Freeze the signal, holding back its reactions until the returned guard is dropped
+    ///
+    /// While frozen, the value of the signal still changes, but the signal does not react: its
+    /// observers do not run and the signals derived from it are not updated. When the guard is
+    /// dropped, the signal reacts once with its latest value if it was sent to or updated in the
+    /// meantime, however many times. Unlike `batch`, which groups the sends made in one place,
+    /// this holds back a single signal across any number of unrelated send sites. Freezes nest,
+    /// the signal reacts when the last guard is dropped.
+    /// A send to a frozen signal with predecessors, like the target of `promap`, is not written
+    /// back to them either until the signal thaws.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let _observer = a.map(|x| println!("a changed: {}", x));
+    /// let frozen = a.freeze();
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints nothing
+    /// drop(frozen); // prints "a changed: 2"
+    /// ```
+    pub fn freeze(&self) -> FreezeGuard<'a> {
+        *self.0.frozen.borrow_mut() += 1;
+        let signal = self.clone();
+        FreezeGuard {
+            thaw: Some(Box::new(move || {
+                *signal.0.frozen.borrow_mut() -= 1;
+                if *signal.0.frozen.borrow() == 0 && signal.0.missed_while_frozen.replace(false) {
+                    drop(signal.thaw_guard());
                 }
-            }
-        });
+            })),
+        }
+    }
 
-        dependency.0.react_fns.borrow_mut().push(react_fn);
-        dependency
-            .0
-            .successors
-            .borrow_mut()
-            .push(WeakSignalRef::new(self));
-        dependency
+    /// Map the signal to a new signal
+    ///
+    /// This creates a new signal that depends on the current signal.
+    /// Changes to the source signal will propagate to the new signal.
+    /// `f` runs once right away to compute the initial value; see `map_lazy` to defer it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// let _observer = b.map(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "b changed: 10"
+    /// ```
+    pub fn map<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        self.map_ref::<U, F, WeakRefStrategy>(f)
     }
 
-    pub(crate) fn modify(&self, f: impl FnOnce(&mut T)) {
-        let mut value = self.0.value.borrow_mut();
-        f(&mut value);
+    /// Map the signal to a new signal with strong references
+    ///
+    /// This creates a new signal that depends on the current signal.
+    /// Changes to the source signal will propagate to the new signal.
+    /// This mapping uses strong references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// b.with(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "b changed: 10"
+    /// ```
+    pub fn with<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        self.map_ref::<U, F, StrongRefStrategy>(f)
     }
 
-    fn mark_dirty(&self) {
-        *self.0.dirty.borrow_mut() += 1;
+    /// Map the signal to a new signal, passing the value by copy
+    ///
+    /// This is a specialization of `map` for `Copy` values: the value is copied out of the signal
+    /// and the borrow is released before `f` runs, instead of keeping the source borrowed
+    /// for the duration of the call. As a consequence `f` may freely read the source signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.map_copied(|x| x * 2);
+    /// let _observer = b.map_copied(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "b changed: 10"
+    /// ```
+    pub fn map_copied<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    where
+        T: Copy,
+        F: Fn(T) -> U + 'a,
+    {
+        let initial = *self.0.value.borrow();
+        let new_signal = self.new_mapped(f(initial));
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = *src.value.borrow();
+                let new_value = f(value);
+                *new_sig.value.borrow_mut() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
     }
 
-    fn collect_and_iterate<F>(&self, refs: &RefCell<Vec<WeakSignalRef<'a>>>, mut callback: F)
+    /// Map the signal to a new signal, passing `f` an owned clone of the value
+    ///
+    /// This works like `map_copied` for `Clone` values: the value is cloned out of the signal and
+    /// the borrow is released before `f` takes ownership of the clone, so closures that consume their
+    /// input need no `clone` of their own.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(String::from("agility"));
+    /// let b = a.map_cloned(|s| s.into_bytes());
+    /// a.send(String::from("signal"));
+    /// assert_eq!(b.get(), b"signal");
+    /// ```
+    pub fn map_cloned<U: 'a, F>(&self, f: F) -> Signal<'a, U>
     where
-        F: FnMut(&dyn SignalExt<'a>),
+        T: Clone,
+        F: Fn(T) -> U + 'a,
     {
-        refs.borrow_mut().retain(|s| s.is_alive());
-        for s in refs.borrow().iter() {
-            if let Some(signal) = s.upgrade() {
-                callback(&*signal);
+        let initial = self.0.value.borrow().clone();
+        let new_signal = self.new_mapped(f(initial));
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.borrow().clone();
+                let new_value = f(value);
+                *new_sig.value.borrow_mut() = new_value;
             }
-        }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
     }
 
-    fn collect_guards(&self, result: &mut Vec<SignalGuardInner<'a>>) {
-        self.mark_dirty();
-        result.push(SignalGuardInner(self.clone_box()));
-        self.collect_and_iterate(&self.0.successors, |signal| {
-            signal.collect_guards_recursive(result);
+    /// Map the signal into a value updated in place
+    ///
+    /// This works like `map`, but instead of building a new value on each change, `f` updates
+    /// the value of the new signal through a mutable reference, so a buffer keeps its allocation
+    /// across updates. The new signal starts with `init`, which `f` updates right away with the
+    /// current value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let samples = Signal::new(vec![1u8, 2, 3]);
+    /// let scaled = samples.map_into(Vec::with_capacity(1024), |input, output: &mut Vec<u16>| {
+    ///     output.clear();
+    ///     output.extend(input.iter().map(|&x| x as u16 * 256));
+    /// });
+    /// assert_eq!(scaled.get(), vec![256, 512, 768]);
+    /// ```
+    pub fn map_into<U: 'a, F>(&self, mut init: U, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&T, &mut U) + 'a,
+    {
+        f(&self.0.value.borrow(), &mut init);
+        let new_signal = self.new_mapped(init);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                f(&src.value.borrow(), &mut new_sig.value.borrow_mut());
+            }
         });
-        self.collect_and_iterate(&self.0.predecessors, |signal| {
-            signal.collect_predecessors_recursive(result);
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, deferring `f` to the first change
+    ///
+    /// Unlike `map`, which runs `f` on the current value to seed the new signal, this does not
+    /// run `f` at construction: the new signal holds `None` until the current signal first
+    /// changes, then `Some` of `f` applied to each new value. This suits an `f` with side effects
+    /// or a current value that is only a placeholder.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.map_lazy(|x| x * 2);
+    /// assert_eq!(b.get(), None);
+    /// a.send(5);
+    /// assert_eq!(b.get(), Some(10));
+    /// ```
+    pub fn map_lazy<U: 'a, F>(&self, f: F) -> Signal<'a, Option<U>>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        let new_signal = self.new_mapped(None);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let new_value = f(&src.value.borrow());
+                *new_sig.value.borrow_mut() = Some(new_value);
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, skipping `f` when the value did not actually change
+    ///
+    /// This works like `map`, but keeps the last value `f` was applied to and only reruns `f`
+    /// when the new value of the current signal differs from it, which saves an expensive `f`
+    /// when the signal propagates without a real change, like a send of the same value.
+    /// Where `dedupe` compares the outputs, this compares the inputs; observers of the new
+    /// signal are still notified.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(2);
+    /// let squared = a.map_memo(|x| {
+    ///     println!("computing");
+    ///     x * x
+    /// }); // prints "computing"
+    /// a.send(2); // prints nothing
+    /// a.send(3); // prints "computing"
+    /// ```
+    pub fn map_memo<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    where
+        T: Clone + PartialEq,
+        F: Fn(&T) -> U + 'a,
+    {
+        let initial = self.0.value.borrow().clone();
+        let new_signal = self.new_mapped(f(&initial));
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let last_input = RefCell::new(initial);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.borrow();
+                if *last_input.borrow() == *value {
+                    return;
+                }
+                let new_value = f(&value);
+                *last_input.borrow_mut() = value.clone();
+                drop(value);
+                *new_sig.value.borrow_mut() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, forwarding the guard of a send made by the mapping
+    ///
+    /// This works like `map`, except that `f` also returns the guard of a `send` it makes to
+    /// another signal. Instead of settling on its own while the current batch is still
+    /// propagating, the forwarded guard joins the current batch, so the dirtiness of the
+    /// target is merged into the ongoing propagation and its observers run once, after the
+    /// reactions of the current signal. The guard returned at creation is dropped immediately.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let b = Signal::new(0);
+    /// let target = b.clone();
+    /// let _forward = a.map_forward(move |x| ((), Some(target.send(x * 10))));
+    /// let _observer_b = b.map(|x| println!("b changed: {}", x));
+    /// let _observer_a = a.map(|x| println!("a changed: {}", x));
+    /// a.send(1); // prints "a changed: 1" and then "b changed: 10"
+    /// ```
+    pub fn map_forward<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> (U, Option<SignalGuard<'a>>) + 'a,
+    {
+        let (initial, _guard) = f(&self.0.value.borrow());
+        let new_signal = self.new_mapped(initial);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let (new_value, guard) = f(&src.value.borrow());
+                *new_sig.value.borrow_mut() = new_value;
+                guard
+            } else {
+                None
+            }
+        });
+
+        self.0
+            .react_fns
+            .borrow_mut()
+            .push_forwarding_owned(react_fn, WeakSignalRef::new(&new_signal));
+        self.0
+            .successors
+            .borrow_mut()
+            .push(WeakSignalRef::new(&new_signal));
+
+        new_signal
+    }
+
+    /// Record every change of the signal into an append-only event log
+    ///
+    /// This creates a new signal holding `(epoch, value)` pairs, where the initial value is
+    /// recorded with epoch `0` and every settled change of the current signal appends the next
+    /// epoch. The log is a signal itself, so other parts of the graph can observe it.
+    /// See `event_log_bounded` to limit the number of retained events.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new("idle");
+    /// let log = a.event_log();
+    /// a.send("running");
+    /// a.send("done");
+    /// log.with(|events| println!("{:?}", events));
+    /// // prints "[(0, "idle"), (1, "running"), (2, "done")]"
+    /// ```
+    pub fn event_log(&self) -> Signal<'a, Vec<(u64, T)>>
+    where
+        T: Clone,
+    {
+        self.event_log_ref(None)
+    }
+
+    /// Record every change of the signal into an event log keeping the last `cap` events
+    ///
+    /// This is the same as `event_log`, but older events are discarded once more than `cap`
+    /// events have been recorded. Epochs keep counting, so gaps reveal discarded events.
+    pub fn event_log_bounded(&self, cap: usize) -> Signal<'a, Vec<(u64, T)>>
+    where
+        T: Clone,
+    {
+        self.event_log_ref(Some(cap))
+    }
+
+    fn event_log_ref(&self, cap: Option<usize>) -> Signal<'a, Vec<(u64, T)>>
+    where
+        T: Clone,
+    {
+        let mut initial = vec![(0, self.0.value.borrow().clone())];
+        if cap == Some(0) {
+            initial.clear();
+        }
+        let new_signal = Signal::new(initial);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let epoch = std::cell::Cell::new(0u64);
+
+        let react_fn = Box::new(move || {
+            if let Some(log) = new_signal_weak.upgrade()
+                && !*log.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                epoch.set(epoch.get() + 1);
+                let current = epoch.get();
+                let value = src.value.borrow().clone();
+                let mut events = log.value.borrow_mut();
+                events.push((current, value));
+                if let Some(cap) = cap
+                    && events.len() > cap
+                {
+                    let excess = events.len() - cap;
+                    events.drain(..excess);
+                }
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal that reacts to every send
+    ///
+    /// Unlike `map`, which runs once per batch with the final value, the closure of an eager
+    /// map runs immediately on every `send`/`send_with` to the current signal, so it observes
+    /// all intermediate values of a batch. When the current signal is updated by propagation
+    /// instead of a direct send, the closure runs once when the batch settles.
+    /// Successors of the new signal are still batched as usual.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let _eager = a.map_eager(|x| println!("eager: {}", x));
+    /// let _batched = a.map(|x| println!("batched: {}", x));
+    /// (a.send(1), a.send(2));
+    /// // prints "eager: 1", "eager: 2" and then "batched: 2"
+    /// ```
+    pub fn map_eager<U: 'a, F>(&self, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        let new_signal = self.new_mapped(f(&self.0.value.borrow()));
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let eager_react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let new_value = f(&src.value.borrow());
+                *new_sig.value.borrow_mut() = new_value;
+            }
+        });
+
+        self.0
+            .eager_react_fns
+            .borrow_mut()
+            .push_owned(eager_react_fn, WeakSignalRef::new(&new_signal));
+        self.0
+            .successors
+            .borrow_mut()
+            .push(WeakSignalRef::new(&new_signal));
+
+        new_signal
+    }
+
+    /// Observe the signal without keeping it alive
+    ///
+    /// This registers `f` to run with the new value whenever the signal changes, without
+    /// creating a derived signal. The returned `WeakObserver` holds only a weak reference to the
+    /// signal, and dropping it removes the reaction from the signal, so nothing is left behind.
+    /// The observer does not run at registration, only on subsequent changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let observer = a.observe_weak(|x| println!("a changed: {}", x));
+    /// a.send(1); // prints "a changed: 1"
+    /// drop(observer);
+    /// a.send(2); // prints nothing
+    /// ```
+    pub fn observe_weak<F>(&self, f: F) -> WeakObserver<'a>
+    where
+        F: Fn(&T) + 'a,
+    {
+        WeakObserver {
+            detach: Some(self.observe_detachable(f)),
+        }
+    }
+
+    /// Subscribe to the changes of the signal
+    ///
+    /// This registers `f` to run with the new value whenever the signal changes and returns a
+    /// `Subscription` handle. Unlike keeping a derived signal from `map` alive, the lifetime of the
+    /// side effect is explicit: dropping the handle removes the reaction from the signal. The
+    /// handle does not keep the signal alive, and `f` does not run at subscription time.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let subscription = a.subscribe(|x| println!("a changed: {}", x));
+    /// a.send(1); // prints "a changed: 1"
+    /// drop(subscription);
+    /// a.send(2); // prints nothing
+    /// ```
+    pub fn subscribe<F>(&self, f: F) -> Subscription<'a>
+    where
+        F: Fn(&T) + 'a,
+    {
+        Subscription {
+            detach: Some(self.observe_detachable(f)),
+        }
+    }
+
+    /// Subscribe to the signal, running `f` with the current value right away
+    ///
+    /// Like `subscribe`, but `f` also runs once with the current value at subscription time,
+    /// for observers that need the initial state as well as the changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let _subscription = a.subscribe_now(|x| println!("a is {}", x)); // prints "a is 0"
+    /// a.send(1); // prints "a is 1"
+    /// ```
+    pub fn subscribe_now<F>(&self, f: F) -> Subscription<'a>
+    where
+        F: Fn(&T) + 'a,
+    {
+        f(&self.0.value.borrow());
+        self.subscribe(f)
+    }
+
+    /// Run a side effect on every change of the signal
+    ///
+    /// `f` runs with each new value until the returned `Effect` is dropped, without creating a
+    /// result signal to keep alive as `map` would. `f` may return a cleanup closure, such as
+    /// closing a connection it opened: the cleanup of a run is called right before the next run,
+    /// and the last one when the effect is dropped. Returning `()` means there is nothing to
+    /// clean up. Like `subscribe`, the effect does not run on the current value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let effect = a.effect(|x| {
+    ///     println!("open {}", x);
+    ///     let x = *x;
+    ///     move || println!("close {}", x)
+    /// });
+    /// a.send(1); // prints "open 1"
+    /// a.send(2); // prints "close 1" and then "open 2"
+    /// drop(effect); // prints "close 2"
+    /// a.send(3); // prints nothing
+    /// ```
+    pub fn effect<F, C>(&self, f: F) -> Effect<'a>
+    where
+        F: Fn(&T) -> C + 'a,
+        C: EffectCleanup<'a>,
+    {
+        let cleanup = Rc::new(RefCell::new(None::<Cleanup<'a>>));
+        let slot = cleanup.clone();
+        let subscription = self.subscribe(move |value| {
+            let previous = slot.borrow_mut().take();
+            if let Some(previous) = previous {
+                previous();
+            }
+            let next = f(value).into_cleanup();
+            *slot.borrow_mut() = next;
+        });
+        Effect {
+            subscription: Some(subscription),
+            cleanup,
+        }
+    }
+
+    /// Create a thread-safe signal following this signal
+    ///
+    /// The new `SignalSync` starts with a clone of the current value, and a reaction on this
+    /// signal sends it every later value, so a leaf of a graph built with `Signal` can be handed
+    /// to other threads. The forwarding is one of this signal's reactions: the returned signal
+    /// only updates while the thread owning this signal processes its reactions. Like `map`,
+    /// the returned signal is weakly referenced: once it is dropped, the forwarding reaction
+    /// removes itself on the next change.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let shared = a.to_sync();
+    /// a.send(2);
+    /// let reader = shared.clone();
+    /// let value = std::thread::spawn(move || reader.get()).join().unwrap();
+    /// assert_eq!(value, 2);
+    /// ```
+    pub fn to_sync(&self) -> SignalSync<'a, T>
+    where
+        T: Clone + Send + Sync,
+    {
+        let sync_signal = SignalSync::new(self.0.value.borrow().clone());
+        let sync_weak = Arc::downgrade(&sync_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let id = Rc::new(Cell::new(None));
+        let reaction_id = id.clone();
+
+        let react_fn = Box::new(move || {
+            let Some(src) = source_weak.upgrade() else {
+                return;
+            };
+            if let Some(target) = sync_weak.upgrade() {
+                let value = src.value.borrow().clone();
+                SignalSync(target).send(value);
+            } else if let Some(id) = reaction_id.take() {
+                src.react_fns.borrow_mut().remove(id);
+            }
+        });
+
+        id.set(Some(self.0.react_fns.borrow_mut().push(react_fn)));
+        sync_signal
+    }
+
+    /// Register `f` as a reaction, returning the closure that removes it again
+    fn observe_detachable<F>(&self, f: F) -> Box<dyn FnOnce() + 'a>
+    where
+        F: Fn(&T) + 'a,
+    {
+        let source_weak = Rc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(src) = source_weak.upgrade() {
+                f(&src.value.borrow());
+            }
+        });
+        let id = self.0.react_fns.borrow_mut().push(react_fn);
+
+        let source_weak = Rc::downgrade(&self.0);
+        Box::new(move || {
+            if let Some(src) = source_weak.upgrade() {
+                src.react_fns.borrow_mut().remove(id);
+            }
+        })
+    }
+
+    /// Run initialization once, on the first value the signal receives
+    ///
+    /// This registers `f` to run with the value of the first `send` or propagation reaching the
+    /// signal after registration, then removes the reaction so it never runs again. The current
+    /// value is treated as a placeholder and is never passed to `f`. This is useful for lazy
+    /// setup, like opening a connection when the configuration first arrives.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let config = Signal::new(String::new());
+    /// config.on_first(|url| println!("connecting to {}", url));
+    /// config.send(String::from("db://primary")); // prints "connecting to db://primary"
+    /// config.send(String::from("db://replica")); // prints nothing
+    /// ```
+    pub fn on_first(&self, f: impl FnOnce(&T) + 'a) {
+        let source_weak = Rc::downgrade(&self.0);
+        let f = RefCell::new(Some(f));
+        let id = Rc::new(Cell::new(None));
+        let reaction_id = id.clone();
+        let react_fn = Box::new(move || {
+            let Some(src) = source_weak.upgrade() else {
+                return;
+            };
+            if let Some(id) = reaction_id.take() {
+                src.react_fns.borrow_mut().remove(id);
+            }
+            if let Some(f) = f.borrow_mut().take() {
+                f(&src.value.borrow());
+            }
+        });
+        id.set(Some(self.0.react_fns.borrow_mut().push(react_fn)));
+    }
+
+    /// Observe the signal for as long as an owner is alive
+    ///
+    /// This registers `f` to run with the new value whenever the signal changes, like
+    /// `observe_weak`, but ties the observer to `owner` instead of a returned handle. The
+    /// observer only holds a weak reference to the owner: once the owner is dropped, `f` no longer
+    /// runs and the reaction removes itself on the next change. This gives "the observer dies
+    /// with its component" semantics without keeping a handle or unsubscribing manually.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// # use std::rc::Rc;
+    /// let value = Signal::new(0);
+    /// let widget = Rc::new(String::from("label"));
+    /// value.bind_to_owner(&widget, |x| println!("render: {}", x));
+    /// value.send(1); // prints "render: 1"
+    /// drop(widget);
+    /// value.send(2); // prints nothing
+    /// ```
+    pub fn bind_to_owner<O: 'a>(&self, owner: &Rc<O>, f: impl Fn(&T) + 'a) {
+        let owner_weak = Rc::downgrade(owner);
+        let source_weak = Rc::downgrade(&self.0);
+        let id = Rc::new(Cell::new(None));
+        let reaction_id = id.clone();
+        let react_fn = Box::new(move || {
+            let Some(src) = source_weak.upgrade() else {
+                return;
+            };
+            if owner_weak.strong_count() > 0 {
+                f(&src.value.borrow());
+            } else if let Some(id) = reaction_id.take() {
+                src.react_fns.borrow_mut().remove(id);
+            }
+        });
+        id.set(Some(self.0.react_fns.borrow_mut().push(react_fn)));
+    }
+
+    /// Remove the edges and reactions left behind by dropped signals
+    ///
+    /// Dead successors and predecessors, along with the reactions that fed derived signals which
+    /// have since been dropped, are normally only cleaned up lazily, the next time the signal
+    /// propagates. This removes them right away, so a long-lived source that rarely changes
+    /// does not accumulate stale entries.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let source = Signal::new(1);
+    /// for i in 0..100 {
+    ///     let _short_lived = source.map(move |x| x + i);
+    /// }
+    /// source.prune_dead(); // the reactions of the 100 mapped signals are gone
+    /// ```
+    pub fn prune_dead(&self) {
+        self.0.react_fns.borrow_mut().retain_alive();
+        self.0.eager_react_fns.borrow_mut().retain_alive();
+        self.0.successors.borrow_mut().retain(|s| s.is_alive());
+        self.0.predecessors.borrow_mut().retain(|s| s.is_alive());
+    }
+
+    /// Get the number of signals registered as derived from this signal
+    ///
+    /// This counts the successor edges as they are, including the ones left by dropped signals
+    /// that have not been pruned yet; see `live_successor_count`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = a.promap(|x| x + 1, |y| y - 1);
+    /// assert_eq!(a.successor_count(), 1);
+    /// assert_eq!(b.predecessor_count(), 1);
+    /// ```
+    pub fn successor_count(&self) -> usize {
+        self.0.successors.borrow().len()
+    }
+
+    /// Get the number of signals this signal writes back to
+    ///
+    /// Only the derivations that propagate changes backwards, like `promap` and `contramap`,
+    /// register their source as a predecessor; `map` does not. Like `successor_count`, this
+    /// includes the edges to dropped signals not pruned yet.
+    pub fn predecessor_count(&self) -> usize {
+        self.0.predecessors.borrow().len()
+    }
+
+    /// Get the number of live signals derived from this signal
+    ///
+    /// This runs `prune_dead` first, so the edges and reactions left by dropped signals are
+    /// removed and only the successors still alive are counted.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = a.map(|x| x + 1);
+    /// drop(b);
+    /// assert_eq!(a.successor_count(), 1);
+    /// assert_eq!(a.live_successor_count(), 0);
+    /// ```
+    pub fn live_successor_count(&self) -> usize {
+        self.prune_dead();
+        self.successor_count()
+    }
+
+    /// Render the graph reachable from the signal in Graphviz DOT format
+    ///
+    /// This walks the signals derived from this one, and the signals they write back to, and
+    /// emits a node for each with an edge for each dependency; write-back edges, like the ones
+    /// of `promap`, are dashed. Nodes are numbered in the order they are reached, starting with
+    /// this signal at `#0`, and show the label of the signal if it has one, as well as whether
+    /// it is dirty or explicitly modified. Each signal is visited once, so cycles are rendered
+    /// without looping.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1).with_label("a");
+    /// let _b = a.map(|x| x * 2);
+    /// println!("{}", a.to_dot()); // pipe into `dot -Tsvg` to draw the graph
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::default();
+        self.write_dot(&mut graph);
+        graph.finish()
+    }
+
+    /// Detach everything downstream of the signal
+    ///
+    /// This removes every reaction and successor registered on the signal, so signals derived
+    /// from it and observers of it stop being updated. Signals it is derived from are not
+    /// affected. This is useful to tear down a set of bindings at once, or to reset a graph
+    /// between test cases.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let source = Signal::new(1);
+    /// let _observer = source.map(|x| println!("source changed: {}", x));
+    /// source.clear_observers();
+    /// source.send(2); // prints nothing
+    /// ```
+    pub fn clear_observers(&self) {
+        self.0.react_fns.borrow_mut().clear();
+        self.0.eager_react_fns.borrow_mut().clear();
+        self.0.successors.borrow_mut().clear();
+    }
+
+    /// Disconnect a signal derived from this one, without dropping either
+    ///
+    /// This removes the reactions through which this signal updates `derived`, along with the
+    /// edge to it, so the graph can be rewired at runtime without leaving closures behind on a
+    /// long-lived source. `derived` keeps its last value and its own observers. It covers the
+    /// signals derived with `map`, `combine`, `filter`, `zip` and the like; returns whether
+    /// anything was removed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let source = Signal::new(1);
+    /// let doubled = source.map(|x| x * 2);
+    /// assert!(source.disconnect(&doubled));
+    /// source.send(5);
+    /// assert_eq!(doubled.get(), 2);
+    /// ```
+    pub fn disconnect<U: 'a>(&self, derived: &Signal<'a, U>) -> bool {
+        let removed = self.0.react_fns.borrow_mut().remove_owned_by(derived)
+            | self.0.eager_react_fns.borrow_mut().remove_owned_by(derived);
+        self.0
+            .successors
+            .borrow_mut()
+            .retain(|s| !s.points_to(derived));
+        removed
+    }
+
+    /// Split the signal into several independent branches
+    ///
+    /// This creates `n` new signals that each track the current signal, so independent
+    /// observer chains can be built off each branch. Every branch only clones the current
+    /// value; the reaction that produced it is run once per update no matter how many
+    /// branches or observers hang off it. Like `map`, the branches are weakly referenced.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let shared = a.map(|x| x * 10); // runs once per update of `a`
+    /// let branches = shared.tee(2);
+    /// let _first = branches[0].map(|x| println!("first: {}", x));
+    /// let _second = branches[1].map(|x| println!("second: {}", x));
+    /// a.send(2); // prints "first: 20" and "second: 20"
+    /// ```
+    pub fn tee(&self, n: usize) -> Vec<Signal<'a, T>>
+    where
+        T: Clone,
+    {
+        (0..n).map(|_| self.map(T::clone)).collect()
+    }
+
+    /// Sample the signal whenever a clock signal changes
+    ///
+    /// This creates a new signal that takes the current value of this signal each time `clock`
+    /// changes, and ignores changes of this signal between clock ticks, which limits the rate at
+    /// which downstream observers see updates without any timer.
+    /// If this signal did not change between two ticks, the same value is emitted again;
+    /// observers that only care about changes should dedupe it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let value = Signal::new(0);
+    /// let clock = Signal::new(());
+    /// let sampled = value.sample_when(&clock);
+    /// let _observer = sampled.map(|x| println!("sampled: {}", x));
+    /// value.send(1);
+    /// value.send(2); // prints nothing
+    /// clock.send(()); // prints "sampled: 2"
+    /// clock.send(()); // prints "sampled: 2" again
+    /// ```
+    pub fn sample_when<B: 'a>(&self, clock: &Signal<'a, B>) -> Signal<'a, T>
+    where
+        T: Clone,
+    {
+        let new_signal = Signal::new(self.0.value.borrow().clone());
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let new_value = src.value.borrow().clone();
+                *new_sig.value.borrow_mut() = new_value;
+            }
+        });
+
+        clock.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Sample the signal whenever a trigger signal changes
+    ///
+    /// This is `sample_when` for any `Liftable` trigger, so owned and borrowed signals can be
+    /// passed alike. The new signal takes the current value of this signal each time `trigger`
+    /// changes and ignores changes of this signal in between.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let value = Signal::new(0);
+    /// let trigger = Signal::new(());
+    /// let sampled = value.sample(&trigger);
+    /// let _observer = sampled.map(|x| println!("sampled: {}", x));
+    /// value.send(1); // prints nothing
+    /// trigger.send(()); // prints "sampled: 1"
+    /// ```
+    pub fn sample<S>(&self, trigger: S) -> Signal<'a, T>
+    where
+        S: Liftable<'a>,
+        S::Inner: 'a,
+        T: Clone,
+    {
+        self.sample_when(trigger.as_ref())
+    }
+
+    /// Accumulate the values of the signal, starting over whenever a reset signal changes
+    ///
+    /// This creates a new signal holding an accumulator that starts at `init`. Each change of
+    /// this signal folds the new value into the accumulator with `f`, and each change of
+    /// `reset` sets the accumulator back to `init`, whatever its value. This is useful for
+    /// "sum since the last reset" or "events since the session started". Like `map`, the new
+    /// signal is weakly referenced by both this signal and `reset`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let clicks = Signal::new(());
+    /// let new_session = Signal::new(());
+    /// let count = clicks.scan_reset(0, |n, _| n + 1, &new_session);
+    /// let _observer = count.map(|n| println!("clicks this session: {}", n));
+    /// (clicks.send(()), clicks.send(())); // prints "clicks this session: 2"
+    /// new_session.send(()); // prints "clicks this session: 0"
+    /// clicks.send(()); // prints "clicks this session: 1"
+    /// ```
+    pub fn scan_reset<U, F, B>(&self, init: U, f: F, reset: &Signal<'a, B>) -> Signal<'a, U>
+    where
+        U: Clone + 'a,
+        F: Fn(&U, &T) -> U + 'a,
+        B: 'a,
+    {
+        let new_signal = Signal::new(init.clone());
+
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let next = f(&new_sig.value.borrow(), &src.value.borrow());
+                *new_sig.value.borrow_mut() = next;
+            }
+        });
+        self.push_derived(react_fn, &new_signal);
+
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let reset_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+            {
+                *new_sig.value.borrow_mut() = init.clone();
+            }
+        });
+        reset.push_derived(reset_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Track the signal until a condition is met, then switch to another signal for good
+    ///
+    /// This creates a new signal that mirrors the current signal until `switch_when` returns
+    /// true for its value. At that point the new signal takes the value of `next`, stops
+    /// listening to the current signal and mirrors `next` from then on. If the condition already
+    /// holds for the initial value, the new signal mirrors `next` right away. This is useful for
+    /// phase-based logic, like showing a loading value until loaded and then the real value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let loading = Signal::new(0);
+    /// let data = Signal::new(String::from("data"));
+    /// let progress = loading.map(|p| format!("loading {}%", p));
+    /// let shown = progress.then(&data, |p| p == "loading 100%");
+    /// let _observer = shown.map(|s| println!("{}", s)); // prints "loading 0%"
+    /// loading.send(50); // prints "loading 50%"
+    /// loading.send(100); // prints "data"
+    /// data.send(String::from("more data")); // prints "more data"
+    /// ```
+    pub fn then<P>(&self, next: &Signal<'a, T>, switch_when: P) -> Signal<'a, T>
+    where
+        T: Clone,
+        P: Fn(&T) -> bool + 'a,
+    {
+        if switch_when(&self.0.value.borrow()) {
+            let new_signal = Signal::new(next.0.value.borrow().clone());
+            next.mirror_into(&new_signal);
+            return new_signal;
+        }
+
+        let new_signal = Signal::new(self.0.value.borrow().clone());
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let next_weak = Rc::downgrade(&next.0);
+        let id = Rc::new(Cell::new(None));
+        let reaction_id = id.clone();
+
+        let react_fn = Box::new(move || {
+            let (Some(new_sig), Some(src)) = (new_signal_weak.upgrade(), source_weak.upgrade())
+            else {
+                return;
+            };
+            if *new_sig.explicitly_modified.borrow() {
+                return;
+            }
+            let value = src.value.borrow().clone();
+            if !switch_when(&value) {
+                *new_sig.value.borrow_mut() = value;
+                return;
+            }
+
+            // Switch over to `next`: detach from the current signal and mirror `next`
+            if let Some(id) = reaction_id.take() {
+                src.react_fns.borrow_mut().remove(id);
+            }
+            let new_signal = Signal(new_sig);
+            src.successors
+                .borrow_mut()
+                .retain(|s| !s.points_to(&new_signal));
+            if let Some(next) = next_weak.upgrade() {
+                let next = Signal(next);
+                let value = next.0.value.borrow().clone();
+                *new_signal.0.value.borrow_mut() = value;
+                next.mirror_into(&new_signal);
+            }
+        });
+
+        id.set(Some(self.push_derived(react_fn, &new_signal)));
+
+        new_signal
+    }
+
+    /// Erase the value type of the signal
+    ///
+    /// The erased signal keeps the signal and its graph alive like a clone would, and can be
+    /// stored alongside signals of other types. Use `downcast` to get the concrete signal back.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::{BoxedSignal, Signal};
+    /// let signals: Vec<BoxedSignal> = vec![Signal::new(1).erase(), Signal::new("one").erase()];
+    /// let number = signals[0].downcast_ref::<i32>().unwrap();
+    /// number.send(2);
+    /// assert!(signals[1].downcast_ref::<i32>().is_none());
+    /// ```
+    pub fn erase(self) -> BoxedSignal<'a>
+    where
+        T: 'static,
+    {
+        BoxedSignal {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            signal: Box::new(self),
+        }
+    }
+
+    /// Create an independent copy of the signal seeded with the current value
+    ///
+    /// Unlike `clone`, which shares the same inner signal and therefore the same graph, the
+    /// fork is a brand new signal with its own empty successors, predecessors and reactions.
+    /// It does NOT track the original: later changes to either signal are not seen by the other.
+    /// This is useful to edit a snapshot of a live value and commit it back later.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let draft = a.fork();
+    /// draft.send(2); // `a` still holds 1
+    /// a.send(3); // `draft` still holds 2
+    /// ```
+    pub fn fork(&self) -> Self
+    where
+        T: Clone,
+    {
+        Signal::new(self.0.value.borrow().clone())
+    }
+
+    fn map_ref<U: 'a, F, S: RefStrategy<'a>>(&self, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        let new_signal = self.new_mapped(f(&self.0.value.borrow()));
+        let result_new_signal = new_signal.clone();
+
+        let new_signal_ref = S::new_ref(&new_signal.0);
+        let source_ref = S::new_ref(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig_inner) = S::upgrade(&new_signal_ref)
+                && !*new_sig_inner.explicitly_modified.borrow()
+                && let Some(src_inner) = S::upgrade(&source_ref)
+            {
+                let new_value = f(&src_inner.value.borrow());
+                *new_sig_inner.value.borrow_mut() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        result_new_signal
+    }
+
+    /// Map the signal contravariantly to a new signal
+    ///
+    /// This creates a new signal that the current signal depends on.
+    /// Changes to the new signal will propagate back to the original signal.
+    /// It is inspired by the concept of contravariant functors in category theory.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let result = Signal::new(42);
+    /// let source = result.contramap(|x| x * 2);
+    /// result.with(|x| println!("result changed: {}", x));
+    /// source.with(|x| println!("source changed: {}", x));
+    /// source.send(100);
+    /// // prints "source changed: 100" and "result changed: 50"
+    /// ```
+    pub fn contramap<F, U>(&self, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&U) -> T + 'a,
+        U: Default + 'a,
+    {
+        self.contramap_with(U::default(), f)
+    }
+
+    /// Map the signal contravariantly to a new signal with an explicit initial value
+    ///
+    /// This is the same as `contramap`, but the new signal starts with `initial`
+    /// instead of `U::default()`, so it works for types without a `Default` implementation.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let result = Signal::new(42);
+    /// let source = result.contramap_with(84, |x| x / 2);
+    /// result.with(|x| println!("result changed: {}", x));
+    /// source.send(100); // prints "result changed: 50"
+    /// ```
+    pub fn contramap_with<F, U>(&self, initial: U, f: F) -> Signal<'a, U>
+    where
+        F: Fn(&U) -> T + 'a,
+        U: 'a,
+    {
+        let new_signal = Signal::new(initial);
+        let result_new_signal = new_signal.clone();
+        let source_inner = Rc::downgrade(&self.0);
+        let new_signal_rc = Rc::downgrade(&new_signal.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_rc.upgrade()
+                && *new_sig.explicitly_modified.borrow()
+            {
+                let u_value_ref = new_sig.value.borrow();
+                let t_value = f(&u_value_ref);
+                drop(u_value_ref);
+
+                if let Some(source) = source_inner.upgrade() {
+                    *source.value.borrow_mut() = t_value;
+                    *source.explicitly_modified.borrow_mut() = true;
+                }
+            }
+        });
+        new_signal.0.react_fns.borrow_mut().push(react_fn);
+
+        new_signal
+            .0
+            .predecessors
+            .borrow_mut()
+            .push(WeakSignalRef::new(self));
+
+        result_new_signal
+    }
+
+    /// Map the signal bidirectionally to a new signal
+    ///
+    /// This creates a new signal that depends on the current signal,
+    /// and the current signal also depends on the new signal.
+    /// Changes to either signal will propagate to the other signal.
+    /// It is inspired by the concept of profunctors in category theory.
+    ///
+    /// The new signal starts with `f` applied to the current value, so it is correct before
+    /// anything is sent.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.promap(|x| x * 2, |y| y / 2);
+    /// a.with(|x| println!("a changed: {}", x));
+    /// b.with(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "a changed: 5" and "b changed: 10"
+    /// b.send(50); // prints "b changed: 50" and "a changed: 25"
+    /// ```
+    pub fn promap<F, G, U>(&self, f: F, g: G) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+        G: Fn(&U) -> T + 'a,
+        U: 'a,
+    {
+        let initial = f(&self.0.value.borrow());
+        self.promap_with(initial, f, g)
+    }
+
+    /// Map the signal bidirectionally to a new signal with an explicit initial value
+    ///
+    /// This is the same as `promap`, but the new signal starts with `initial`
+    /// instead of `f` applied to the current value, for when that is expensive or not wanted.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.promap_with(20, |x| x * 2, |y| y / 2);
+    /// a.with(|x| println!("a changed: {}", x));
+    /// b.send(50); // prints "a changed: 25"
+    /// ```
+    pub fn promap_with<F, G, U>(&self, initial: U, f: F, g: G) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+        G: Fn(&U) -> T + 'a,
+        U: 'a,
+    {
+        self.promap_by(initial, f, g, |t_value, new_t_value| *t_value = new_t_value)
+    }
+
+    /// Project the signal to one of its parts, writing the changes of the part back
+    ///
+    /// This is a lens built on `promap`: the new signal holds `get` applied to the current value
+    /// and follows its changes, while sending to the new signal updates the current value in
+    /// place through `set` and notifies its other observers.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// #[derive(Debug)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    /// let user = Signal::new(User { name: String::from("Ann"), age: 30 });
+    /// let age = user.project(|u| u.age, |u, age| u.age = age);
+    /// user.with(|u| println!("user changed: {:?}", u));
+    /// age.send(31); // prints "user changed: User { name: \"Ann\", age: 31 }"
+    /// ```
+    pub fn project<U, Get, Set>(&self, get: Get, set: Set) -> Signal<'a, U>
+    where
+        U: Clone + 'a,
+        Get: Fn(&T) -> U + 'a,
+        Set: Fn(&mut T, U) + 'a,
+    {
+        let initial = get(&self.0.value.borrow());
+        self.promap_by(initial, get, U::clone, set)
+    }
+
+    fn promap_by<F, G, H, U, V>(&self, initial: U, f: F, g: G, apply: H) -> Signal<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+        G: Fn(&U) -> V + 'a,
+        H: Fn(&mut T, V) + 'a,
+        U: 'a,
+    {
+        let new_signal = Signal::new(initial);
+        let result_new_signal = new_signal.clone();
+        let source_weak = Rc::downgrade(&self.0);
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+
+        // Forward reaction: T -> U (covariant)
+        let source_inner = source_weak.clone();
+        let new_signal_rc = new_signal_weak.clone();
+        let forward_react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_rc.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(source) = source_inner.upgrade()
+            {
+                let t_value = source.value.borrow();
+                let u_value = f(&t_value);
+                drop(t_value);
+                *new_sig.value.borrow_mut() = u_value;
+            }
+        });
+
+        self.push_derived(forward_react_fn, &new_signal);
+
+        // Backward reaction: U -> T (contravariant)
+        let new_signal_rc_back = new_signal_weak.clone();
+        let source_inner_back = source_weak.clone();
+
+        let backward_react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_rc_back.upgrade()
+                && *new_sig.explicitly_modified.borrow()
+            {
+                let u_value_ref = new_sig.value.borrow();
+                let t_value = g(&u_value_ref);
+                drop(u_value_ref);
+
+                if let Some(source) = source_inner_back.upgrade() {
+                    apply(&mut source.value.borrow_mut(), t_value);
+                    *source.explicitly_modified.borrow_mut() = true;
+                }
+            }
+        });
+        new_signal.0.react_fns.borrow_mut().push(backward_react_fn);
+
+        // Register self (source) as a predecessor of new_signal (backward dependency)
+        // When new_signal changes, it propagates backward to self
+        new_signal
+            .0
+            .predecessors
+            .borrow_mut()
+            .push(WeakSignalRef::new(self));
+
+        result_new_signal
+    }
+
+    /// Combine two signals into one
+    ///
+    /// This combines two signals into a new signal that holds a tuple of their values.
+    /// Changes to either signal will propagate to the new combined signal.
+    /// To transform the pair right away, prefer `combine_map`, which skips the tuple signal.
+    /// The values are cloned into the tuple, since the sources and the combined signal each
+    /// hold one; moving them instead would leave a source with no valid value to hold.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// let ab = a.combine(&b);
+    /// ab.with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// ```
+    pub fn combine<S>(&self, another: S) -> Signal<'a, (T, S::Inner)>
+    where
+        S: Liftable<'a>,
+        S::Inner: Clone + 'a,
+        T: Clone + 'a,
+    {
+        self.combine_ref::<S, WeakRefStrategy>(another)
+    }
+
+    /// Combine two signals into one with strong references
+    ///
+    /// This combines two signals into a new signal that holds a tuple of their values.
+    /// Changes to either signal will propagate to the new combined signal.
+    /// This combination uses strong references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// a.and(&b).with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// ```
+    pub fn and<S>(&self, another: S) -> Signal<'a, (T, S::Inner)>
+    where
+        S: Liftable<'a>,
+        S::Inner: Clone + 'a,
+        T: Clone + 'a,
+    {
+        self.combine_ref::<S, StrongRefStrategy>(another)
+    }
+
+    fn combine_ref<S: Liftable<'a>, Strat: RefStrategy<'a>>(
+        &self,
+        another: S,
+    ) -> Signal<'a, (T, S::Inner)>
+    where
+        S::Inner: Clone + 'a,
+        T: Clone + 'a,
+    {
+        let another = another.as_ref();
+
+        let new_signal = Signal::new((
+            self.0.value.borrow().clone(),
+            another.0.value.borrow().clone(),
+        ));
+
+        let result_new_signal = new_signal.clone();
+
+        // Register reaction for first source
+        let new_signal_ref = Strat::new_ref(&new_signal.0);
+        let self_ref = Strat::new_ref(&self.0);
+        let react_fn_self = Box::new(move || {
+            if let (Some(new_sig), Some(src)) =
+                (Strat::upgrade(&new_signal_ref), Strat::upgrade(&self_ref))
+                && !*new_sig.explicitly_modified.borrow()
+            {
+                new_sig.value.borrow_mut().0 = src.value.borrow().clone();
+            }
+        });
+        self.push_derived(react_fn_self, &result_new_signal);
+
+        // Register reaction for second source
+        let new_signal_ref_2 = Strat::new_ref(&new_signal.0);
+        let another_ref = Strat::new_ref(&another.0);
+        let react_fn_another = Box::new(move || {
+            if let (Some(new_sig), Some(src)) = (
+                Strat::upgrade(&new_signal_ref_2),
+                Strat::upgrade(&another_ref),
+            ) && !*new_sig.explicitly_modified.borrow()
+            {
+                new_sig.value.borrow_mut().1 = src.value.borrow().clone();
+            }
+        });
+        another.push_derived(react_fn_another, &result_new_signal);
+
+        result_new_signal
+    }
+
+    /// Combine two signals and transform their values in a single reaction
+    ///
+    /// This is the fused form of `a.combine(&b).map(f)`: the new signal holds `f` applied to
+    /// the values of both signals and is recomputed by reading both sources directly whenever
+    /// either changes, without an intermediate tuple signal or an extra reaction.
+    /// Like `map`, the new signal is weakly referenced by both sources.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let width = Signal::new(2);
+    /// let height = Signal::new(3);
+    /// let area = width.combine_map(&height, |w, h| w * h);
+    /// let _observer = area.map(|a| println!("area: {}", a));
+    /// width.send(4); // prints "area: 12"
+    /// (width.send(5), height.send(5)); // prints "area: 25" once
+    /// ```
+    pub fn combine_map<B, U, F>(&self, other: &Signal<'a, B>, f: F) -> Signal<'a, U>
+    where
+        B: 'a,
+        U: 'a,
+        F: Fn(&T, &B) -> U + 'a,
+    {
+        self.combine_map_ref::<B, U, F, WeakRefStrategy>(other, f)
+    }
+
+    fn combine_map_ref<B, U, F, Strat: RefStrategy<'a>>(
+        &self,
+        other: &Signal<'a, B>,
+        f: F,
+    ) -> Signal<'a, U>
+    where
+        B: 'a,
+        U: 'a,
+        F: Fn(&T, &B) -> U + 'a,
+    {
+        let new_signal = Signal::new(f(&self.0.value.borrow(), &other.0.value.borrow()));
+        let f = Rc::new(f);
+
+        // Shared so the reaction can be cloned for both sources
+        let new_signal_ref = Rc::new(Strat::new_ref(&new_signal.0));
+        let self_weak = Rc::downgrade(&self.0);
+        let other_weak = Rc::downgrade(&other.0);
+        let react_fn = move || {
+            if let Some(new_sig) = Strat::upgrade(&new_signal_ref)
+                && !*new_sig.explicitly_modified.borrow()
+                && let (Some(left), Some(right)) = (self_weak.upgrade(), other_weak.upgrade())
+            {
+                let new_value = f(&left.value.borrow(), &right.value.borrow());
+                *new_sig.value.borrow_mut() = new_value;
+            }
+        };
+        self.push_derived(Box::new(react_fn.clone()), &new_signal);
+        other.push_derived(Box::new(react_fn), &new_signal);
+
+        new_signal
+    }
+
+    /// Combine with any liftable signal and transform both values into one
+    ///
+    /// This is `combine_map` for any `Liftable`, so owned and borrowed signals can be passed alike.
+    /// The new signal holds `f` applied to both values, with no intermediate tuple signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(2);
+    /// let sum = a.combine_with(b.clone(), |x, y| x + y);
+    /// let _observer = sum.map(|s| println!("sum: {}", s)); // prints "sum: 3"
+    /// b.send(5); // prints "sum: 6"
+    /// ```
+    pub fn combine_with<S, U, F>(&self, other: S, f: F) -> Signal<'a, U>
+    where
+        S: Liftable<'a>,
+        S::Inner: 'a,
+        U: 'a,
+        F: Fn(&T, &S::Inner) -> U + 'a,
+    {
+        self.combine_map(other.as_ref(), f)
+    }
+
+    /// Combine two signals into one that emits only once both have changed
+    ///
+    /// Unlike `combine`, the new signal does not notify its observers until both signals have
+    /// been sent a value since it was created; from then on it emits the latest pair whenever
+    /// either changes, like `combineLatest` in RxJS. Until then it holds the current values.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(0);
+    /// let b = Signal::new(0);
+    /// let latest = a.combine_latest(&b);
+    /// let _observer = latest.map(|(x, y)| println!("latest: {} {}", x, y));
+    /// a.send(1); // prints nothing
+    /// b.send(2); // prints "latest: 1 2"
+    /// a.send(3); // prints "latest: 3 2"
+    /// ```
+    pub fn combine_latest<S>(&self, other: S) -> Signal<'a, (T, S::Inner)>
+    where
+        S: Liftable<'a>,
+        S::Inner: Clone + 'a,
+        T: Clone,
+    {
+        let other = other.as_ref();
+        let new_signal = Signal::new((
+            self.0.value.borrow().clone(),
+            other.0.value.borrow().clone(),
+        ));
+
+        // The values sent to each signal since construction, the pair is emitted once both are set
+        let latest = Rc::new(RefCell::new((None::<T>, None::<S::Inner>)));
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let latest_emit = latest.clone();
+        let emit = Rc::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
+            }
+            let (Some(left), Some(right)) = &*latest_emit.borrow() else {
+                return None;
+            };
+            *new_sig.value.borrow_mut() = (left.clone(), right.clone());
+            Some(Signal(new_sig).guard())
+        });
+
+        let self_weak = Rc::downgrade(&self.0);
+        let (latest_self, emit_self) = (latest.clone(), emit.clone());
+        let react_fn_self = Box::new(move || {
+            let src = self_weak.upgrade()?;
+            latest_self.borrow_mut().0 = Some(src.value.borrow().clone());
+            emit_self()
+        });
+        self.push_forwarded(react_fn_self, &new_signal);
+
+        let other_weak = Rc::downgrade(&other.0);
+        let react_fn_other = Box::new(move || {
+            let src = other_weak.upgrade()?;
+            latest.borrow_mut().1 = Some(src.value.borrow().clone());
+            emit()
+        });
+        other.push_forwarded(react_fn_other, &new_signal);
+
+        new_signal
+    }
+
+    /// Pair the changes of two signals in order, like zipping two iterators
+    ///
+    /// The nth change of this signal is paired with the nth change of `other`: the new values of
+    /// each signal are queued, and the new signal emits a pair whenever both queues hold a value,
+    /// taking the oldest of each. Unlike `combine_latest`, no value is paired twice or skipped.
+    /// A signal reacting several times before the other reacts once has all its values buffered,
+    /// so if one signal consistently changes faster than the other, its queue grows without
+    /// bound. Until the first pair, the new signal holds the current values without notifying.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let requests = Signal::new(0);
+    /// let responses = Signal::new(String::new());
+    /// let exchanges = requests.zip(&responses);
+    /// let _observer = exchanges.map(|(id, body)| println!("{}: {}", id, body));
+    /// requests.send(1); // prints nothing
+    /// requests.send(2); // prints nothing
+    /// responses.send(String::from("one")); // prints "1: one"
+    /// responses.send(String::from("two")); // prints "2: two"
+    /// ```
+    pub fn zip<S>(&self, other: S) -> Signal<'a, (T, S::Inner)>
+    where
+        S: Liftable<'a>,
+        S::Inner: Clone + 'a,
+        T: Clone,
+    {
+        let other = other.as_ref();
+        let new_signal = Signal::new((
+            self.0.value.borrow().clone(),
+            other.0.value.borrow().clone(),
+        ));
+
+        // The values of each signal not paired yet, oldest first
+        let queues = Rc::new(RefCell::new((
+            VecDeque::<T>::new(),
+            VecDeque::<S::Inner>::new(),
+        )));
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let queues_emit = queues.clone();
+        let emit = Rc::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let pair = {
+                let mut queues = queues_emit.borrow_mut();
+                if queues.0.is_empty() || queues.1.is_empty() {
+                    return None;
+                }
+                (queues.0.pop_front()?, queues.1.pop_front()?)
+            };
+            // The pair is consumed even when the signal was sent to directly, to keep the pace
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
+            }
+            *new_sig.value.borrow_mut() = pair;
+            Some(Signal(new_sig).guard())
+        });
+
+        let self_weak = Rc::downgrade(&self.0);
+        let (queues_self, emit_self) = (queues.clone(), emit.clone());
+        let react_fn_self = Box::new(move || {
+            let src = self_weak.upgrade()?;
+            queues_self
+                .borrow_mut()
+                .0
+                .push_back(src.value.borrow().clone());
+            emit_self()
+        });
+        self.push_forwarded(react_fn_self, &new_signal);
+
+        let other_weak = Rc::downgrade(&other.0);
+        let react_fn_other = Box::new(move || {
+            let src = other_weak.upgrade()?;
+            queues.borrow_mut().1.push_back(src.value.borrow().clone());
+            emit()
+        });
+        other.push_forwarded(react_fn_other, &new_signal);
+
+        new_signal
+    }
+
+    /// Extend the signal with a vector of signals
+    ///
+    /// This creates a new signal that depends on the current signal and the provided signals.
+    /// Changes to any of the source signals will propagate to the new signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(2);
+    /// let c = Signal::new(3);
+    /// let d = a.extend(vec![&b, &c]);
+    /// d.with(|values| println!("d changed: {:?}", values));
+    /// a.send(10); // prints "d changed: [10, 2, 3]"
+    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// ```
+    pub fn extend<S>(&self, others: impl IntoIterator<Item = S>) -> Signal<'a, Vec<T>>
+    where
+        S: Liftable<'a, Inner = T>,
+        T: Clone + 'a,
+    {
+        self.extend_ref::<S, WeakRefStrategy>(others)
+    }
+
+    /// Extend the signal with a vector of signals with strong references
+    ///
+    /// This creates a new signal that depends on the current signal and the provided signals.
+    /// Changes to any of the source signals will propagate to the new signal.
+    /// It uses strong references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(2);
+    /// let c = Signal::new(3);
+    /// a.follow(vec![&b, &c]).with(|values| println!("d changed: {:?}", values));
+    /// a.send(10); // prints "d changed: [10, 2, 3]"
+    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// ```
+    pub fn follow<S>(&self, others: impl IntoIterator<Item = S>) -> Signal<'a, Vec<T>>
+    where
+        S: Liftable<'a, Inner = T>,
+        T: Clone + 'a,
+    {
+        self.extend_ref::<S, StrongRefStrategy>(others)
+    }
+
+    fn extend_ref<S, Strat: RefStrategy<'a>>(
+        &self,
+        others: impl IntoIterator<Item = S>,
+    ) -> Signal<'a, Vec<T>>
+    where
+        S: Liftable<'a, Inner = T>,
+        T: Clone + 'a,
+    {
+        let others_signals: Vec<Signal<'a, T>> =
+            others.into_iter().map(|s| s.as_ref().clone()).collect();
+
+        let values: Vec<T> = iter::once(self)
+            .chain(others_signals.iter())
+            .map(|s| s.0.value.borrow().clone())
+            .collect();
+        let new_signal: Signal<'a, Vec<T>> = Signal::new(values);
+
+        let result_new_signal = new_signal.clone();
+
+        iter::once(self)
+            .chain(others_signals.iter())
+            .enumerate()
+            .for_each(|(index, signal)| {
+                let new_signal_ref = Strat::new_ref(&new_signal.0);
+                let source_ref = Strat::new_ref(&signal.0);
+
+                let react_fn = Box::new(move || {
+                    if let Some(new_sig) = Strat::upgrade(&new_signal_ref)
+                        && !*new_sig.explicitly_modified.borrow()
+                        && let Some(src) = Strat::upgrade(&source_ref)
+                    {
+                        new_sig.value.borrow_mut()[index] = src.value.borrow().clone();
+                    }
+                });
+
+                signal.push_derived(react_fn, &new_signal);
+            });
+
+        result_new_signal
+    }
+
+    /// Extend the signal with other signals, reporting which element changed
+    ///
+    /// Like `extend`, this creates a signal holding the values of this signal followed by the
+    /// values of `others`, but paired with the index of the element that changed last, so
+    /// observers can update incrementally instead of diffing the whole vector. The index starts
+    /// at 0; when several elements change in one batch, the last one to settle is reported.
+    /// Values are cloned into the new signal, and like `extend` it is weakly referenced.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(2);
+    /// let c = Signal::new(3);
+    /// let list = a.extend_tracked([&b, &c]);
+    /// let _observer = list.map(|(i, values)| println!("item {} is now {}", i, values[*i]));
+    /// c.send(30); // prints "item 2 is now 30"
+    /// a.send(10); // prints "item 0 is now 10"
+    /// ```
+    pub fn extend_tracked<S>(
+        &self,
+        others: impl IntoIterator<Item = S>,
+    ) -> Signal<'a, (usize, Vec<T>)>
+    where
+        S: Liftable<'a, Inner = T>,
+        T: Clone,
+    {
+        let signals: Vec<Signal<'a, T>> = iter::once(self.clone())
+            .chain(others.into_iter().map(|s| s.as_ref().clone()))
+            .collect();
+        let values: Vec<T> = signals.iter().map(|s| s.0.value.borrow().clone()).collect();
+        let new_signal = Signal::new((0, values));
+
+        for (index, signal) in signals.iter().enumerate() {
+            let new_signal_weak = Rc::downgrade(&new_signal.0);
+            let source_weak = Rc::downgrade(&signal.0);
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !*new_sig.explicitly_modified.borrow()
+                    && let Some(src) = source_weak.upgrade()
+                {
+                    let value = src.value.borrow().clone();
+                    let mut tracked = new_sig.value.borrow_mut();
+                    tracked.0 = index;
+                    tracked.1[index] = value;
+                }
+            });
+            signal.push_derived(react_fn, &new_signal);
+        }
+
+        new_signal
+    }
+
+    /// Let this signal depend on another signal
+    ///
+    /// This synchronizes the value of this signal with the value of the dependency signal.
+    /// Whenever the dependency signal changes, this signal will be updated to match its value.
+    ///
+    /// The returned signal is exactly the argument `dependency`. You can break the dependency chain
+    /// by dropping the returned signal if `dependency` is weakly referenced.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let mut b = Signal::new(2);
+    /// b = a.depend(b);
+    /// a.with(|v| println!("a changed: {}", v));
+    /// b.send(3); // prints "a changed: 3"
+    /// ```
+    ///
+    /// The example above is analogous to:
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = a.map(|v| *v);
+    /// b.with(|v| println!("b changed: {}", v));
+    /// a.send(3); // prints "b changed: 3"
+    /// ```
+    pub fn depend(&self, dependency: Signal<'a, T>) -> Signal<'a, T>
+    where
+        T: Clone,
+    {
+        let self_weak = Rc::downgrade(&self.0);
+        let dependency_weak = Rc::downgrade(&dependency.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(dep) = dependency_weak.upgrade()
+                && let Some(target) = self_weak.upgrade()
+                && !*target.explicitly_modified.borrow()
+            {
+                // Swap values instead of cloning (during reaction only)
+                std::mem::swap(
+                    &mut *target.value.borrow_mut(),
+                    &mut *dep.value.borrow_mut(),
+                );
+            }
+        });
+
+        dependency.push_derived(react_fn, self);
+        dependency
+    }
+
+    pub(crate) fn modify(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.0.value.borrow_mut();
+        f(&mut value);
+    }
+
+    /// Create the signal of a `map` variant, reporting to the same error sink as this signal
+    ///
+    /// Every `map_*` variant seeds its new signal through this, so a failing mapping reaches the
+    /// handlers installed with `on_reaction_error` whichever variant built it. The label and the
+    /// equality predicate describe this signal's own values and are not carried over.
+    fn new_mapped<U: 'a>(&self, initial: U) -> Signal<'a, U> {
+        let new_signal = Signal::new(initial);
+        *new_signal.0.error_sink.borrow_mut() = Some(self.error_sink());
+        new_signal
+    }
+
+    /// Get the error sink of the graph, creating it if needed
+    fn error_sink(&self) -> Rc<ErrorSink<'a>> {
+        self.0
+            .error_sink
+            .borrow_mut()
+            .get_or_insert_with(|| Rc::new(ErrorSink::new()))
+            .clone()
+    }
+
+    /// Report a rejected value to the error sink of the graph, if one is installed
+    fn report_rejected(&self, value: &T)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(sink) = self.0.error_sink.borrow().as_ref() {
+            sink.report(&ReactionError::new(format!(
+                "rejected invalid value: {:?}",
+                value
+            )));
+        }
+    }
+
+    /// Check with the predicate installed by `with_eq` whether two values are the same
+    fn is_unchanged(&self, current: &T, new_value: &T) -> bool {
+        self.0
+            .eq
+            .borrow()
+            .as_ref()
+            .is_some_and(|eq| eq(current, new_value))
+    }
+
+    /// Feed this signal into one field of `lifted`, used by the code `#[derive(Lift)]` generates
+    /// and by the tuple `LiftInto` implementations
+    ///
+    /// On every change the value of this signal is cloned into the field selected by `field`,
+    /// unless `lifted` was explicitly modified in the same propagation.
+    #[doc(hidden)]
+    pub fn lift_field<U: 'a>(&self, lifted: &Signal<'a, U>, field: fn(&mut U) -> &mut T)
+    where
+        T: Clone,
+    {
+        let lifted_ref = Rc::downgrade(&lifted.0);
+        let self_ref = Rc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let (Some(lifted), Some(src)) = (lifted_ref.upgrade(), self_ref.upgrade())
+                && !*lifted.explicitly_modified.borrow()
+            {
+                *field(&mut lifted.value.borrow_mut()) = src.value.borrow().clone();
+            }
+        });
+        self.push_derived(react_fn, lifted);
+    }
+
+    /// Keep one field of `lifted` and this signal in sync both ways, used by the code
+    /// `#[derive(Lift)]` generates for `lift_bidirectional`
+    ///
+    /// Changes of this signal are cloned into the field selected by `field`, and when `lifted`
+    /// is sent to, that field is cloned back into this signal, which then propagates to its
+    /// own dependents like with `promap`. Unlike `lift_field`, both sides keep their values.
+    #[doc(hidden)]
+    pub fn bind_field<U: 'a>(&self, lifted: &Signal<'a, U>, field: fn(&mut U) -> &mut T)
+    where
+        T: Clone,
+    {
+        let lifted_weak = Rc::downgrade(&lifted.0);
+        let self_weak = Rc::downgrade(&self.0);
+
+        // Forward reaction: this signal -> field of lifted
+        let (lifted_ref, self_ref) = (lifted_weak.clone(), self_weak.clone());
+        let forward_react_fn = Box::new(move || {
+            if let (Some(lifted), Some(src)) = (lifted_ref.upgrade(), self_ref.upgrade())
+                && !*lifted.explicitly_modified.borrow()
+            {
+                let value = src.value.borrow().clone();
+                *field(&mut lifted.value.borrow_mut()) = value;
+            }
+        });
+        self.push_derived(forward_react_fn, lifted);
+
+        // Backward reaction: field of lifted -> this signal
+        let backward_react_fn = Box::new(move || {
+            if let Some(lifted) = lifted_weak.upgrade()
+                && *lifted.explicitly_modified.borrow()
+                && let Some(src) = self_weak.upgrade()
+            {
+                let value = field(&mut lifted.value.borrow_mut()).clone();
+                *src.value.borrow_mut() = value;
+                *src.explicitly_modified.borrow_mut() = true;
+            }
+        });
+        lifted.0.react_fns.borrow_mut().push(backward_react_fn);
+        lifted
+            .0
+            .predecessors
+            .borrow_mut()
+            .push(WeakSignalRef::new(self));
+    }
+
+    /// Register a reaction feeding `derived` along with the successor edge to it
+    ///
+    /// The reaction is owned by `derived`, so `prune_dead` drops it once `derived` is gone.
+    fn push_derived<U: 'a>(
+        &self,
+        react_fn: Box<dyn Fn() + 'a>,
+        derived: &Signal<'a, U>,
+    ) -> ReactionId {
+        let id = self
+            .0
+            .react_fns
+            .borrow_mut()
+            .push_owned(react_fn, WeakSignalRef::new(derived));
+        self.0
+            .successors
+            .borrow_mut()
+            .push(WeakSignalRef::new(derived));
+        id
+    }
+
+    /// Register a reaction forwarding the guard of `derived` along with the successor edge to it
+    ///
+    /// Unlike the edge of `push_derived`, propagation does not follow this one: `derived` is
+    /// only marked dirty by the guard the reaction forwards, so it can skip an update. The edge
+    /// still shows `derived` to `successor_count` and `to_dot`, and the reaction is owned by
+    /// `derived`, so both are pruned once it is gone.
+    fn push_forwarded<U: 'a>(
+        &self,
+        react_fn: Box<dyn Fn() -> Option<SignalGuard<'a>> + 'a>,
+        derived: &Signal<'a, U>,
+    ) -> ReactionId {
+        let id = self
+            .0
+            .react_fns
+            .borrow_mut()
+            .push_forwarding_owned(react_fn, WeakSignalRef::new(derived));
+        self.0
+            .successors
+            .borrow_mut()
+            .push(WeakSignalRef::forwarded(derived));
+        id
+    }
+
+    /// Run a reaction, catching a panic so the rest of the graph still settles
+    ///
+    /// A panicking reaction is skipped for this propagation and the panic is reported to the
+    /// error sink of the signal, see `on_reaction_error`. When no handler is installed, the first
+    /// such panic is raised again once the outermost guard has settled the graph.
+    fn run_isolated(&self, react_fn: &Reaction<'a>) -> Option<SignalGuard<'a>> {
+        match panic::catch_unwind(AssertUnwindSafe(|| react_fn.run())) {
+            Ok(forwarded) => forwarded,
+            Err(payload) => {
+                let sink = self.0.error_sink.borrow().clone();
+                let reported = sink.is_some_and(|sink| {
+                    sink.report(&ReactionError::new(format!(
+                        "reaction panicked: {}",
+                        panic_message(&*payload)
+                    )))
+                });
+                if !reported {
+                    UNREPORTED_PANIC.with(|slot| {
+                        slot.borrow_mut().get_or_insert(payload);
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// Keep `target` updated with the value of this signal, returning the reaction doing it
+    fn mirror_into(&self, target: &Signal<'a, T>) -> ReactionId
+    where
+        T: Clone,
+    {
+        let target_weak = Rc::downgrade(&target.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = target_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.borrow().clone();
+                *new_sig.value.borrow_mut() = value;
+            }
+        });
+
+        self.push_derived(react_fn, target)
+    }
+
+    /// Make `target` mirror `inner` instead of the inner signal it currently mirrors
+    ///
+    /// The reaction and the successor edge of the previous inner signal are removed, so its
+    /// later changes no longer reach `target`.
+    fn switch_inner(current: &mut Option<(Self, ReactionId)>, target: &Self, inner: Self)
+    where
+        T: Clone,
+    {
+        if let Some((previous, id)) = current.take() {
+            previous.0.react_fns.borrow_mut().remove(id);
+            previous
+                .0
+                .successors
+                .borrow_mut()
+                .retain(|s| !s.points_to(target));
+        }
+        *target.0.value.borrow_mut() = inner.0.value.borrow().clone();
+        let id = inner.mirror_into(target);
+        *current = Some((inner, id));
+    }
+
+    /// Run the eager reactions right after a direct modification
+    fn react_eager(&self) {
+        *self.0.eagerly_reacted.borrow_mut() = true;
+        let eager_react_fns = self.0.eager_react_fns.borrow().snapshot();
+        eager_react_fns.iter().for_each(|react_fn| {
+            self.run_isolated(react_fn);
+        });
+    }
+
+    fn mark_dirty(&self) {
+        trace(TraceEvent::MarkDirty(self.addr()));
+        *self.0.dirty.borrow_mut() += 1;
+    }
+
+    /// Drop the dead references and call `callback` on the live ones
+    ///
+    /// Forwarded references are skipped, their signals are marked dirty by the guards their
+    /// reactions forward. Returns whether any dead reference was dropped.
+    fn collect_and_iterate<F>(
+        &self,
+        refs: &RefCell<Vec<WeakSignalRef<'a>>>,
+        mut callback: F,
+    ) -> bool
+    where
+        F: FnMut(&dyn SignalExt<'a>),
+    {
+        let len = refs.borrow().len();
+        refs.borrow_mut().retain(|s| s.is_alive());
+        let pruned = refs.borrow().len() != len;
+        for s in refs.borrow().iter().filter(|s| !s.forwarded) {
+            if let Some(signal) = s.upgrade() {
+                callback(&*signal);
+            }
+        }
+        pruned
+    }
+
+    fn collect_guards(&self, result: &mut Vec<SignalGuardInner<'a>>) {
+        trace(TraceEvent::Propagate(self.addr()));
+        // Collect every signal once, in topological order, so each one reacts once per send
+        // and only after all the signals it depends on
+        let mut visited = HashSet::new();
+        let start = result.len();
+        self.collect_guards_recursive(result, &mut visited);
+        result[start..].reverse();
+        // A frozen signal holds back its predecessors too, they are updated once it thaws
+        if *self.0.frozen.borrow() > 0 {
+            *self.0.sent_while_frozen.borrow_mut() = true;
+            return;
+        }
+        self.collect_and_iterate(&self.0.predecessors, |signal| {
+            signal.collect_predecessors_recursive(result, &mut visited);
+        });
+    }
+
+    /// Collect the guard of a signal thawing with changes it held back
+    ///
+    /// A send held back from the predecessors is made again, to write it back to them. Without
+    /// one, the signal was only updated by its sources, so its predecessors are left out.
+    fn thaw_guard(&self) -> SignalGuard<'a> {
+        if self.0.sent_while_frozen.replace(false) {
+            *self.0.explicitly_modified.borrow_mut() = true;
+            return self.guard();
+        }
+        let mut result = Vec::new();
+        self.collect_guards_recursive(&mut result, &mut HashSet::new());
+        result.reverse();
+        SignalGuard(result, None)
+    }
+
+    /// Identify the signal by the address of its inner part, shared by all its clones
+    fn addr(&self) -> usize {
+        Rc::as_ptr(&self.0) as *const () as usize
+    }
+
+    /// Create a signal computed from explicitly declared dependencies
+    ///
+    /// This creates a new signal holding the result of `f`, which is recomputed whenever any
+    /// of `deps` changes. Dependencies are declared rather than tracked: `f` may read anything
+    /// it captured, but only changes of the declared signals trigger a recomputation. This is
+    /// the general multi-input computed signal, without nesting `combine`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new("x");
+    /// let recomputed = std::sync::atomic::AtomicUsize::new(0);
+    /// let count = Signal::computed_from(&[&a, &b], move || {
+    ///     recomputed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1
+    /// });
+    /// let _observer = count.map(|n| println!("recomputed {} times", n));
+    /// a.send(2); // prints "recomputed 2 times"
+    /// b.send("y"); // prints "recomputed 3 times"
+    /// ```
+    pub fn computed_from<F>(deps: &[&dyn ErasedSignal<'a>], f: F) -> Signal<'a, T>
+    where
+        F: Fn() -> T + 'a,
+    {
+        let f = Rc::new(f);
+        let new_signal = Signal::new(f());
+
+        for dep in deps {
+            let SignalHandle(dep) = dep.handle();
+            let new_signal_weak = Rc::downgrade(&new_signal.0);
+            let f = f.clone();
+
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !*new_sig.explicitly_modified.borrow()
+                {
+                    let new_value = f();
+                    *new_sig.value.borrow_mut() = new_value;
+                }
+            });
+
+            dep.push_reaction(react_fn, WeakSignalRef::new(&new_signal));
+            dep.push_successor(WeakSignalRef::new(&new_signal));
+        }
+
+        new_signal
+    }
+
+    /// Reduce a collection of signals into a single aggregate signal
+    ///
+    /// This creates a new signal holding the fold of the current values of all `signals`,
+    /// starting from `init`. The fold is recomputed whenever any of the source signals changes.
+    /// The sources are weakly referenced; a dropped source no longer contributes to the fold.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(2);
+    /// let sum = Signal::reduce(vec![a.clone(), b.clone()], 0, |acc, x| acc + x);
+    /// sum.with(|total| println!("sum changed: {}", total));
+    /// a.send(10); // prints "sum changed: 12"
+    /// ```
+    pub fn reduce<U, F>(signals: Vec<Signal<'a, T>>, init: U, f: F) -> Signal<'a, U>
+    where
+        U: Clone + 'a,
+        F: Fn(U, &T) -> U + 'a,
+    {
+        let sources: Vec<_> = signals.iter().map(|s| Rc::downgrade(&s.0)).collect();
+        let fold = Rc::new(move || {
+            sources
+                .iter()
+                .filter_map(|source| source.upgrade())
+                .fold(init.clone(), |acc, source| f(acc, &source.value.borrow()))
+        });
+
+        let new_signal = Signal::new(fold());
+
+        for signal in &signals {
+            let new_signal_weak = Rc::downgrade(&new_signal.0);
+            let fold = fold.clone();
+
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !*new_sig.explicitly_modified.borrow()
+                {
+                    let new_value = fold();
+                    *new_sig.value.borrow_mut() = new_value;
+                }
+            });
+
+            signal.push_derived(react_fn, &new_signal);
+        }
+
+        new_signal
+    }
+
+    /// Lift an array of liftable items into a signal of an array
+    ///
+    /// This creates a new signal that depends on the provided liftable items.
+    /// Changes to any of the source signals will propagate to the new signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(2);
+    /// let c = Signal::new(3);
+    /// let abc = Signal::<i32>::lift_from_array([&a, &b, &c]);
+    /// abc.with(|values| println!("abc changed: {:?}", values));
+    /// (a.send(10), b.send(20), c.send(30)); // prints "abc changed: [10, 20, 30]"
+    /// ```
+    pub fn lift_from_array<S, const N: usize>(items: [S; N]) -> Signal<'a, [S::Inner; N]>
+    where
+        S: Liftable<'a>,
+        S::Inner: Clone + 'a,
+    {
+        let signals: [Signal<'a, S::Inner>; N] = std::array::from_fn(|i| items[i].as_ref().clone());
+
+        let initial: [S::Inner; N] = std::array::from_fn(|i| signals[i].0.value.borrow().clone());
+        let new_signal: Signal<'a, [S::Inner; N]> = Signal::new(initial);
+
+        let result_new_signal = new_signal.clone();
+
+        for (index, signal) in signals.iter().enumerate() {
+            let new_signal_weak = Rc::downgrade(&new_signal.0);
+            let source_for_closure = Rc::downgrade(&signal.0);
+
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !*new_sig.explicitly_modified.borrow()
+                    && let Some(source) = source_for_closure.upgrade()
+                {
+                    new_sig.value.borrow_mut()[index] = source.value.borrow().clone();
+                }
+            });
+
+            signal.push_derived(react_fn, &new_signal);
+        }
+
+        result_new_signal
+    }
+}
+
+impl<'a, T: 'a> Signal<'a, Vec<T>> {
+    /// Group the elements of the signal by a key
+    ///
+    /// This creates a new signal holding a map from each key to the elements that produced it,
+    /// in their original order. The grouping is recomputed whenever the source signal changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let words = Signal::new(vec!["apple", "avocado", "banana"]);
+    /// let groups = words.group_by(|w| w.chars().next().unwrap());
+    /// let _observer = groups.map(|g| println!("a: {:?}, b: {:?}", g[&'a'], g[&'b']));
+    /// words.send(vec!["blueberry", "apricot"]); // prints "a: ["apricot"], b: ["blueberry"]"
+    /// ```
+    pub fn group_by<K, F>(&self, key: F) -> Signal<'a, HashMap<K, Vec<T>>>
+    where
+        K: Eq + Hash + 'a,
+        T: Clone,
+        F: Fn(&T) -> K + 'a,
+    {
+        self.map(move |items| {
+            let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+            for item in items {
+                groups.entry(key(item)).or_default().push(item.clone());
+            }
+            groups
+        })
+    }
+
+    /// Diff the elements of the signal by key on every change
+    ///
+    /// This creates a new signal holding the list of changes, as `ListChange`s, that turn the
+    /// previous vector into the new one, instead of the vector itself. Elements are identified
+    /// across changes by `key`, which should be unique within the vector, so a reordered element
+    /// is reported as a `Move` rather than a removal and an insertion. The initial value of the
+    /// new signal inserts every element. This is the building block for rendering reactive
+    /// lists, where moved elements can be animated instead of rebuilt.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::{ListChange, Signal};
+    /// let todos = Signal::new(vec![(1, "write"), (2, "test"), (3, "ship")]);
+    /// let changes = todos.diff_keyed(|(id, _)| *id);
+    /// let _observer = changes.map(|c| println!("{:?}", c));
+    /// todos.send(vec![(3, "ship"), (1, "write"), (2, "test")]);
+    /// // prints "[Move { key: 3, from: 2, to: 0 }]"
+    /// ```
+    pub fn diff_keyed<K, F>(&self, key: F) -> Signal<'a, Vec<ListChange<K, T>>>
+    where
+        K: Eq + Hash + Clone + 'a,
+        T: Clone + PartialEq,
+        F: Fn(&T) -> K + 'a,
+    {
+        let previous: RefCell<Vec<(K, T)>> = RefCell::new(Vec::new());
+        self.map(move |items| {
+            let next: Vec<(K, T)> = items.iter().map(|item| (key(item), item.clone())).collect();
+            let changes = diff_keyed_changes(&previous.borrow(), &next);
+            *previous.borrow_mut() = next;
+            changes
+        })
+    }
+
+    /// Map the elements of the signal by key, remapping only the elements that changed
+    ///
+    /// This creates a new signal holding `map` applied to each element, like mapping the vector,
+    /// but the results are cached by `key` across changes: on each change, `map` only runs for
+    /// the elements whose key is new or whose value differs from the last time, and the cached
+    /// result is reused for the others, whatever their new position. This saves an expensive
+    /// `map` when a long list changes one element at a time. Keys should be unique within the
+    /// vector; the cache only keeps the keys of the current vector.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let todos = Signal::new(vec![(1, "write"), (2, "test")]);
+    /// let rows = todos.map_keyed(|(id, _)| *id, |(id, text)| {
+    ///     println!("rendering {}", id);
+    ///     format!("<li>{}</li>", text)
+    /// }); // prints "rendering 1" and "rendering 2"
+    /// todos.send(vec![(2, "test"), (1, "write"), (3, "ship")]); // prints "rendering 3"
+    /// ```
+    pub fn map_keyed<K, U, KeyFn, MapFn>(&self, key: KeyFn, map: MapFn) -> Signal<'a, Vec<U>>
+    where
+        K: Eq + Hash + 'a,
+        U: Clone + 'a,
+        T: Clone + PartialEq,
+        KeyFn: Fn(&T) -> K + 'a,
+        MapFn: Fn(&T) -> U + 'a,
+    {
+        let cache: RefCell<HashMap<K, (T, U)>> = RefCell::new(HashMap::new());
+        self.map(move |items| {
+            let mut previous = cache.take();
+            let mut next_cache = HashMap::with_capacity(items.len());
+            let mapped = items
+                .iter()
+                .map(|item| {
+                    let k = key(item);
+                    let result = match previous.remove(&k) {
+                        Some((cached_item, result)) if cached_item == *item => result,
+                        _ => map(item),
+                    };
+                    next_cache.insert(k, (item.clone(), result.clone()));
+                    result
+                })
+                .collect();
+            cache.replace(next_cache);
+            mapped
+        })
+    }
+
+    /// Bind each element of the signal to its own child signal, in both directions
+    ///
+    /// This creates a signal holding one child signal per element. Sending to a child writes
+    /// the value back into the vector at the child's index, and changes of the vector are
+    /// pushed down to the children. When the length of the vector changes, children are
+    /// added or removed at the end and the list of children is updated; the child at a given
+    /// index stays the same as long as that index exists. A child that was removed is unbound
+    /// and sending to it no longer affects the vector. A direct `send` to the vector wins over
+    /// child edits made in the same batch.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let items = Signal::new(vec![1, 2, 3]);
+    /// let children = items.bind_elements();
+    /// let _observer = items.map(|v| println!("items: {:?}", v));
+    /// let _edit = children.map(|c| {
+    ///     c[1].send(20); // prints "items: [1, 20, 3]"
+    /// });
+    /// ```
+    pub fn bind_elements(&self) -> Signal<'a, Vec<Signal<'a, T>>>
+    where
+        T: Clone,
+    {
+        let children = Signal::new(Vec::new());
+        let parent_weak = Rc::downgrade(&self.0);
+        let children_weak = Rc::downgrade(&children.0);
+        let from_child = Rc::new(Cell::new(false));
+
+        let initial = self
+            .0
+            .value
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                Self::bind_element(
+                    &parent_weak,
+                    &children_weak,
+                    &from_child,
+                    index,
+                    value.clone(),
+                )
+            })
+            .collect();
+        *children.0.value.borrow_mut() = initial;
+
+        let react_fn = Box::new(move || {
+            // A single element was written back by a child, the others are unchanged
+            if from_child.replace(false) {
+                return None;
+            }
+            let parent = parent_weak.upgrade()?;
+            let list = children_weak.upgrade()?;
+
+            let values = parent.value.borrow();
+            let mut guards = Vec::new();
+            let mut bound = list.value.borrow_mut();
+            let resized = bound.len() != values.len();
+            bound.truncate(values.len());
+            for (index, value) in values.iter().enumerate() {
+                if let Some(child) = bound.get(index) {
+                    child.modify(|v| *v = value.clone());
+                    guards.push(child.guard());
+                } else {
+                    bound.push(Self::bind_element(
+                        &parent_weak,
+                        &children_weak,
+                        &from_child,
+                        index,
+                        value.clone(),
+                    ));
+                }
+            }
+            drop(bound);
+            if resized {
+                guards.push(Signal(list).guard());
+            }
+            guards.into_iter().reduce(SignalGuard::and)
+        });
+
+        self.0
+            .react_fns
+            .borrow_mut()
+            .push_forwarding_owned(react_fn, WeakSignalRef::new(&children));
+        children
+    }
+
+    /// Create a child signal that writes back into the vector at `index`
+    fn bind_element(
+        parent: &Weak<SignalInner<'a, Vec<T>>>,
+        list: &Weak<SignalInner<'a, Vec<Signal<'a, T>>>>,
+        from_child: &Rc<Cell<bool>>,
+        index: usize,
+        value: T,
+    ) -> Signal<'a, T>
+    where
+        T: Clone,
+    {
+        let child = Signal::new(value);
+        let child_weak = Rc::downgrade(&child.0);
+        let parent = parent.clone();
+        let list = list.clone();
+        let from_child = from_child.clone();
+
+        let react_fn = Box::new(move || {
+            let child = child_weak.upgrade()?;
+            let parent = parent.upgrade()?;
+            let list = list.upgrade()?;
+            if !*child.explicitly_modified.borrow() || *parent.explicitly_modified.borrow() {
+                return None;
+            }
+            // Only a child that is still bound at its index writes back
+            let still_bound = list
+                .value
+                .borrow()
+                .get(index)
+                .is_some_and(|bound| Rc::ptr_eq(&bound.0, &child));
+            if !still_bound {
+                return None;
+            }
+            let value = child.value.borrow().clone();
+            from_child.set(true);
+            Some(Signal(parent).send_with(|items| items[index] = value))
+        });
+
+        child.0.react_fns.borrow_mut().push_forwarding(react_fn);
+        child
+    }
+}
+
+impl<'a, T: 'a, E: 'a> Signal<'a, Result<T, E>> {
+    /// Split the signal into one signal for the `Ok` values and one for the `Err` values
+    ///
+    /// Each of the new signals holds the latest value of its variant and only emits when the
+    /// source holds that variant; it keeps its previous value while the source holds the other
+    /// variant. A signal holds `None` until the source first holds its variant.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let parsed = Signal::new("1".parse::<i32>());
+    /// let (ok, err) = parsed.split_result();
+    /// let _ok = ok.map(|v| println!("ok: {:?}", v)); // prints "ok: Some(1)"
+    /// let _err = err.map(|e| println!("err: {:?}", e)); // prints "err: None"
+    /// parsed.send("x".parse::<i32>()); // prints "err: Some(ParseIntError { kind: InvalidDigit })" only
+    /// ```
+    pub fn split_result(&self) -> (Signal<'a, Option<T>>, Signal<'a, Option<E>>)
+    where
+        T: Clone,
+        E: Clone,
+    {
+        let (ok_signal, err_signal) = match &*self.0.value.borrow() {
+            Ok(value) => (Signal::new(Some(value.clone())), Signal::new(None)),
+            Err(error) => (Signal::new(None), Signal::new(Some(error.clone()))),
+        };
+        let ok_weak = Rc::downgrade(&ok_signal.0);
+        let err_weak = Rc::downgrade(&err_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            let src = source_weak.upgrade()?;
+            let value = src.value.borrow();
+            match &*value {
+                Ok(value) => {
+                    let ok = ok_weak.upgrade()?;
+                    *ok.value.borrow_mut() = Some(value.clone());
+                    Some(Signal(ok).guard())
+                }
+                Err(error) => {
+                    let err = err_weak.upgrade()?;
+                    *err.value.borrow_mut() = Some(error.clone());
+                    Some(Signal(err).guard())
+                }
+            }
+        });
+
+        self.0.react_fns.borrow_mut().push_forwarding(react_fn);
+        (ok_signal, err_signal)
+    }
+
+    /// Create a signal that only takes the `Ok` values of the current signal
+    ///
+    /// The new signal is updated, and its observers notified, only when the current signal
+    /// holds an `Ok` value; an `Err` is skipped and the new signal keeps the last successful
+    /// value. It holds `None` until the current signal first holds an `Ok` value. Together with
+    /// `try_map`, this keeps validation pipelines reactive while errors go to the error sink.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let input = Signal::new(String::from("1"));
+    /// let parsed = input.try_map(|s| s.parse::<i32>());
+    /// let valid = parsed.filter_ok();
+    /// let _observer = valid.map(|v| println!("valid: {:?}", v)); // prints "valid: Some(1)"
+    /// input.send(String::from("x")); // prints nothing
+    /// input.send(String::from("2")); // prints "valid: Some(2)"
+    /// ```
+    pub fn filter_ok(&self) -> Signal<'a, Option<T>>
+    where
+        T: Clone,
+    {
+        let new_signal = Signal::new(self.0.value.borrow().as_ref().ok().cloned());
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if *new_sig.explicitly_modified.borrow() {
+                return None;
+            }
+            let value = src.value.borrow().as_ref().ok().cloned()?;
+            *new_sig.value.borrow_mut() = Some(value);
+            Some(Signal(new_sig).guard())
+        });
+
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Signal<'a, (A, B)> {
+    /// Split a signal of pairs into one signal per component
+    ///
+    /// This is the inverse of `combine`: each of the new signals holds its component of the
+    /// current pair and is updated whenever the current signal changes, even when only the
+    /// other component did. Chain `distinct` on a half to skip the unchanged values.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let pair = Signal::new((1, "one"));
+    /// let (number, name) = pair.split();
+    /// let _number = number.map(|n| println!("number: {}", n)); // prints "number: 1"
+    /// let _name = name.map(|s| println!("name: {}", s)); // prints "name: one"
+    /// pair.send((2, "two")); // prints "number: 2" and "name: two"
+    /// ```
+    pub fn split(&self) -> (Signal<'a, A>, Signal<'a, B>)
+    where
+        A: Clone,
+        B: Clone,
+    {
+        (self.map(|(a, _)| a.clone()), self.map(|(_, b)| b.clone()))
+    }
+}
+
+impl<'a, T: 'a> Signal<'a, Signal<'a, T>> {
+    /// Flatten a signal of signals into a signal mirroring the current inner signal
+    ///
+    /// This creates a new signal that holds the value of the inner signal currently held by
+    /// this signal, and follows its changes. When this signal switches to another inner signal,
+    /// the new signal takes its value and unsubscribes from the previous inner signal, so stale
+    /// updates of the previous inner signal don't leak through.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::Signal;
+    /// let a = Signal::new(1);
+    /// let b = Signal::new(10);
+    /// let current = Signal::new(a.clone());
+    /// let flat = current.flatten();
+    /// let _observer = flat.map(|x| println!("flat: {}", x)); // prints "flat: 1"
+    /// a.send(2); // prints "flat: 2"
+    /// current.send(b.clone()); // prints "flat: 10"
+    /// a.send(3); // prints nothing
+    /// ```
+    pub fn flatten(&self) -> Signal<'a, T>
+    where
+        T: Clone,
+    {
+        let inner = self.0.value.borrow().clone();
+        let new_signal = Signal::new(inner.0.value.borrow().clone());
+        let mut current = None;
+        Signal::switch_inner(&mut current, &new_signal, inner);
+        let subscription = RefCell::new(current);
+
+        let new_signal_weak = Rc::downgrade(&new_signal.0);
+        let source_weak = Rc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !*new_sig.explicitly_modified.borrow()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let inner = src.value.borrow().clone();
+                Signal::switch_inner(&mut subscription.borrow_mut(), &Signal(new_sig), inner);
+            }
+        });
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+}
+
+impl<'a, T: 'a> SignalExt<'a> for Signal<'a, T> {
+    fn react(&self) -> Vec<SignalGuardInner<'a>> {
+        if *self.0.frozen.borrow() > 0 {
+            *self.0.missed_while_frozen.borrow_mut() = true;
+            return Vec::new();
+        }
+        trace(TraceEvent::React(self.addr()));
+        #[cfg(test)]
+        self.0
+            .reactions
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        // Eager reactions already ran if the value was modified directly
+        if !self.0.eagerly_reacted.replace(false) {
+            let eager_react_fns = self.0.eager_react_fns.borrow().snapshot();
+            eager_react_fns.iter().for_each(|react_fn| {
+                self.run_isolated(react_fn);
+            });
+        }
+        let react_fns = self.0.react_fns.borrow().snapshot();
+        react_fns
+            .iter()
+            .filter_map(|react_fn| self.run_isolated(react_fn))
+            .flat_map(|mut guard| std::mem::take(&mut guard.0))
+            .collect()
+    }
+    fn guard(&self) -> SignalGuard<'a> {
+        // Reuse the cached buffer if enabled; a reentrant send finds it taken and allocates
+        let cached = self
+            .0
+            .guard_scratch
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take);
+        let home = cached.is_some().then(|| self.clone_box());
+        let mut result = cached.unwrap_or_default();
+        self.collect_guards(&mut result);
+        SignalGuard(result, home)
+    }
+    fn clone_box(&self) -> Box<dyn SignalExt<'a> + 'a> {
+        Box::new(Signal(Rc::clone(&self.0)))
+    }
+    fn decrease_dirty(&self) {
+        *self.0.dirty.borrow_mut() -= 1;
+    }
+    fn get_dirty(&self) -> isize {
+        *self.0.dirty.borrow()
+    }
+    fn reset_explicitly_modified(&self) {
+        *self.0.explicitly_modified.borrow_mut() = false;
+    }
+    fn return_guard_buffer(&self, mut buffer: Vec<SignalGuardInner<'a>>) {
+        buffer.clear();
+        if let Some(scratch) = self.0.guard_scratch.borrow_mut().as_mut()
+            && buffer.capacity() > scratch.capacity()
+        {
+            *scratch = buffer;
+        }
+    }
+    fn write_dot(&self, graph: &mut DotGraph) -> usize {
+        let (id, new) = graph.node(
+            self.addr(),
+            *self.0.label.borrow(),
+            self.get_dirty() != 0,
+            *self.0.explicitly_modified.borrow(),
+        );
+        if !new {
+            return id;
+        }
+        // Upgrade first, so no borrow is held while the rest of the graph is walked
+        let successors: Vec<_> = self
+            .0
+            .successors
+            .borrow()
+            .iter()
+            .filter_map(WeakSignalRef::upgrade)
+            .collect();
+        for signal in successors {
+            let to = signal.write_dot(graph);
+            graph.edge(id, to, false);
+        }
+        let predecessors: Vec<_> = self
+            .0
+            .predecessors
+            .borrow()
+            .iter()
+            .filter_map(WeakSignalRef::upgrade)
+            .collect();
+        for signal in predecessors {
+            let to = signal.write_dot(graph);
+            graph.edge(id, to, true);
+        }
+        id
+    }
+    fn push_reaction(&self, react_fn: Box<dyn Fn() + 'a>, owner: WeakSignalRef<'a>) -> ReactionId {
+        self.0.react_fns.borrow_mut().push_owned(react_fn, owner)
+    }
+    fn push_successor(&self, successor: WeakSignalRef<'a>) {
+        self.0.successors.borrow_mut().push(successor);
+    }
+    fn collect_guards_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInner<'a>>,
+        visited: &mut HashSet<usize>,
+    ) {
+        // A signal reached through several paths, like the bottom of a diamond, is collected once;
+        // this also cuts cycles, which settle after a single pass
+        if !visited.insert(self.addr()) {
+            return;
+        }
+        self.mark_dirty();
+        // A frozen signal holds back its successors as well, they are updated once it thaws
+        if *self.0.frozen.borrow() > 0 {
+            result.push(SignalGuardInner(self.clone_box(), false));
+            return;
+        }
+        let mut successors = Vec::new();
+        let pruned = self.collect_and_iterate(&self.0.successors, |signal| {
+            successors.push(signal.clone_box());
+        });
+        if pruned {
+            // The reactions that fed the dropped successors are dead as well
+            self.0.react_fns.borrow_mut().retain_alive();
+            self.0.eager_react_fns.borrow_mut().retain_alive();
+        }
+        // Post-order, reversed by `collect_guards`; visit successors backwards to keep their order
+        for signal in successors.iter().rev() {
+            signal.collect_guards_recursive(result, visited);
+        }
+        result.push(SignalGuardInner(self.clone_box(), false));
+    }
+    fn collect_predecessors_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInner<'a>>,
+        visited: &mut HashSet<usize>,
+    ) {
+        if !visited.insert(self.addr()) {
+            return;
+        }
+        self.mark_dirty();
+        result.push(SignalGuardInner(self.clone_box(), true));
+        // Collect predecessors last so they drop last (react last)
+        self.collect_and_iterate(&self.0.predecessors, |signal| {
+            signal.collect_predecessors_recursive(result, visited);
+        });
+    }
+}
+
+impl<T> Clone for Signal<'_, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, T> AsRef<Signal<'a, T>> for Signal<'a, T> {
+    fn as_ref(&self) -> &Signal<'a, T> {
+        self
+    }
+}
+
+/// Creates a signal holding the default value of `T`
+impl<'a, T: Default + 'a> Default for Signal<'a, T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Creates a signal holding the given value, like `Signal::new`
+impl<'a, T: 'a> From<T> for Signal<'a, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Compares the current values of two signals
+///
+/// This compares snapshots of the values, not the identity of the signals: two unrelated
+/// signals holding equal values are equal, and may stop being so after a send.
+impl<T: PartialEq> PartialEq for Signal<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        *self.0.value.borrow() == *other.0.value.borrow()
+    }
+}
+
+/// Formats the label, the current value and the wiring of the signal, for diagnostics
+///
+/// A value borrowed mutably, for example by a reaction in progress, is shown as `<borrowed>`.
+impl<T: fmt::Debug> fmt::Debug for Signal<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Signal");
+        if let Some(label) = *self.0.label.borrow() {
+            debug.field("label", &label);
+        }
+        match self.0.value.try_borrow() {
+            Ok(value) => debug.field("value", &*value),
+            Err(_) => debug.field("value", &format_args!("<borrowed>")),
+        };
+        let alive = |refs: &RefCell<Vec<WeakSignalRef<'_>>>| {
+            refs.try_borrow()
+                .map(|refs| refs.iter().filter(|s| s.is_alive()).count())
+                .ok()
+        };
+        debug
+            .field("successors", &alive(&self.0.successors))
+            .field("predecessors", &alive(&self.0.predecessors))
+            .field("dirty", &*self.0.dirty.borrow())
+            .finish()
+    }
+}
+
+/// Formats the current value of the signal, or `<borrowed>` if it is borrowed mutably
+impl<T: fmt::Display> fmt::Display for Signal<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.value.try_borrow() {
+            Ok(value) => value.fmt(f),
+            Err(_) => f.write_str("<borrowed>"),
+        }
+    }
+}
+
+/// Implement arithmetic operators on references to signals
+///
+/// The result stays equal to the operation applied to both operands, like `combine_map`, but
+/// is strongly referenced by its operands, like `and`, so expressions such as
+/// `&(&a + &b) * &c` keep working after the intermediate signals are dropped.
+macro_rules! impl_signal_ops {
+    ($($op:ident::$method:ident),*) => {$(
+        impl<'a, T> ops::$op for &Signal<'a, T>
+        where
+            T: Copy + ops::$op<Output = T> + 'a,
+        {
+            type Output = Signal<'a, T>;
+
+            fn $method(self, rhs: Self) -> Signal<'a, T> {
+                self.combine_map_ref::<T, T, _, StrongRefStrategy>(rhs, |x, y| ops::$op::$method(*x, *y))
+            }
+        }
+    )*};
+}
+
+impl_signal_ops!(Add::add, Sub::sub, Mul::mul, Div::div);
+
+#[cfg(test)]
+#[allow(clippy::unnecessary_operation)]
+mod tests {
+
+    use crate::api::LiftInto;
+
+    use super::*;
+
+    #[test]
+    fn test_signal() {
+        let a = Signal::new(0);
+        let _a = a.map(|x| println!("a changed: {}", x));
+        (a.send(100), a.send(5));
+    }
+
+    #[test]
+    fn test_signal1() {
+        let a = Signal::new(0);
+        let b = a.map(|x| x * 2);
+        let _b = b.map(|x| println!("b changed: {}", x));
+        drop(b);
+        a.send(100);
+    }
+
+    #[test]
+    fn test_signal2() {
+        let a = Signal::new(0);
+        let b = a.map(|x| x * 2);
+        let ab = a.combine(&b);
+        let _ab = ab.map(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+        (a.send(5), a.send(100));
+    }
+
+    #[test]
+    fn test_signal3() {
+        let a = Signal::new(0);
+        let b = a.map(|x| x * 2);
+        let ab = (&a, &b).lift();
+        let _ab = ab.map(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+        (a.send(5), b.send(100));
+    }
+
+    #[test]
+    fn test_signal4() {
+        let a = Signal::new(0);
+        let b = Signal::new(10);
+        let c = Signal::new(20);
+        let abc = [&a, &b, &c].lift();
+        let _abc =
+            abc.map(|[x, y, z]| println!("d changed: {} + {} + {} = {}", x, y, z, x + y + z));
+        let d = Signal::new(0);
+        let abcd = abc.combine(&d);
+        let _abcd = abcd.map(|numbers| {
+            println!(
+                "e changed: {} + {} + {} + {} = {}",
+                numbers.0[0],
+                numbers.0[1],
+                numbers.0[2],
+                numbers.1,
+                numbers.0.iter().sum::<i32>() + numbers.1
+            )
+        });
+        (a.send(5), b.send(15), c.send(25), abc.send([2, 3, 4]));
+    }
+
+    #[test]
+    fn test_signal5() {
+        let result = Signal::new(42);
+        let source1 = result.contramap(|x| x + 1);
+        let source2 = source1.contramap(|x| x * 2);
+
+        let _observer_1 = result.map(|x| println!("result changed: {}", x));
+        let _observer_2 = source1.map(|x| println!("source1 changed: {}", x));
+        let _observer_3 = source2.map(|x| println!("source2 changed: {}", x));
+
+        println!("--- Sending to source1 ---");
+        source1.send(100);
+        println!("--- Sending to source2 ---");
+        source2.send(200);
+        println!("--- Sending to source1 and source2 ---");
+        (source1.send(300), source2.send(400));
+    }
+
+    #[test]
+    fn test_promap_forward() {
+        let source = Signal::new(10);
+        let derived = source.promap(|x| x * 2, |y| y / 2);
+
+        let _ = derived.map(|x| println!("derived changed: {}", x));
+        source.send(5);
+    }
+
+    #[test]
+    fn test_promap_backward() {
+        let source = Signal::new(10);
+        let derived = source.promap(|x| x * 2, |y| y / 2);
+
+        let _ = source.map(|x| println!("source changed: {}", x));
+        let _ = derived.map(|x| println!("derived changed: {}", x));
+        derived.send(50);
+    }
+
+    #[test]
+    fn test_promap_bidirectional() {
+        let a = Signal::new(10);
+        let b = a.promap(|x| x * 2, |y| y / 2);
+        let c = b.promap(|x| x + 3, |y| y - 3);
+
+        let _observer_a = a.map(|x| println!("a changed: {}", x));
+        let _observer_b = b.map(|x| println!("b changed: {}", x));
+        let _observer_c = c.map(|x| println!("c changed: {}", x));
+
+        println!("--- Sending to a ---");
+        a.send(5);
+        println!("--- Sending to c ---");
+        c.send(13);
+        println!("--- Sending to b ---");
+        b.send(10);
+        println!("--- Sending to a and c ---");
+        a.send(20).and(c.send(50));
+        println!("--- Sending to b and c ---");
+        (b.send(30), c.send(60));
+    }
+
+    #[test]
+    fn test_high_order_signal() {
+        let source = Signal::new(0);
+        let derived = source.map(|x| Signal::new(x + 1));
+        let _ = derived.map(|s| s.send(233));
+        let _ = derived.map(|s| s.map(|x| println!("derived changed: {}", x)));
+        source.send(5);
+    }
+
+    #[test]
+    fn test_depend() {
+        let a = Signal::new(10);
+        let b = Signal::new(10);
+        let c = Signal::new(10);
+
+        let _observer_a = a.map(|x| println!("a changed: {}", x));
+        let _observer_b = b.map(|x| println!("b changed: {}", x));
+        let _observer_c = c.map(|x| println!("c changed: {}", x));
+
+        c.depend(b.with(|x| x * 2));
+        b.depend(a.clone());
+
+        a.send(42);
+    }
+
+    #[test]
+    fn test_depend2() {
+        let a = Signal::new(10);
+        let b = a.map(|x| *x);
+        let c = b.map(|x| *x);
+
+        let _observer_a = a.map(|x| println!("a changed: {}", x));
+        let _observer_b = b.map(|x| println!("b changed: {}", x));
+        let _observer_c = c.map(|x| println!("c changed: {}", x));
+
+        (a.send(42), b.send(88));
+    }
+
+    #[test]
+    fn test_group_by() {
+        let numbers = Signal::new(vec![1, 2, 3, 4]);
+        let groups = numbers.group_by(|x| x % 2 == 0);
+        assert_eq!(groups.0.value.borrow()[&true], vec![2, 4]);
+        assert_eq!(groups.0.value.borrow()[&false], vec![1, 3]);
+
+        numbers.send(vec![5, 7, 8]);
+        assert_eq!(groups.0.value.borrow()[&true], vec![8]);
+        assert_eq!(groups.0.value.borrow()[&false], vec![5, 7]);
+    }
+
+    #[test]
+    fn test_tee_shares_computation() {
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let calls_clone = calls.clone();
+        let a = Signal::new(1);
+        let shared = a.map(move |x| {
+            calls_clone.set(calls_clone.get() + 1);
+            x * 10
+        });
+        let branches = shared.tee(3);
+        assert_eq!(calls.get(), 1);
+
+        a.send(2);
+        assert_eq!(calls.get(), 2);
+        for branch in &branches {
+            assert_eq!(*branch.0.value.borrow(), 20);
+        }
+    }
+
+    #[test]
+    fn test_map_eager() {
+        let seen_eager = Rc::new(RefCell::new(Vec::new()));
+        let seen_batched = Rc::new(RefCell::new(Vec::new()));
+        let a = Signal::new(0);
+        let eager_log = seen_eager.clone();
+        let _eager = a.map_eager(move |x| eager_log.borrow_mut().push(*x));
+        let batched_log = seen_batched.clone();
+        let _batched = a.map(move |x| batched_log.borrow_mut().push(*x));
+
+        (a.send(1), a.send(2), a.send(3));
+        assert_eq!(*seen_eager.borrow(), vec![0, 1, 2, 3]);
+        assert_eq!(*seen_batched.borrow(), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_map_eager_on_derived() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let a = Signal::new(0);
+        let b = a.map(|x| x * 2);
+        let log = seen.clone();
+        let _eager = b.map_eager(move |x| log.borrow_mut().push(*x));
+
+        (a.send(1), a.send(2));
+        assert_eq!(*seen.borrow(), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_label() {
+        let a = Signal::new(0);
+        assert_eq!(a.label(), None);
+        let a = a.with_label("a");
+        let b = a.clone();
+        assert_eq!(b.label(), Some("a"));
+    }
+
+    #[test]
+    fn test_contramap_promap_with() {
+        struct Celsius(f64);
+
+        let fahrenheit = Signal::new(212.0);
+        let celsius = fahrenheit.contramap_with(Celsius(100.0), |c| c.0 * 9.0 / 5.0 + 32.0);
+        assert_eq!(celsius.0.value.borrow().0, 100.0);
+        celsius.send(Celsius(0.0));
+        assert_eq!(*fahrenheit.0.value.borrow(), 32.0);
+
+        let kelvin = fahrenheit.promap_with(
+            Celsius(273.15),
+            |f| Celsius((f - 32.0) * 5.0 / 9.0 + 273.15),
+            |k| (k.0 - 273.15) * 9.0 / 5.0 + 32.0,
+        );
+        fahrenheit.send(212.0);
+        assert!((kelvin.0.value.borrow().0 - 373.15).abs() < 1e-9);
+        kelvin.send(Celsius(273.15));
+        assert!((*fahrenheit.0.value.borrow() - 32.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reduce() {
+        let a = Signal::new(1);
+        let b = Signal::new(5);
+        let c = Signal::new(3);
+        let max = Signal::reduce(vec![a.clone(), b.clone(), c.clone()], i32::MIN, |acc, x| {
+            acc.max(*x)
+        });
+        assert_eq!(*max.0.value.borrow(), 5);
+
+        a.send(10);
+        assert_eq!(*max.0.value.borrow(), 10);
+        (a.send(0), b.send(1), c.send(2));
+        assert_eq!(*max.0.value.borrow(), 2);
+    }
+
+    #[test]
+    fn test_observe_weak() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let a = Signal::new(0);
+        let log = seen.clone();
+        let observer = a.observe_weak(move |x| log.borrow_mut().push(*x));
+        assert_eq!(Rc::strong_count(&a.0), 1);
+
+        (a.send(1), a.send(2));
+        assert_eq!(*seen.borrow(), vec![2]);
+
+        drop(observer);
+        assert!(a.0.react_fns.borrow().snapshot().is_empty());
+        a.send(3);
+        assert_eq!(*seen.borrow(), vec![2]);
+    }
+
+    #[test]
+    fn test_map_copied() {
+        let a = Signal::new(1);
+        let a_clone = a.clone();
+        // The source is not borrowed while the closure runs
+        let b = a.map_copied(move |x| x + *a_clone.0.value.borrow());
+        assert_eq!(*b.0.value.borrow(), 2);
+
+        a.send(21);
+        assert_eq!(*b.0.value.borrow(), 42);
+    }
+
+    #[test]
+    fn test_event_log() {
+        let a = Signal::new(0);
+        let log = a.event_log();
+        let bounded = a.event_log_bounded(2);
+
+        a.send(1);
+        (a.send(2), a.send(3));
+        a.send(4);
+        assert_eq!(*log.0.value.borrow(), vec![(0, 0), (1, 1), (2, 3), (3, 4)]);
+        assert_eq!(*bounded.0.value.borrow(), vec![(2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn test_map_forward() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let sum = Signal::reduce(vec![a.clone(), b.clone()], 0, |acc, x| acc + x);
+
+        let target = b.clone();
+        let _forward = a.map_forward(move |x| ((), Some(target.send(x * 10))));
+        let log = seen.clone();
+        let _observer_b = b.map(move |x| log.borrow_mut().push(format!("b: {}", x)));
+        let log = seen.clone();
+        let _observer_a = a.map(move |x| log.borrow_mut().push(format!("a: {}", x)));
+        let log = seen.clone();
+        let _observer_sum = sum.map(move |x| log.borrow_mut().push(format!("sum: {}", x)));
+        seen.borrow_mut().clear();
+
+        a.send(1);
+        assert_eq!(*b.0.value.borrow(), 10);
+        assert_eq!(*sum.0.value.borrow(), 11);
+        assert_eq!(*seen.borrow(), vec!["a: 1", "b: 10", "sum: 11"]);
+    }
+
+    #[test]
+    fn test_fork() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x * 10);
+        let draft = a.fork();
+        let draft_b = draft.map(|x| x + 1);
+        assert_eq!(*draft.0.value.borrow(), 1);
+
+        draft.send(2);
+        assert_eq!(*a.0.value.borrow(), 1);
+        assert_eq!(*b.0.value.borrow(), 10);
+        assert_eq!(*draft_b.0.value.borrow(), 3);
+
+        a.send(3);
+        assert_eq!(*b.0.value.borrow(), 30);
+        assert_eq!(*draft.0.value.borrow(), 2);
+        assert_eq!(*draft_b.0.value.borrow(), 3);
+    }
+
+    #[test]
+    fn test_erase() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x * 10);
+        let signals: Vec<BoxedSignal> = vec![a.erase(), Signal::new(String::from("one")).erase()];
+        assert!(signals[0].is::<i32>());
+        assert_eq!(signals[1].type_name(), "alloc::string::String");
+        assert!(signals[1].downcast_ref::<i32>().is_none());
+
+        signals[0].downcast_ref::<i32>().unwrap().send(2);
+        assert_eq!(*b.0.value.borrow(), 20);
+
+        let erased = signals[1].clone();
+        let Err(erased) = erased.downcast::<i32>() else {
+            panic!("downcast to the wrong type succeeded");
+        };
+        let Ok(text) = erased.downcast::<String>() else {
+            panic!("downcast to the erased type failed");
+        };
+        text.send(String::from("two"));
+        assert_eq!(
+            *signals[1]
+                .downcast_ref::<String>()
+                .unwrap()
+                .0
+                .value
+                .borrow(),
+            "two"
+        );
+    }
+
+    #[test]
+    fn test_sample_when() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let value = Signal::new(0);
+        let clock = Signal::new(0);
+        let sampled = value.sample_when(&clock);
+        let log = seen.clone();
+        let _observer = sampled.map(move |x| log.borrow_mut().push(*x));
+
+        value.send(1);
+        value.send(2);
+        assert_eq!(*sampled.0.value.borrow(), 0);
+        clock.send(1);
+        assert_eq!(*sampled.0.value.borrow(), 2);
+        // `value` did not change between the ticks, so the same value is emitted again
+        clock.send(2);
+        (value.send(3), clock.send(3));
+        assert_eq!(*seen.borrow(), vec![0, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_try_map() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let input = Signal::new(String::from("1"));
+        let doubled = input.map(|s| s.repeat(2));
+        let log = errors.clone();
+        doubled.on_reaction_error(move |e| log.borrow_mut().push(e.message().to_string()));
+        let parsed = doubled.try_map(|s| s.parse::<i32>());
+        assert_eq!(*parsed.0.value.borrow(), Ok(11));
+
+        input.send(String::from("2"));
+        assert_eq!(*parsed.0.value.borrow(), Ok(22));
+        input.send(String::from("x"));
+        assert!(parsed.0.value.borrow().is_err());
+        assert_eq!(*errors.borrow(), vec!["invalid digit found in string"]);
+
+        // a handler registered upstream sees errors of the whole subgraph
+        let log = errors.clone();
+        input.on_reaction_error(move |e| log.borrow_mut().push(e.to_string()));
+        input.send(String::from("y"));
+        assert_eq!(errors.borrow().len(), 3);
+        assert_eq!(
+            errors.borrow()[2],
+            "reaction error: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_bind_elements() {
+        let items = Signal::new(vec![1, 2, 3]);
+        let children = items.bind_elements();
+        let child_updates = Rc::new(RefCell::new(0));
+        let counter = child_updates.clone();
+        let _child_observer = children.0.value.borrow()[0].map(move |_| {
+            *counter.borrow_mut() += 1;
+        });
+        let lengths = children.map(|c| c.len());
+
+        // Editing a child writes back without touching the other children
+        let child = children.0.value.borrow()[1].clone();
+        child.send(20);
+        assert_eq!(*items.0.value.borrow(), vec![1, 20, 3]);
+        assert_eq!(*child_updates.borrow(), 1);
+
+        // Editing the vector updates the children and resizes them
+        items.send(vec![5, 6]);
+        assert_eq!(*lengths.0.value.borrow(), 2);
+        assert_eq!(*children.0.value.borrow()[0].0.value.borrow(), 5);
+        assert_eq!(*child.0.value.borrow(), 6);
+        assert_eq!(*child_updates.borrow(), 2);
+
+        let removed = Signal(children.0.value.borrow()[1].0.clone());
+        items.send(vec![7]);
+        removed.send(60);
+        assert_eq!(*items.0.value.borrow(), vec![7]);
+
+        items.send(vec![7, 8, 9]);
+        assert_eq!(*lengths.0.value.borrow(), 3);
+        let added = children.0.value.borrow()[2].clone();
+        (added.send(90), children.0.value.borrow()[0].send(70));
+        assert_eq!(*items.0.value.borrow(), vec![70, 8, 90]);
+    }
+
+    #[test]
+    fn test_with_eq() {
+        #[derive(Clone)]
+        struct Reading {
+            value: f64,
+            timestamp: u64,
+        }
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let reading = Signal::new(Reading {
+            value: 1.0,
+            timestamp: 0,
+        })
+        .with_eq(|a, b| (a.value - b.value).abs() < 1e-9);
+        let deduped = reading.dedupe();
+        let log = seen.clone();
+        let _observer = deduped.map(move |r| log.borrow_mut().push(r.timestamp));
+        let sends = Rc::new(RefCell::new(0));
+        let counter = sends.clone();
+        let _sends = reading.map(move |_| *counter.borrow_mut() += 1);
+
+        reading.send(Reading {
+            value: 1.0 + 1e-12,
+            timestamp: 1,
+        });
+        assert!(
+            reading
+                .send_if_changed(Reading {
+                    value: 1.0,
+                    timestamp: 2,
+                })
+                .is_none()
+        );
+        reading.send_if_changed(Reading {
+            value: 2.0,
+            timestamp: 3,
+        });
+        assert_eq!(*seen.borrow(), vec![0, 3]);
+        assert_eq!(*sends.borrow(), 3);
+    }
+
+    #[test]
+    fn test_split_result() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let result: Signal<Result<i32, String>> = Signal::new(Err(String::from("empty")));
+        let (ok, err) = result.split_result();
+        let log = seen.clone();
+        let _ok = ok.map(move |v| log.borrow_mut().push(format!("ok: {:?}", v)));
+        let log = seen.clone();
+        let _err = err.map(move |e| log.borrow_mut().push(format!("err: {:?}", e)));
+        seen.borrow_mut().clear();
+
+        result.send(Ok(1));
+        result.send(Ok(2));
+        result.send(Err(String::from("failed")));
+        assert_eq!(*ok.0.value.borrow(), Some(2));
+        assert_eq!(*err.0.value.borrow(), Some(String::from("failed")));
+        assert_eq!(
+            *seen.borrow(),
+            vec!["ok: Some(1)", "ok: Some(2)", "err: Some(\"failed\")"]
+        );
+    }
+
+    #[test]
+    fn test_then() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let loading = Signal::new(0);
+        let data = Signal::new(-1);
+        let shown = loading.then(&data, |p| *p >= 100);
+        let log = seen.clone();
+        let _observer = shown.map(move |v| log.borrow_mut().push(*v));
+
+        loading.send(50);
+        data.send(7); // not tracked yet
+        loading.send(100);
+        assert_eq!(*shown.0.value.borrow(), 7);
+        loading.send(20); // no longer tracked
+        data.send(8);
+        assert_eq!(*seen.borrow(), vec![0, 50, 7, 8]);
+        assert!(loading.0.react_fns.borrow().snapshot().is_empty());
+
+        let ready = Signal::new(100).then(&data, |p| *p >= 100);
+        data.send(9);
+        assert_eq!(*ready.0.value.borrow(), 9);
+    }
+
+    #[test]
+    fn test_cache_last_guard() {
+        let a = Signal::new(0).cache_last_guard();
+        let b = a.map(|x| x + 1);
+        let _c = b.map(|x| x * 2);
+        a.send(1);
+        let buffer =
+            a.0.guard_scratch
+                .borrow_mut()
+                .as_ref()
+                .map(|v| (v.as_ptr(), v.capacity()));
+        assert!(buffer.is_some_and(|(_, capacity)| capacity >= 3));
+        a.send(2);
+        assert_eq!(
+            a.0.guard_scratch
+                .borrow_mut()
+                .as_ref()
+                .map(|v| (v.as_ptr(), v.capacity())),
+            buffer
+        );
+        assert_eq!(*b.0.value.borrow(), 3);
+
+        // A send forwarded while the buffer is in use gets its own buffer
+        let source = a.clone();
+        let _reentrant = b.map_forward(move |x| ((), (*x == 6).then(|| source.send(6))));
+        a.send(5);
+        assert_eq!(*b.0.value.borrow(), 7);
+    }
+
+    #[test]
+    fn test_computed_from() {
+        let a = Signal::new(1);
+        let b = Signal::new(String::from("x"));
+        let erased = Signal::new(10).erase();
+        let (a_read, b_read) = (a.clone(), b.clone());
+        let c_read = erased.downcast_ref::<i32>().unwrap().clone();
+        let summary = Signal::computed_from(&[&a, &b, &erased], move || {
+            format!(
+                "{}{}{}",
+                a_read.0.value.borrow(),
+                b_read.0.value.borrow(),
+                c_read.0.value.borrow()
+            )
+        });
+        assert_eq!(*summary.0.value.borrow(), "1x10");
+
+        a.send(2);
+        assert_eq!(*summary.0.value.borrow(), "2x10");
+        (
+            b.send(String::from("y")),
+            erased.downcast_ref::<i32>().unwrap().send(20),
+        );
+        assert_eq!(*summary.0.value.borrow(), "2y20");
+    }
+
+    #[test]
+    fn test_reaction_counts() {
+        let a = Signal::new(1);
+        let doubled = a.map(|x| x * 2);
+        let tripled = a.with(|x| x * 3);
+        a.send(2);
+        assert_eq!(a.reaction_count(), 1);
+        assert_eq!(doubled.reaction_count(), 1);
+        assert_eq!(tripled.reaction_count(), 1);
+
+        // A batch reacts once
+        (a.send(3), a.send(4));
+        assert_eq!(a.reaction_count(), 2);
+        assert_eq!(doubled.reaction_count(), 2);
+
+        // Both branches of a diamond settle before the join reacts, once
+        let joined = doubled.combine(&tripled);
+        a.send(5);
+        assert_eq!(joined.reaction_count(), 1);
+
+        let b = Signal::new(10);
+        let lifted = (&a, &b).lift();
+        let extended = a.extend([&b]);
+        (a.send(6), b.send(11));
+        assert_eq!(lifted.reaction_count(), 1);
+        assert_eq!(extended.reaction_count(), 1);
+        b.send(12);
+        assert_eq!(lifted.reaction_count(), 2);
+        assert_eq!(extended.reaction_count(), 2);
+
+        let promapped = b.promap(|x| x + 1, |x| x - 1);
+        promapped.send(20);
+        assert_eq!(promapped.reaction_count(), 1);
+        assert_eq!(b.reaction_count(), 3);
+        b.send(30);
+        assert_eq!(promapped.reaction_count(), 2);
+
+        let dependency = Signal::new(0);
+        let depending = b.depend(dependency.clone());
+        dependency.send(1);
+        assert_eq!(depending.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_validated() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let reading = Signal::new(1.0_f64);
+        let log = errors.clone();
+        reading.on_reaction_error(move |e| log.borrow_mut().push(e.message().to_string()));
+        let valid = reading.validated(|x| x.is_finite());
+        let doubled = valid.map(|x| x * 2.0);
+
+        reading.send(f64::NAN);
+        assert_eq!(*valid.0.value.borrow(), 1.0);
+        assert_eq!(*doubled.0.value.borrow(), 2.0);
+        assert_eq!(doubled.reaction_count(), 0);
+        reading.send(f64::INFINITY);
+        reading.send(3.0);
+        assert_eq!(*doubled.0.value.borrow(), 6.0);
+        assert_eq!(doubled.reaction_count(), 1);
+        assert_eq!(
+            *errors.borrow(),
+            vec!["rejected invalid value: NaN", "rejected invalid value: inf"]
+        );
+
+        // A handler installed on the validated signal afterwards sees the rejections too
+        let count = Signal::new(1);
+        let positive = count.validated(|x| *x > 0);
+        let log = errors.clone();
+        positive.on_reaction_error(move |e| log.borrow_mut().push(e.message().to_string()));
+        count.send(-1);
+        assert_eq!(
+            errors.borrow().last().unwrap(),
+            "rejected invalid value: -1"
+        );
+    }
+
+    #[test]
+    fn test_combine_mixed_owned_and_borrowed() {
+        let (a, b) = (Signal::new(1), Signal::new(2));
+        let (c, d) = (Signal::new(3), Signal::new(4));
+        let owned = a.combine(b.clone());
+        let nested = c.combine(&&d);
+        (a.send(10), b.send(20));
+        (c.send(30), d.send(40));
+        assert_eq!(*owned.0.value.borrow(), (10, 20));
+        assert_eq!(*nested.0.value.borrow(), (30, 40));
+    }
+
+    #[test]
+    fn test_extend_mixed_owned_and_borrowed() {
+        let a = Signal::new(1);
+        let owned = [Signal::new(2), Signal::new(3)];
+        let from_iter = a.extend(owned.iter());
+        let b = Signal::new(4);
+        let borrowed = [Signal::new(5), Signal::new(6)];
+        let refs = [&borrowed[0], &borrowed[1]];
+        let from_refs = b.extend(refs.iter());
+        (a.send(10), owned[0].send(20), owned[1].send(30));
+        (b.send(40), borrowed[0].send(50), borrowed[1].send(60));
+        assert_eq!(*from_iter.0.value.borrow(), vec![10, 20, 30]);
+        assert_eq!(*from_refs.0.value.borrow(), vec![40, 50, 60]);
+    }
+
+    #[test]
+    fn test_lift_mixed_owned_and_borrowed() {
+        let (a, b) = (Signal::new(1), Signal::new(2));
+        let pair = (a.clone(), &b).lift();
+        let (c, d) = (Signal::new(3), Signal::new(4));
+        let refs = [&c, &d];
+        let list = refs.iter().collect::<Vec<_>>().lift();
+        let (e, f) = (Signal::new(5), Signal::new(6));
+        let array = [&&e, &&f].lift();
+        (a.send(10), b.send(20));
+        (c.send(30), d.send(40));
+        (e.send(50), f.send(60));
+        assert_eq!(*pair.0.value.borrow(), (10, 20));
+        assert_eq!(*list.0.value.borrow(), vec![30, 40]);
+        assert_eq!(*array.0.value.borrow(), [50, 60]);
+    }
+
+    #[test]
+    fn test_on_first() {
+        let config = Signal::new(0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        config.on_first(move |x| seen_clone.borrow_mut().push(*x));
+        assert!(seen.borrow().is_empty());
+        config.send(1);
+        config.send(2);
+        assert_eq!(*seen.borrow(), vec![1]);
+        assert_eq!(config.0.react_fns.borrow().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_on_first_through_propagation() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x * 10);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        b.on_first(move |x| seen_clone.borrow_mut().push(*x));
+        a.send(2);
+        a.send(3);
+        assert_eq!(*seen.borrow(), vec![20]);
+    }
+
+    #[test]
+    fn test_diff_keyed_reorder() {
+        let items = Signal::new(vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+        let changes = items.diff_keyed(|(id, _)| *id);
+        assert_eq!(changes.0.value.borrow().len(), 4);
+        items.send(vec![(4, 'd'), (1, 'a'), (2, 'b'), (3, 'c')]);
+        assert_eq!(
+            *changes.0.value.borrow(),
+            vec![ListChange::Move {
+                key: 4,
+                from: 3,
+                to: 0
+            }]
+        );
+        items.send(vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+        assert_eq!(
+            *changes.0.value.borrow(),
+            vec![ListChange::Move {
+                key: 4,
+                from: 0,
+                to: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_keyed_insert_remove_update() {
+        let items = Signal::new(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+        let changes = items.diff_keyed(|(id, _)| *id);
+        items.send(vec![(3, 'c'), (1, 'z'), (5, 'e')]);
+        assert_eq!(
+            *changes.0.value.borrow(),
+            vec![
+                ListChange::Remove { key: 2, index: 1 },
+                ListChange::Move {
+                    key: 3,
+                    from: 2,
+                    to: 0
+                },
+                ListChange::Update {
+                    key: 1,
+                    index: 1,
+                    value: (1, 'z')
+                },
+                ListChange::Insert {
+                    key: 5,
+                    index: 2,
+                    value: (5, 'e')
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_scope() {
+        let a = Signal::new(1);
+        let b = Signal::new(2);
+        let pair = a.combine(&b);
+        let sum = pair.map(|(x, y)| x + y);
+        let mut scope = BatchScope::new();
+        scope.send(&a, 10).send_with(&b, |v| *v = 20);
+        assert_eq!(sum.reaction_count(), 0);
+        let c = Signal::new(3);
+        fn fill<'a>(scope: &mut BatchScope<'a>, c: &Signal<'a, i32>) {
+            scope.send(c, 30);
+        }
+        fill(&mut scope, &c);
+        scope.flush();
+        assert_eq!(*sum.0.value.borrow(), 30);
+        assert_eq!(sum.reaction_count(), 1);
+        assert_eq!(*c.0.value.borrow(), 30);
+    }
+
+    #[test]
+    fn test_prune_dead() {
+        let source = Signal::new(1);
+        let kept = source.map(|x| x + 1);
+        for i in 0..10 {
+            let _dropped = source.map(move |x| x + i);
+        }
+        assert_eq!(source.0.react_fns.borrow().snapshot().len(), 11);
+        source.prune_dead();
+        assert_eq!(source.0.react_fns.borrow().snapshot().len(), 1);
+        assert_eq!(source.0.successors.borrow().len(), 1);
+        source.send(2);
+        assert_eq!(*kept.0.value.borrow(), 3);
+    }
+
+    #[test]
+    fn test_clear_observers() {
+        let source = Signal::new(1);
+        let doubled = source.map(|x| x * 2);
+        let tripled = source.map(|x| x * 3);
+        source.send(2);
+        source.clear_observers();
+        source.send(3);
+        assert_eq!(*doubled.0.value.borrow(), 4);
+        assert_eq!(*tripled.0.value.borrow(), 6);
+        assert_eq!(doubled.reaction_count(), 1);
+        assert_eq!(*source.0.value.borrow(), 3);
+    }
+
+    #[test]
+    fn test_scan_reset() {
+        let source = Signal::new(0);
+        let reset = Signal::new(());
+        let sum = source.scan_reset(0, |acc, x| acc + x, &reset);
+        assert_eq!(*sum.0.value.borrow(), 0);
+        source.send(1);
+        source.send(2);
+        assert_eq!(*sum.0.value.borrow(), 3);
+        reset.send(());
+        assert_eq!(*sum.0.value.borrow(), 0);
+        source.send(4);
+        assert_eq!(*sum.0.value.borrow(), 4);
+        (reset.send(()), reset.send(()));
+        assert_eq!(*sum.0.value.borrow(), 0);
+        assert_eq!(sum.reaction_count(), 5);
+    }
+
+    #[test]
+    fn test_bind_to_owner() {
+        let value = Signal::new(0);
+        let owner = Rc::new(());
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        value.bind_to_owner(&owner, move |x| seen_clone.borrow_mut().push(*x));
+        value.send(1);
+        drop(owner);
+        value.send(2);
+        value.send(3);
+        assert_eq!(*seen.borrow_mut(), vec![1]);
+        assert_eq!(value.0.react_fns.borrow().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_combine_map() {
+        let width = Signal::new(2);
+        let height = Signal::new(3);
+        let area = width.combine_map(&height, |w, h| w * h);
+        assert_eq!(*area.0.value.borrow(), 6);
+        width.send(4);
+        assert_eq!(*area.0.value.borrow(), 12);
+        (width.send(5), height.send(5));
+        assert_eq!(*area.0.value.borrow(), 25);
+        assert_eq!(area.reaction_count(), 2);
+        assert_eq!(width.0.successors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_send_from_reaction_is_deferred() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let b_clone = b.clone();
+        let _forward = a.observe_weak(move |x| {
+            b_clone.send(x * 10).defer();
+        });
+        let log_b = log.clone();
+        let _observe_b = b.observe_weak(move |x| log_b.borrow_mut().push(format!("b {}", x)));
+        let log_a = log.clone();
+        let _observe_a = a.observe_weak(move |x| log_a.borrow_mut().push(format!("a {}", x)));
+        a.send(1);
+        a.send(2);
+        assert_eq!(*log.borrow_mut(), vec!["a 1", "b 10", "a 2", "b 20"]);
+        assert_eq!(b.reaction_count(), 2);
+    }
+
+    #[test]
+    fn test_send_from_reaction_settles_inline() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let b_clone = b.clone();
+        let _forward = a.observe_weak(move |x| {
+            b_clone.send(x * 10);
+        });
+        let log_b = log.clone();
+        let _observe_b = b.observe_weak(move |x| log_b.borrow_mut().push(format!("b {}", x)));
+        let log_a = log.clone();
+        let _observe_a = a.observe_weak(move |x| log_a.borrow_mut().push(format!("a {}", x)));
+        a.send(1);
+        a.send(2);
+        assert_eq!(*log.borrow_mut(), vec!["b 10", "a 1", "b 20", "a 2"]);
+    }
+
+    #[test]
+    fn test_send_from_reaction_to_local_signal() {
+        // A signal local to a reaction, whose reactions borrow data local to it, has settled
+        // before the reaction returns
+        let a = Signal::new(0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        // The observer outlives the reaction, so it would still run if the send were deferred
+        let owner = Rc::new(());
+        let _forward = a.observe_weak(move |x| {
+            let words = vec![String::from("word"); *x];
+            let local = Signal::new(0);
+            let words_ref = &words;
+            let seen_clone = seen_clone.clone();
+            local.bind_to_owner(&owner, move |v| {
+                let v: Vec<_> = words_ref.iter().take(*v).collect();
+                seen_clone.borrow_mut().push(v.len());
+            });
+            local.send(*x);
+        });
+        a.send(2);
+        a.send(3);
+        assert_eq!(*seen.borrow(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_sends_from_reaction_settle_in_order() {
+        let source = Signal::new(0);
+        let first = Signal::new(0);
+        let second = Signal::new(0);
+        let sum = first.combine_map(&second, |x, y| x + y);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (first_clone, second_clone) = (first.clone(), second.clone());
+        let _forward = source.observe_weak(move |x| {
+            first_clone.send(*x).defer();
+            second_clone.send(*x * 2).defer();
+        });
+        let log_first = log.clone();
+        let _observe_first = first.observe_weak(move |x| log_first.borrow_mut().push(*x));
+        let log_second = log.clone();
+        let _observe_second = second.observe_weak(move |x| log_second.borrow_mut().push(*x));
+        let (chained, first_clone) = (Signal::new(0), first.clone());
+        let chained_clone = chained.clone();
+        let _chain = second.observe_weak(move |x| {
+            chained_clone
+                .send(*x + *first_clone.0.value.borrow())
+                .defer();
+        });
+        source.send(1);
+        assert_eq!(*log.borrow_mut(), vec![1, 2]);
+        assert_eq!(*sum.0.value.borrow(), 3);
+        assert_eq!(*chained.0.value.borrow(), 3);
+        // Both sends of the reaction are settled before `sum` reacts, so it fires once
+        assert_eq!(sum.reaction_count(), 1);
+        assert_eq!(chained.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_get_and_peek() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x * 2);
+        let successors = a.0.successors.borrow().len();
+        a.send(5);
+        assert_eq!(a.get(), 5);
+        assert_eq!(b.peek(|x| *x + 1), 11);
+        assert_eq!(a.0.successors.borrow().len(), successors);
+        assert_eq!(a.0.react_fns.borrow().snapshot().len(), 1);
+        let len = a.peek(|x| x.to_string().len());
+        a.send(len as i32);
+        assert_eq!(b.get(), 2);
+    }
+
+    #[test]
+    fn test_filter() {
+        let a = Signal::new(0);
+        let even = a.filter(|x| x % 2 == 0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let log_clone = log.clone();
+        let _observer = even.map(move |x| log_clone.borrow_mut().push(*x));
+        for x in 1..=5 {
+            a.send(x);
+        }
+        assert_eq!(*log.borrow_mut(), vec![0, 2, 4]);
+        assert_eq!(*even.0.value.borrow(), 4);
+        assert_eq!(even.reaction_count(), 2);
+    }
+
+    #[test]
+    fn test_distinct() {
+        let a = Signal::new(1);
+        let b = Signal::new(10);
+        let distinct = a.distinct();
+        let sum = distinct.combine_map(&b, |x, y| x + y);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let log_clone = log.clone();
+        let _observer = sum.map(move |x| log_clone.borrow_mut().push(*x));
+        a.send(1);
+        (a.send(1), b.send(20));
+        a.send(2);
+        a.send(2);
+        assert_eq!(*log.borrow_mut(), vec![11, 21, 22]);
+        assert_eq!(distinct.reaction_count(), 1);
+        assert_eq!(sum.get_dirty(), 0);
+    }
+
+    #[test]
+    fn test_distinct_by() {
+        let a = Signal::new(1.0_f64);
+        let rounded = a.distinct_by(|x, y| x.round() == y.round());
+        a.send(1.2);
+        assert_eq!(*rounded.0.value.borrow(), 1.0);
+        a.send(1.6);
+        assert_eq!(*rounded.0.value.borrow(), 1.6);
+        assert_eq!(rounded.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_pairwise() {
+        let a = Signal::new(1);
+        let pairs = a.pairwise();
+        assert_eq!(*pairs.0.value.borrow(), (1, 1));
+        a.send(2);
+        assert_eq!(*pairs.0.value.borrow(), (1, 2));
+        (a.send(3), a.send(4));
+        assert_eq!(*pairs.0.value.borrow(), (2, 4));
+        a.send(5);
+        assert_eq!(*pairs.0.value.borrow(), (4, 5));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let a = Signal::new(1);
+        let b = Signal::new(10);
+        let current = Signal::new(a.clone());
+        let flat = current.flatten();
+        assert_eq!(*flat.0.value.borrow(), 1);
+        a.send(2);
+        assert_eq!(*flat.0.value.borrow(), 2);
+        current.send(b.clone());
+        assert_eq!(*flat.0.value.borrow(), 10);
+        a.send(3);
+        assert_eq!(*flat.0.value.borrow(), 10);
+        assert_eq!(a.0.successors.borrow().len(), 0);
+        assert_eq!(a.0.react_fns.borrow().snapshot().len(), 0);
+        b.send(11);
+        assert_eq!(*flat.0.value.borrow(), 11);
+        assert_eq!(flat.reaction_count(), 3);
+    }
+
+    #[test]
+    fn test_switch_map() {
+        let selected = Signal::new(1);
+        let shown = selected.switch_map(|x| Signal::new(x * 100));
+        assert_eq!(*shown.0.value.borrow(), 100);
+        let inner = Signal::new(7);
+        let inner_clone = inner.clone();
+        let chosen = selected.switch_map(move |x| {
+            if *x == 2 {
+                inner_clone.clone()
+            } else {
+                Signal::new(0)
+            }
+        });
+        selected.send(2);
+        assert_eq!(*shown.0.value.borrow(), 200);
+        assert_eq!(*chosen.0.value.borrow(), 7);
+        inner.send(8);
+        assert_eq!(*chosen.0.value.borrow(), 8);
+        selected.send(3);
+        inner.send(9);
+        assert_eq!(*chosen.0.value.borrow(), 0);
+    }
+
+    #[test]
+    fn test_combine_with() {
+        let a = Signal::new(1);
+        let b = Signal::new(2);
+        let sum = a.combine_with(&b, |x, y| x + y);
+        let product = a.combine_with(b.clone(), |x, y| x * y);
+        assert_eq!(*sum.0.value.borrow(), 3);
+        (a.send(3), b.send(4));
+        assert_eq!(*sum.0.value.borrow(), 7);
+        assert_eq!(*product.0.value.borrow(), 12);
+        assert_eq!(sum.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_extend_tracked() {
+        let a = Signal::new(1);
+        let others = [Signal::new(2), Signal::new(3)];
+        let list = a.extend_tracked(others.iter());
+        assert_eq!(*list.0.value.borrow(), (0, vec![1, 2, 3]));
+        others[1].send(30);
+        assert_eq!(*list.0.value.borrow(), (2, vec![1, 2, 30]));
+        a.send(10);
+        assert_eq!(*list.0.value.borrow(), (0, vec![10, 2, 30]));
+        assert_eq!(*a.0.value.borrow(), 10);
+        (others[0].send(20), others[1].send(300));
+        assert_eq!(list.0.value.borrow().1, vec![10, 20, 300]);
+        assert_eq!(list.reaction_count(), 3);
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let a = Signal::new(0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let subscription = a.subscribe(move |x| seen_clone.borrow_mut().push(*x));
+        assert!(seen.borrow_mut().is_empty());
+        a.send(1);
+        assert_eq!(*seen.borrow_mut(), vec![1]);
+        drop(subscription);
+        a.send(2);
+        assert_eq!(*seen.borrow_mut(), vec![1]);
+        assert_eq!(a.0.react_fns.borrow().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_subscribe_now() {
+        let a = Signal::new(5);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let subscription = a.subscribe_now(move |x| seen_clone.borrow_mut().push(*x));
+        assert_eq!(*seen.borrow_mut(), vec![5]);
+        a.send(6);
+        drop(subscription);
+        a.send(7);
+        assert_eq!(*seen.borrow_mut(), vec![5, 6]);
+    }
+
+    #[test]
+    fn test_diamond_reacts_once_per_send() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x + 1);
+        let c = a.map(|x| x * 10);
+        let d = b.combine_map(&c, |b, c| b + c);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = d.observe_weak(move |x| seen_clone.borrow_mut().push(*x));
+        a.send(2);
+        a.send(3);
+        // Each send settles `d` once, after both `b` and `c` have updated
+        assert_eq!(*seen.borrow(), vec![23, 34]);
+        assert_eq!(d.reaction_count(), 2);
+        // The bottom of the diamond is collected and marked dirty once
+        let mut guards = Vec::new();
+        a.collect_guards(&mut guards);
+        assert_eq!(guards.len(), 4);
+        assert_eq!(d.get_dirty(), 1);
+    }
+
+    #[test]
+    fn test_lift_clones_non_trivial_values() {
+        // `combine`, `extend` and `lift_from_array` start from the values of their sources,
+        // which keep their own values through later sends
+        let a = Signal::new(vec![String::from("a")]);
+        let b = Signal::new(vec![String::from("b"), String::from("c")]);
+        let pair = a.combine(&b);
+        let list = a.extend([&b]);
+        let array = Signal::<Vec<String>>::lift_from_array([&a, &b]);
+        assert_eq!(
+            pair.get(),
+            (
+                vec!["a".to_string()],
+                vec!["b".to_string(), "c".to_string()]
+            )
+        );
+        assert_eq!(list.get(), vec![vec!["a"], vec!["b", "c"]]);
+        assert_eq!(array.get(), [vec!["a"], vec!["b", "c"]]);
+
+        a.send(vec![String::from("d")]);
+        assert_eq!(*a.0.value.borrow(), vec!["d"]);
+        assert_eq!(*b.0.value.borrow(), vec!["b", "c"]);
+        assert_eq!(
+            pair.get(),
+            (
+                vec!["d".to_string()],
+                vec!["b".to_string(), "c".to_string()]
+            )
+        );
+        assert_eq!(list.get(), vec![vec!["d"], vec!["b", "c"]]);
+        assert_eq!(array.get(), [vec!["d"], vec!["b", "c"]]);
+        a.send(vec![String::from("e")]);
+        assert_eq!(*a.0.value.borrow(), vec!["e"]);
+        assert_eq!(pair.get().0, vec!["e"]);
+    }
+
+    #[test]
+    fn test_cycle_settles_after_one_pass() {
+        let a = Signal::new(1);
+        let b = Signal::new(2);
+        // Each signal mirrors the other, which used to recurse until the stack overflowed
+        a.mirror_into(&b);
+        b.mirror_into(&a);
+        a.send(5);
+        assert_eq!(*a.0.value.borrow(), 5);
+        assert_eq!(*b.0.value.borrow(), 5);
+        assert_eq!(a.reaction_count(), 1);
+        assert_eq!(b.reaction_count(), 1);
+        b.send(7);
+        assert_eq!(*a.0.value.borrow(), 7);
+        assert_eq!(b.reaction_count(), 2);
+    }
+
+    #[test]
+    fn test_panicking_reaction_is_isolated() {
+        let a = Signal::new(0);
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let errors_clone = errors.clone();
+        a.on_reaction_error(move |e| errors_clone.borrow_mut().push(e.message().to_string()));
+        let _panicking = a.observe_weak(|x| {
+            if *x == 1 {
+                panic!("boom");
+            }
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _sibling = a.observe_weak(move |x| seen_clone.borrow_mut().push(*x));
+        let doubled = a.map(|x| x * 2);
+        a.send(1);
+        a.send(2);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+        assert_eq!(*doubled.0.value.borrow(), 4);
+        assert_eq!(*errors.borrow(), vec!["reaction panicked: boom"]);
+        assert_eq!(a.get_dirty(), 0);
+    }
+
+    #[test]
+    fn test_panicking_reaction_without_handler_surfaces() {
+        let a = Signal::new(0);
+        let _panicking = a.observe_weak(|x| {
+            if *x == 1 {
+                panic!("boom");
+            }
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _sibling = a.observe_weak(move |x| seen_clone.borrow_mut().push(*x));
+        let doubled = a.map(|x| x * 2);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(a.send(1))));
+        assert_eq!(panic_message(&*result.unwrap_err()), "boom");
+        // The panic surfaced only once the graph had settled
+        assert_eq!(*seen.borrow(), vec![1]);
+        assert_eq!(*doubled.0.value.borrow(), 2);
+        assert_eq!(a.get_dirty(), 0);
+        a.send(2);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_panicking_eager_reaction_is_isolated() {
+        let a = Signal::new(0);
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let errors_clone = errors.clone();
+        a.on_reaction_error(move |e| errors_clone.borrow_mut().push(e.message().to_string()));
+        let eager = a.map_eager(|x| {
+            if *x == 1 {
+                panic!("eager boom");
+            }
+            *x
+        });
+        let doubled = eager.map(|x| x * 2);
+        a.send(1);
+        a.send(2);
+        assert_eq!(*errors.borrow(), vec!["reaction panicked: eager boom"]);
+        assert_eq!((*eager.0.value.borrow(), *doubled.0.value.borrow()), (2, 4));
+        assert_eq!((a.get_dirty(), eager.get_dirty()), (0, 0));
+    }
+
+    #[test]
+    fn test_derived_values_correct_before_send() {
+        struct Celsius(f64);
+
+        let fahrenheit = Signal::new(212.0);
+        let celsius = fahrenheit.promap(
+            |f| Celsius((f - 32.0) * 5.0 / 9.0),
+            |c| c.0 * 9.0 / 5.0 + 32.0,
+        );
+        assert_eq!(celsius.0.value.borrow().0, 100.0);
+        let source = fahrenheit.contramap_with(Celsius(100.0), |c| c.0 * 9.0 / 5.0 + 32.0);
+        assert_eq!(source.0.value.borrow().0, 100.0);
+        assert_eq!(*fahrenheit.0.value.borrow(), 212.0);
+    }
+
+    #[test]
+    fn test_try_map_filter_ok() {
+        let input = Signal::new(String::from("1"));
+        let parsed = input.try_map(|s| s.parse::<i32>());
+        let valid = parsed.filter_ok();
+        let errors = Rc::new(RefCell::new(0));
+        let errors_clone = errors.clone();
+        input.on_reaction_error(move |_| *errors_clone.borrow_mut() += 1);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = valid.observe_weak(move |v| seen_clone.borrow_mut().push(*v));
+        input.send(String::from("x"));
+        assert!(parsed.0.value.borrow().is_err());
+        assert_eq!(*valid.0.value.borrow(), Some(1));
+        input.send(String::from("2"));
+        assert_eq!(*seen.borrow(), vec![Some(2)]);
+        assert_eq!(*errors.borrow(), 1);
+    }
+
+    #[test]
+    fn test_to_sync_forwards_across_threads() {
+        let a = Signal::new(1);
+        let doubled = a.map(|x| x * 2);
+        let shared = doubled.to_sync();
+        assert_eq!(shared.get(), 2);
+        a.send(5);
+        let reader = shared.clone();
+        let value = thread::spawn(move || reader.get()).join().unwrap();
+        assert_eq!(value, 10);
+        drop(shared);
+        // Dropping the thread-safe signal stops the forwarding reaction, which removes itself
+        assert_eq!(doubled.0.react_fns.borrow().snapshot().len(), 1);
+        a.send(6);
+        assert_eq!(*doubled.0.value.borrow(), 12);
+        assert_eq!(doubled.0.react_fns.borrow().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_arithmetic_operators() {
+        let a = Signal::new(6);
+        let b = Signal::new(3);
+        let sum = &a + &b;
+        let difference = &a - &b;
+        let product = &a * &b;
+        let quotient = &a / &b;
+        assert_eq!(*sum.0.value.borrow(), 9);
+        (a.send(12), b.send(4));
+        assert_eq!(*sum.0.value.borrow(), 16);
+        assert_eq!(*difference.0.value.borrow(), 8);
+        assert_eq!(*product.0.value.borrow(), 48);
+        assert_eq!(*quotient.0.value.borrow(), 3);
+    }
+
+    #[test]
+    fn test_chained_arithmetic_operators() {
+        let a = Signal::new(1);
+        let b = Signal::new(2);
+        let c = Signal::new(3);
+        // The intermediate sum is dropped at the end of the statement
+        let result = &(&a + &b) * &c;
+        assert_eq!(*result.0.value.borrow(), 9);
+        a.send(4);
+        assert_eq!(*result.0.value.borrow(), 18);
+        c.send(10);
+        assert_eq!(*result.0.value.borrow(), 60);
+    }
+
+    #[test]
+    fn test_debug_display() {
+        let a = Signal::new(1).with_label("a");
+        let b = a.map(|x| x + 1);
+        assert_eq!(a.to_string(), "1");
+        assert_eq!(
+            format!("{:?}", a),
+            "Signal { label: \"a\", value: 1, successors: Some(1), predecessors: Some(0), dirty: 0 }"
+        );
+        assert_eq!(
+            format!("{:?}", b),
+            "Signal { value: 2, successors: Some(0), predecessors: Some(0), dirty: 0 }"
+        );
+        let _guard = a.send(5);
+        assert_eq!(a.to_string(), "5");
+        let value = a.0.value.borrow_mut();
+        assert!(format!("{:?}", a).contains("value: <borrowed>, "));
+        assert!(format!("{:?}", a).contains("dirty: 1"));
+        drop(value);
+    }
+
+    #[test]
+    fn test_lift_three_and_four_tuples() {
+        let a = Signal::new(1);
+        let b = Signal::new("b".to_string());
+        let c = Signal::new(false);
+        let d = Signal::new(0.5);
+
+        let abc = (&a, &b, &c).lift();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = abc.map(move |(x, y, z)| {
+            seen_clone.borrow_mut().push((*x, y.clone(), *z));
+        });
+        (a.send(2), b.send("c".to_string()));
+        c.send(true);
+        assert_eq!(
+            *seen.borrow(),
+            vec![
+                (1, "b".to_string(), false),
+                (2, "c".to_string(), false),
+                (2, "c".to_string(), true),
+            ]
+        );
+
+        // Sources that are never sent to keep their initial value in the lifted signal
+        let (e, f, g) = (
+            Signal::new(7),
+            Signal::new("f".to_string()),
+            Signal::new(true),
+        );
+        let efgd = (&e, &f, &g, &d).lift();
+        assert_eq!(efgd.get(), (7, "f".to_string(), true, 0.5));
+        (e.send(3), d.send(1.5));
+        assert_eq!(efgd.get(), (3, "f".to_string(), true, 1.5));
+        assert_eq!(
+            (e.get(), f.get(), g.get(), d.get()),
+            (3, "f".to_string(), true, 1.5)
+        );
+    }
+
+    #[test]
+    fn test_combine_latest_waits_for_both() {
+        let a = Signal::new(0);
+        let b = Signal::new("".to_string());
+        let latest = a.combine_latest(&b);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = latest.map(move |pair| seen_clone.borrow_mut().push(pair.clone()));
+        seen.borrow_mut().clear();
+
+        a.send(1);
+        a.send(2);
+        assert!(seen.borrow().is_empty());
+
+        b.send("x".to_string());
+        a.send(3);
+        assert_eq!(
+            *seen.borrow(),
+            vec![(2, "x".to_string()), (3, "x".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_sample_on_trigger() {
+        let a = Signal::new(0);
+        let b = Signal::new(false);
+        let sampled = a.sample(b.clone());
+        let observed = sampled.map(|x| *x);
+        a.send(1);
+        a.send(2);
+        a.send(3);
+        assert_eq!(sampled.reaction_count(), 0);
+        assert_eq!(observed.get(), 0);
+
+        b.send(true);
+        assert_eq!(sampled.reaction_count(), 1);
+        assert_eq!(observed.get(), 3);
+    }
+
+    #[test]
+    fn test_disconnect_derived() {
+        let source = Signal::new(1);
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+        let derived = source.map(move |x| {
+            calls_clone.set(calls_clone.get() + 1);
+            x * 2
         });
+        let other = source.map(|x| x + 1);
+        source.send(2);
+        assert_eq!(calls.get(), 2);
+
+        assert!(source.disconnect(&derived));
+        assert!(!source.disconnect(&derived));
+        source.send(3);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(derived.get(), 4);
+        assert_eq!(other.get(), 4);
+        assert_eq!(source.0.successors.borrow().len(), 1);
     }
 
-    /// Lift an array of liftable items into a signal of an array
-    ///
-    /// This creates a new signal that depends on the provided liftable items.
-    /// Changes to any of the source signals will propagate to the new signal.
-    ///
-    /// # Example
-    /// ```rust
-    /// let a = Signal::new(1);
-    /// let b = Signal::new(2);
-    /// let c = Signal::new(3);
-    /// let abc = Signal::lift_from_array([a, b, c]);
-    /// abc.with(|values| println!("abc changed: {:?}", values));
-    /// (a.send(10), b.send(20), c.send(30)); // prints "abc changed: [10, 20, 30]"
-    /// ```
-    pub fn lift_from_array<S, const N: usize>(items: [S; N]) -> Signal<'a, [S::Inner; N]>
-    where
-        S: Liftable<'a>,
-        S::Inner: 'a,
-    {
-        let signals: [Signal<'a, S::Inner>; N] = std::array::from_fn(|i| items[i].as_ref().clone());
+    #[test]
+    fn test_disconnect_filter_and_zip() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let even = a.filter(|x| x % 2 == 0);
+        let pairs = a.zip(&b);
+        (a.send(2), b.send(3));
+        assert_eq!(even.get(), 2);
+        assert_eq!(pairs.get(), (2, 3));
 
-        // Take values using helper - no cloning!
-        let initial: [S::Inner; N] = std::array::from_fn(|i| Self::take_value(&signals[i].0.value));
-        let new_signal: Signal<'a, [S::Inner; N]> = Signal::new(initial);
+        assert!(a.disconnect(&even));
+        assert!(a.disconnect(&pairs));
+        assert!(b.disconnect(&pairs));
+        assert!(!a.disconnect(&even));
+        (a.send(4), b.send(5));
+        assert_eq!(even.get(), 2);
+        assert_eq!(pairs.get(), (2, 3));
+        assert_eq!(even.reaction_count(), 1);
+        assert_eq!(pairs.reaction_count(), 1);
+    }
 
-        // Restore original values by swapping back
-        for (index, signal) in signals.iter().enumerate() {
-            std::mem::swap(
-                &mut *signal.0.value.borrow_mut(),
-                &mut new_signal.0.value.borrow_mut()[index],
-            );
+    #[test]
+    fn test_dropped_derived_reactions_are_pruned_on_send() {
+        let source = Signal::new(0);
+        let _kept = source.map(|x| x + 1);
+        for i in 0..1000 {
+            let _short_lived = source.map(move |x| x * i);
+            let _eager = source.map_eager(move |x| x + i);
+            let _computed = Signal::computed_from(&[&source], move || i);
+            source.send(i);
+            assert!(source.0.react_fns.borrow().entries.len() <= 3);
+            assert!(source.0.eager_react_fns.borrow().entries.len() <= 1);
         }
+        source.send(0);
+        assert_eq!(source.0.react_fns.borrow().entries.len(), 1);
+        assert_eq!(source.0.eager_react_fns.borrow().entries.len(), 0);
+        assert_eq!(source.0.successors.borrow().len(), 1);
+    }
 
-        let result_new_signal = new_signal.clone();
+    #[test]
+    fn test_dropped_forwarding_reactions_are_pruned() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let _kept = a.filter(|x| x % 2 == 0);
+        let filtered = a.filter(|x| x % 2 == 1);
+        let zipped = a.zip(&b);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 3);
+        assert_eq!(b.0.react_fns.borrow().entries.len(), 1);
+        drop((filtered, zipped));
+        a.prune_dead();
+        b.prune_dead();
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 1);
+        assert_eq!(b.0.react_fns.borrow().entries.len(), 0);
+    }
 
-        for (index, signal) in signals.iter().enumerate() {
-            let new_signal_weak = Rc::downgrade(&new_signal.0);
-            let source_for_closure = Rc::downgrade(&signal.0);
+    #[test]
+    fn test_map_memo_runs_once_per_change() {
+        let a = Signal::new(1);
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+        let doubled = a.map_memo(move |x| {
+            calls_clone.set(calls_clone.get() + 1);
+            x * 2
+        });
+        assert_eq!(calls.get(), 1);
 
-            let react_fn = Box::new(move || {
-                if let Some(new_sig) = new_signal_weak.upgrade() {
-                    if !*new_sig.explicitly_modified.borrow() {
-                        if let Some(source) = source_for_closure.upgrade() {
-                            // Swap instead of cloning (during reaction only)
-                            std::mem::swap(
-                                &mut new_sig.value.borrow_mut()[index],
-                                &mut *source.value.borrow_mut(),
-                            );
-                        }
-                    }
-                }
-            });
+        a.send(1);
+        a.send(2);
+        a.send(2);
+        (a.send(3), a.send(2));
+        a.send(2);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(doubled.get(), 4);
 
-            signal.0.react_fns.borrow_mut().push(react_fn);
-            signal
-                .0
-                .successors
-                .borrow_mut()
-                .push(WeakSignalRef::new(&new_signal));
+        a.send(5);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(doubled.get(), 10);
+    }
+
+    #[test]
+    fn test_asymmetric_diamond_is_glitch_free() {
+        // A long branch a -> b -> c -> long and a short one a -> short join at `pair`
+        let a = Signal::new(1);
+        let b = a.map(|x| x + 1);
+        let c = b.map(|x| x * 2);
+        let long = c.map(|x| x - 2);
+        let short = a.map(|x| x * 2);
+        let pair = long.combine_map(&short, |l, s| (*l, *s));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = pair.map(move |(c, d)| seen_clone.borrow_mut().push((*c, *d)));
+
+        for value in 2..6 {
+            a.send(value);
         }
+        (a.send(10), a.send(11));
 
-        result_new_signal
+        // Both branches compute 2 * a, an observer never sees one updated without the other
+        assert!(seen.borrow().iter().all(|(l, s)| l == s));
+        assert_eq!(seen.borrow().len(), 6);
+        assert_eq!(pair.reaction_count(), 5);
     }
-}
 
-impl<'a, T: 'a> SignalExt<'a> for Signal<'a, T> {
-    fn react(&self) {
-        self.0.react_fns.borrow().iter().for_each(|react_fn| {
-            react_fn();
+    #[test]
+    fn test_batch_reacts_once() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let sum = a.combine_map(&b, |x, y| x + y);
+
+        // The tuple idiom batches the sends of a single expression
+        (a.send(1), b.send(2));
+        assert_eq!(sum.reaction_count(), 1);
+
+        // Separate statements react once per send
+        a.send(3);
+        b.send(4);
+        assert_eq!(sum.reaction_count(), 3);
+
+        // `batch` batches separate statements, including those of the functions given the scope
+        fn send_b<'a>(scope: &mut BatchScope<'a>, b: &Signal<'a, i32>) {
+            scope.send(b, 6);
+        }
+        let result = batch(|scope| {
+            scope.send(&a, 5);
+            send_b(scope, &b);
+            scope.send(&a, 7);
+            assert_eq!(sum.reaction_count(), 3);
+            "done"
         });
+        assert_eq!(result, "done");
+        assert_eq!(sum.reaction_count(), 4);
+        assert_eq!(sum.get(), 13);
+
+        // Sends after the batch react right away again
+        a.send(8);
+        assert_eq!(sum.reaction_count(), 5);
     }
-    fn guard(&self) -> SignalGuard<'a> {
-        let mut result = vec![];
-        self.collect_guards(&mut result);
-        SignalGuard(result)
+
+    #[test]
+    fn test_effect_stops_after_drop() {
+        let a = Signal::new(0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let effect = a.effect(move |x| seen_clone.borrow_mut().push(*x));
+        a.send(1);
+        a.send(2);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+        drop(effect);
+        a.send(3);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+        assert_eq!(a.0.react_fns.borrow().snapshot().len(), 0);
     }
-    fn clone_box(&self) -> Box<dyn SignalExt<'a> + 'a> {
-        Box::new(Signal(Rc::clone(&self.0)))
+
+    #[test]
+    fn test_effect_cleanup() {
+        let a = Signal::new(0);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let log = events.clone();
+        let effect = a.effect(move |x| {
+            log.borrow_mut().push(format!("open {x}"));
+            let (log, x) = (log.clone(), *x);
+            move || log.borrow_mut().push(format!("close {x}"))
+        });
+        a.send(1);
+        a.send(2);
+        assert_eq!(*events.borrow(), vec!["open 1", "close 1", "open 2"]);
+        drop(effect);
+        a.send(3);
+        assert_eq!(
+            *events.borrow(),
+            vec!["open 1", "close 1", "open 2", "close 2"]
+        );
     }
-    fn decrease_dirty(&self) {
-        *self.0.dirty.borrow_mut() -= 1;
+
+    #[test]
+    fn test_default_and_from() {
+        fn make<'a, T: Default + From<u8> + 'a>() -> (Signal<'a, T>, Signal<'a, T>) {
+            (Signal::default(), T::from(7).into())
+        }
+        let s: Signal<i32> = 5.into();
+        assert_eq!(s.get(), 5);
+        let d: Signal<String> = Signal::default();
+        assert_eq!(d.get(), "");
+        let (d, f) = make::<u32>();
+        assert_eq!((d.get(), f.get()), (0, 7));
+        let doubled = f.map(|x| x * 2);
+        f.send(8);
+        assert_eq!(doubled.get(), 16);
     }
-    fn get_dirty(&self) -> isize {
-        *self.0.dirty.borrow()
+
+    #[test]
+    fn test_partial_eq_compares_values() {
+        let a = Signal::new(1);
+        let b = Signal::new(1);
+        let c = Signal::new(2);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a, a);
+        c.send(1);
+        assert_eq!(a, c);
     }
-    fn reset_explicitly_modified(&self) {
-        *self.0.explicitly_modified.borrow_mut() = false;
+
+    #[test]
+    fn test_map_cloned_passes_ownership() {
+        let name = Signal::new(String::from("ab"));
+        let len = name.map_cloned(|s: String| s.into_bytes().len());
+        assert_eq!(len.get(), 2);
+        name.send(String::from("abcd"));
+        assert_eq!(len.get(), 4);
+        assert_eq!(name.get(), "abcd");
     }
-    fn collect_guards_recursive(&self, result: &mut Vec<SignalGuardInner<'a>>) {
-        self.mark_dirty();
-        result.push(SignalGuardInner(self.clone_box()));
-        self.collect_and_iterate(&self.0.successors, |signal| {
-            signal.collect_guards_recursive(result);
-        });
+
+    #[test]
+    fn test_graph_counts() {
+        let a = Signal::new(1);
+        let b = Signal::new(2);
+        assert_eq!((a.successor_count(), a.predecessor_count()), (0, 0));
+        let doubled = a.map(|x| x * 2);
+        let sum = a.combine_map(&b, |x, y| x + y);
+        let shifted = a.promap(|x| x + 1, |y| y - 1);
+        assert_eq!(a.successor_count(), 3);
+        assert_eq!(b.successor_count(), 1);
+        assert_eq!(
+            (doubled.predecessor_count(), shifted.predecessor_count()),
+            (0, 1)
+        );
+        drop(doubled);
+        assert_eq!(a.successor_count(), 3);
+        assert_eq!(a.live_successor_count(), 2);
+        assert_eq!(a.successor_count(), 2);
+        drop(sum);
+        assert_eq!(b.live_successor_count(), 0);
+        assert_eq!(a.live_successor_count(), 1);
+        drop(shifted);
+        assert_eq!(a.live_successor_count(), 0);
     }
-    fn collect_predecessors_recursive(&self, result: &mut Vec<SignalGuardInner<'a>>) {
-        self.mark_dirty();
-        result.push(SignalGuardInner(self.clone_box()));
-        // Collect predecessors last so they drop last (react last)
-        self.collect_and_iterate(&self.0.predecessors, |signal| {
-            signal.collect_predecessors_recursive(result);
-        });
+
+    #[test]
+    fn test_to_dot() {
+        let a = Signal::new(1).with_label("a");
+        let b = Signal::new(2);
+        let left = a.map(|x| x + 1);
+        let right = a.promap(|x| x * 2, |y| y / 2);
+        let sum = left.combine_map(&right, |x, y| x + y);
+        let _product = sum.combine_map(&b, |x, y| x * y);
+        let dot = a.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("n0 [label=\"#0 a\"];"));
+        // a -> left, a -> right, right -> a (dashed), left -> sum, right -> sum, sum -> product
+        assert_eq!(dot.matches(" -> ").count(), 6);
+        assert_eq!(dot.matches("[style=dashed]").count(), 1);
+        assert_eq!(dot.matches("[label=").count(), 5);
     }
-}
 
-impl<T> Clone for Signal<'_, T> {
-    fn clone(&self) -> Self {
-        Self(self.0.clone())
+    #[test]
+    fn test_trace_hook_events() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x * 2);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let events_clone = events.clone();
+        set_trace_hook(move |event| events_clone.borrow_mut().push(event));
+        a.send(2);
+        clear_trace_hook();
+        a.send(3);
+        let (a, b) = (a.trace_id(), b.trace_id());
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                TraceEvent::Propagate(a),
+                TraceEvent::MarkDirty(a),
+                TraceEvent::MarkDirty(b),
+                TraceEvent::React(a),
+                TraceEvent::React(b),
+            ]
+        );
     }
-}
 
-impl<'a, T> AsRef<Signal<'a, T>> for Signal<'a, T> {
-    fn as_ref(&self) -> &Signal<'a, T> {
-        self
+    #[test]
+    fn test_replace_and_take() {
+        let a = Signal::new(vec![1, 2]);
+        let len = a.map(|v| v.len());
+        let (previous, guard) = a.replace(vec![3]);
+        drop(guard);
+        assert_eq!(previous, vec![1, 2]);
+        assert_eq!(len.get(), 1);
+        let (taken, guard) = a.take();
+        drop(guard);
+        assert_eq!(taken, vec![3]);
+        assert_eq!((a.get(), len.get()), (vec![], 0));
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn test_replace_overrides_derived() {
+        let a = Signal::new(1);
+        let b = a.map(|x| x * 10);
+        let c = b.map(|x| x + 1);
+        let (previous, _) = b.replace(5);
+        assert_eq!((previous, c.get()), (10, 6));
+        a.send(2);
+        assert_eq!((b.get(), c.get()), (20, 21));
+    }
 
-    use crate::api::LiftInto;
+    #[test]
+    fn test_accumulate() {
+        let a = Signal::new(0);
+        let history = a.accumulate();
+        let recent = a.accumulate_bounded(3);
+        for i in 1..=5 {
+            a.send(i);
+        }
+        assert_eq!(history.get(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(recent.get(), vec![3, 4, 5]);
+    }
 
-    use super::*;
+    #[test]
+    #[should_panic(expected = "the accumulate capacity must not be zero")]
+    fn test_accumulate_bounded_zero_cap() {
+        Signal::new(0).accumulate_bounded(0);
+    }
 
     #[test]
-    fn test_signal() {
+    fn test_zip_pairs_in_order() {
         let a = Signal::new(0);
-        let _a = a.map(|x| println!("a changed: {}", x));
-        (a.send(100), a.send(5));
+        let b = Signal::new('-');
+        let zipped = a.zip(&b);
+        let pairs = zipped.accumulate();
+        a.send(1);
+        a.send(2);
+        b.send('x');
+        a.send(3);
+        b.send('y');
+        b.send('z');
+        b.send('w');
+        a.send(4);
+        assert_eq!(
+            pairs.get(),
+            vec![(0, '-'), (1, 'x'), (2, 'y'), (3, 'z'), (4, 'w')]
+        );
+        (a.send(5), b.send('v'));
+        assert_eq!(zipped.get(), (5, 'v'));
     }
 
     #[test]
-    fn test_signal1() {
-        let a = Signal::new(0);
-        let b = a.map(|x| x * 2);
-        let _b = b.map(|x| println!("b changed: {}", x));
-        drop(b);
-        a.send(100);
+    fn test_combined_guard_reacts_predecessors_last() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let result = Signal::new(0);
+        let source = result.contramap(|x: &i32| x * 2);
+        let log_result = log.clone();
+        let _result_observer =
+            result.subscribe(move |x| log_result.borrow_mut().push(("result", *x)));
+        let other = Signal::new(0);
+        let doubled = other.map(|x| x * 2);
+        let log_doubled = log.clone();
+        let _doubled_observer =
+            doubled.subscribe(move |x| log_doubled.borrow_mut().push(("doubled", *x)));
+
+        drop(source.send(1).and(other.send(1)));
+        assert_eq!(*log.borrow(), vec![("doubled", 2), ("result", 2)]);
+        log.borrow_mut().clear();
+        drop(other.send(2).and(source.send(2)));
+        assert_eq!(*log.borrow(), vec![("doubled", 4), ("result", 4)]);
     }
 
     #[test]
-    fn test_signal2() {
-        let a = Signal::new(0);
-        let b = a.map(|x| x * 2);
-        let ab = a.combine(&b);
-        let _ab = ab.map(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-        (a.send(5), a.send(100));
+    fn test_map_into_reuses_allocation() {
+        let source = Signal::new(vec![1u8; 16]);
+        let copy = source.map_into(Vec::with_capacity(4096), |input, output: &mut Vec<u8>| {
+            output.clear();
+            output.extend_from_slice(input);
+        });
+        let buffer = copy.0.value.borrow().as_ptr();
+        for len in [64, 1024, 8] {
+            source.send(vec![len as u8; len]);
+            let output = copy.0.value.borrow();
+            assert_eq!(output.len(), len);
+            assert_eq!(output.capacity(), 4096);
+            assert_eq!(output.as_ptr(), buffer);
+        }
     }
 
     #[test]
-    fn test_signal3() {
-        let a = Signal::new(0);
-        let b = a.map(|x| x * 2);
-        let ab = (&a, &b).lift();
-        let _ab = ab.map(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-        (a.send(5), b.send(100));
+    fn test_freeze_holds_back_promap_write_back() {
+        let a = Signal::new(1);
+        let b = a.promap(|x| x * 10, |y| y / 10);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = a.subscribe(move |x| seen_clone.borrow_mut().push(*x));
+
+        let frozen = b.freeze();
+        b.send(20);
+        b.send(30);
+        assert_eq!(a.get(), 1);
+        assert!(seen.borrow().is_empty());
+        drop(frozen);
+        assert_eq!((a.get(), b.get()), (3, 30));
+        assert_eq!(*seen.borrow(), vec![3]);
+
+        // A frozen target still follows its source, and thaws without writing back
+        let frozen = b.freeze();
+        a.send(4);
+        drop(frozen);
+        assert_eq!((a.get(), b.get()), (4, 40));
+        assert_eq!(*seen.borrow(), vec![3, 4]);
     }
 
     #[test]
-    fn test_signal4() {
+    fn test_freeze_reacts_once_on_thaw() {
         let a = Signal::new(0);
-        let b = Signal::new(10);
-        let c = Signal::new(20);
-        let abc = [&a, &b, &c].lift();
-        let _abc =
-            abc.map(|[x, y, z]| println!("d changed: {} + {} + {} = {}", x, y, z, x + y + z));
-        let d = Signal::new(0);
-        let abcd = abc.combine(&d);
-        let _abcd = abcd.map(|numbers| {
-            println!(
-                "e changed: {} + {} + {} + {} = {}",
-                numbers.0[0],
-                numbers.0[1],
-                numbers.0[2],
-                numbers.1,
-                numbers.0.iter().sum::<i32>() + numbers.1
-            )
-        });
-        (a.send(5), b.send(15), c.send(25), abc.send([2, 3, 4]));
+        let doubled = a.map(|x| x * 2);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = doubled.subscribe(move |x| seen_clone.borrow_mut().push(*x));
+
+        let frozen = a.freeze();
+        a.send(1);
+        a.send(2);
+        assert_eq!(a.get(), 2);
+        assert_eq!(doubled.get(), 0);
+        assert!(seen.borrow().is_empty());
+        drop(frozen);
+        assert_eq!(doubled.get(), 4);
+        assert_eq!(*seen.borrow(), vec![4]);
+
+        // Nested freezes thaw with the last guard, and thawing without a send does nothing
+        let outer = a.freeze();
+        let inner = a.freeze();
+        a.send(3);
+        drop(inner);
+        assert_eq!(*seen.borrow(), vec![4]);
+        drop(outer);
+        assert_eq!(*seen.borrow(), vec![4, 6]);
+        drop(a.freeze());
+        assert_eq!(*seen.borrow(), vec![4, 6]);
     }
 
     #[test]
-    fn test_signal5() {
-        let result = Signal::new(42);
-        let source1 = result.contramap(|x| x + 1);
-        let source2 = source1.contramap(|x| x * 2);
+    fn test_map_keyed_remaps_only_changes() {
+        let items = Signal::new(vec![(1, "a"), (2, "b"), (3, "c")]);
+        let mapped = Rc::new(RefCell::new(Vec::new()));
+        let mapped_clone = mapped.clone();
+        let upper = items.map_keyed(
+            |(id, _)| *id,
+            move |(id, text)| {
+                mapped_clone.borrow_mut().push(*id);
+                text.to_uppercase()
+            },
+        );
+        assert_eq!(upper.get(), vec!["A", "B", "C"]);
+        assert_eq!(*mapped.borrow(), vec![1, 2, 3]);
 
-        let _observer_1 = result.map(|x| println!("result changed: {}", x));
-        let _observer_2 = source1.map(|x| println!("source1 changed: {}", x));
-        let _observer_3 = source2.map(|x| println!("source2 changed: {}", x));
+        mapped.borrow_mut().clear();
+        items.send(vec![(3, "c"), (1, "a"), (2, "bee"), (4, "d")]);
+        assert_eq!(upper.get(), vec!["C", "A", "BEE", "D"]);
+        assert_eq!(*mapped.borrow(), vec![2, 4]);
 
-        println!("--- Sending to source1 ---");
-        source1.send(100);
-        println!("--- Sending to source2 ---");
-        source2.send(200);
-        println!("--- Sending to source1 and source2 ---");
-        (source1.send(300), source2.send(400));
+        // A key dropped and added again is mapped again
+        mapped.borrow_mut().clear();
+        items.send(vec![(1, "a")]);
+        items.send(vec![(1, "a"), (3, "c")]);
+        assert_eq!(upper.get(), vec!["A", "C"]);
+        assert_eq!(*mapped.borrow(), vec![3]);
     }
 
     #[test]
-    fn test_promap_forward() {
-        let source = Signal::new(10);
-        let derived = source.promap(|x| x * 2, |y| y / 2);
+    fn test_split() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let pair = Signal::new((1, String::from("one")));
+        let (number, name) = pair.split();
+        let log = seen.clone();
+        let _number = number.map(move |n| log.borrow_mut().push(format!("number: {}", n)));
+        let log = seen.clone();
+        let _name = name.map(move |s| log.borrow_mut().push(format!("name: {}", s)));
+        seen.borrow_mut().clear();
 
-        let _ = derived.map(|x| println!("derived changed: {}", x));
-        source.send(5);
+        pair.send((2, String::from("two")));
+        assert_eq!(*number.0.value.borrow(), 2);
+        assert_eq!(*name.0.value.borrow(), "two");
+        assert_eq!(*seen.borrow(), vec!["number: 2", "name: two"]);
+
+        drop(name);
+        pair.send((3, String::from("three")));
+        assert_eq!(*number.0.value.borrow(), 3);
+        assert_eq!(seen.borrow().last().unwrap(), "number: 3");
     }
 
     #[test]
-    fn test_promap_backward() {
-        let source = Signal::new(10);
-        let derived = source.promap(|x| x * 2, |y| y / 2);
+    fn test_project_field() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let point = Signal::new(Point { x: 1, y: 2 });
+        let x = point.project(|p| p.x, |p, x| p.x = x);
+        let log = seen.clone();
+        let _observer = point.map(move |p| log.borrow_mut().push((p.x, p.y)));
+        seen.borrow_mut().clear();
+        assert_eq!(*x.0.value.borrow(), 1);
 
-        let _ = source.map(|x| println!("source changed: {}", x));
-        let _ = derived.map(|x| println!("derived changed: {}", x));
-        derived.send(50);
+        point.send(Point { x: 3, y: 4 });
+        assert_eq!(*x.0.value.borrow(), 3);
+
+        x.send(5);
+        assert_eq!(*point.0.value.borrow(), Point { x: 5, y: 4 });
+        assert_eq!(*seen.borrow(), vec![(3, 4), (5, 4)]);
+
+        point.send(Point { x: 6, y: 7 });
+        assert_eq!(*x.0.value.borrow(), 6);
     }
 
     #[test]
-    fn test_promap_bidirectional() {
+    fn test_map_lazy_defers_f() {
+        let calls = Rc::new(RefCell::new(0));
         let a = Signal::new(10);
-        let b = a.promap(|x| x * 2, |y| y / 2);
-        let c = b.promap(|x| x + 3, |y| y - 3);
-
-        let _observer_a = a.map(|x| println!("a changed: {}", x));
-        let _observer_b = b.map(|x| println!("b changed: {}", x));
-        let _observer_c = c.map(|x| println!("c changed: {}", x));
+        let count = calls.clone();
+        let b = a.map_lazy(move |x| {
+            *count.borrow_mut() += 1;
+            x * 2
+        });
+        assert_eq!(*calls.borrow(), 0);
+        assert_eq!(*b.0.value.borrow(), None);
 
-        println!("--- Sending to a ---");
         a.send(5);
-        println!("--- Sending to c ---");
-        c.send(13);
-        println!("--- Sending to b ---");
-        b.send(10);
-        println!("--- Sending to a and c ---");
-        a.send(20).and(c.send(50));
-        println!("--- Sending to b and c ---");
-        (b.send(30), c.send(60));
+        assert_eq!(*calls.borrow(), 1);
+        assert_eq!(*b.0.value.borrow(), Some(10));
+
+        let count = calls.clone();
+        let _eager = a.map(move |x| {
+            *count.borrow_mut() += 1;
+            x * 2
+        });
+        assert_eq!(*calls.borrow(), 2);
     }
 
     #[test]
-    fn test_high_order_signal() {
-        let source = Signal::new(0);
-        let derived = source.map(|x| Signal::new(x + 1));
-        let _ = derived.map(|s| s.send(233));
-        let _ = derived.map(|s| s.map(|x| println!("derived changed: {}", x)));
-        source.send(5);
+    fn test_filter_registers_successor() {
+        let a = Signal::new(0);
+        let even = a.filter(|x| x % 2 == 0);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(even.reaction_count(), 0);
+        drop(even);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
     }
 
     #[test]
-    fn test_depend() {
-        let a = Signal::new(10);
-        let b = Signal::new(10);
-        let c = Signal::new(10);
+    fn test_distinct_by_registers_successor() {
+        let a = Signal::new(0);
+        let distinct = a.distinct_by(|x, y| x % 10 == y % 10);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(10);
+        assert_eq!(distinct.reaction_count(), 0);
+        drop(distinct);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
+    }
 
-        let _observer_a = a.map(|x| println!("a changed: {}", x));
-        let _observer_b = b.map(|x| println!("b changed: {}", x));
-        let _observer_c = c.map(|x| println!("c changed: {}", x));
+    #[test]
+    fn test_dedupe_registers_successor() {
+        let a = Signal::new(1).with_eq(PartialEq::eq);
+        let deduped = a.dedupe();
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(deduped.reaction_count(), 0);
+        drop(deduped);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
+    }
 
-        c.depend(b.with(|x| x * 2));
-        b.depend(a.clone());
+    #[test]
+    fn test_validated_registers_successor() {
+        let a = Signal::new(1.0_f64);
+        let valid = a.validated(|x| x.is_finite());
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(f64::NAN);
+        assert_eq!(valid.reaction_count(), 0);
+        drop(valid);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
+    }
 
-        a.send(42);
+    #[test]
+    fn test_map_forward_registers_successor() {
+        let a = Signal::new(0);
+        let tens = a.map_forward(|x| (x * 10, None));
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Unlike the forwarding combinators, propagation follows this edge
+        a.send(1);
+        assert_eq!((tens.get(), tens.reaction_count()), (10, 1));
+        drop(tens);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
     }
 
     #[test]
-    fn test_depend2() {
-        let a = Signal::new(10);
-        let b = a.map(|x| *x);
-        let c = b.map(|x| *x);
+    fn test_combine_latest_registers_successor() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let latest = a.combine_latest(&b);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        assert_eq!(b.successor_count(), 1);
+        assert!(b.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(latest.reaction_count(), 0);
+        drop(latest);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
+        assert_eq!(b.live_successor_count(), 0);
+        assert_eq!(b.0.react_fns.borrow().entries.len(), 0);
+    }
 
-        let _observer_a = a.map(|x| println!("a changed: {}", x));
-        let _observer_b = b.map(|x| println!("b changed: {}", x));
-        let _observer_c = c.map(|x| println!("c changed: {}", x));
+    #[test]
+    fn test_zip_registers_successor() {
+        let a = Signal::new(0);
+        let b = Signal::new(0);
+        let zipped = a.zip(&b);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        assert_eq!(b.successor_count(), 1);
+        assert!(b.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(zipped.reaction_count(), 0);
+        drop(zipped);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.borrow().entries.len(), 0);
+        assert_eq!(b.live_successor_count(), 0);
+        assert_eq!(b.0.react_fns.borrow().entries.len(), 0);
+    }
 
-        (a.send(42), b.send(88));
+    #[test]
+    fn test_map_variants_share_error_sink() {
+        let a = Signal::new(1);
+        let sink = a.error_sink();
+        let shares =
+            |mapped: Option<&Rc<_>>| mapped.is_some_and(|mapped| Rc::ptr_eq(mapped, &sink));
+        assert!(shares(a.map(|x| x + 1).0.error_sink.borrow().as_ref()));
+        assert!(shares(a.with(|x| x + 1).0.error_sink.borrow().as_ref()));
+        assert!(shares(
+            a.map_copied(|x| x + 1).0.error_sink.borrow().as_ref()
+        ));
+        assert!(shares(
+            a.map_cloned(|x| x + 1).0.error_sink.borrow().as_ref()
+        ));
+        assert!(shares(
+            a.map_into(0, |x, y| *y = *x).0.error_sink.borrow().as_ref()
+        ));
+        assert!(shares(a.map_lazy(|x| x + 1).0.error_sink.borrow().as_ref()));
+        assert!(shares(a.map_memo(|x| x + 1).0.error_sink.borrow().as_ref()));
+        assert!(shares(
+            a.map_forward(|x| (*x, None)).0.error_sink.borrow().as_ref()
+        ));
+        assert!(shares(
+            a.map_eager(|x| x + 1).0.error_sink.borrow().as_ref()
+        ));
     }
 }
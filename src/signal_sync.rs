@@ -1,22 +1,51 @@
 use std::{
-    iter,
+    any::{Any, TypeId, type_name},
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    hash::Hash,
+    iter, ops,
+    panic::{self, AssertUnwindSafe},
     sync::{
-        Arc, Mutex, RwLock,
-        atomic::{AtomicBool, AtomicIsize, Ordering},
+        Arc, Condvar, Mutex, MutexGuard, PoisonError, RwLock, TryLockError, Weak,
+        atomic::{AtomicBool, AtomicIsize, AtomicU64, AtomicUsize, Ordering},
+        mpsc::{self, Receiver},
     },
+    thread,
+    time::{Duration, Instant},
 };
 
 use crate::api::LiftableSync;
+use crate::signal::{
+    DotGraph, ListChange, ReactionError, ReactionId, TraceEvent, diff_keyed_changes, panic_message,
+};
 
 pub(crate) trait SignalExtSync<'a>: Send + Sync {
-    fn react(&self);
+    fn react(&self) -> Vec<SignalGuardInnerSync<'a>>;
     fn guard(&self) -> SignalGuardSync<'a>;
     fn decrease_dirty(&self);
     fn get_dirty(&self) -> isize;
     fn clone_box(&self) -> Box<dyn SignalExtSync<'a> + 'a>;
-    fn collect_guards_recursive(&self, result: &mut Vec<SignalGuardInnerSync<'a>>);
-    fn collect_predecessors_recursive(&self, result: &mut Vec<SignalGuardInnerSync<'a>>);
+    fn collect_guards_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInnerSync<'a>>,
+        visited: &mut HashSet<usize>,
+    );
+    fn collect_predecessors_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInnerSync<'a>>,
+        visited: &mut HashSet<usize>,
+    );
     fn reset_explicitly_modified(&self);
+    fn clear_poison(&self);
+    fn push_reaction(
+        &self,
+        react_fn: Box<dyn Fn() + Send + Sync + 'a>,
+        owner: WeakSignalRefSync<'a>,
+    ) -> ReactionId;
+    fn push_successor(&self, successor: WeakSignalRefSync<'a>);
+    fn return_guard_buffer(&self, buffer: Vec<SignalGuardInnerSync<'a>>);
+    fn write_dot(&self, graph: &mut DotGraph) -> usize;
 }
 
 // Strategy trait for reference handling (thread-safe version)
@@ -64,6 +93,9 @@ impl<'a> RefStrategySync<'a> for StrongRefStrategySync {
 
 // Helper struct to hold weak references that can be upgraded (thread-safe version)
 pub(crate) struct WeakSignalRefSync<'a> {
+    addr: usize,
+    /// Whether the signal is only reached through the guard its reaction forwards
+    forwarded: bool,
     upgrade: Box<dyn Fn() -> Option<Box<dyn SignalExtSync<'a> + 'a>> + Send + Sync + 'a>,
 }
 
@@ -71,6 +103,8 @@ impl<'a> WeakSignalRefSync<'a> {
     pub fn new<T: Send + Sync + 'a>(signal: &SignalSync<'a, T>) -> Self {
         let weak = Arc::downgrade(&signal.0);
         WeakSignalRefSync {
+            addr: Arc::as_ptr(&signal.0) as *const () as usize,
+            forwarded: false,
             upgrade: Box::new(move || {
                 weak.upgrade()
                     .map(|arc| Box::new(SignalSync(arc)) as Box<dyn SignalExtSync<'a> + 'a>)
@@ -78,6 +112,18 @@ impl<'a> WeakSignalRefSync<'a> {
         }
     }
 
+    /// Refer to a signal that propagation does not mark dirty directly
+    ///
+    /// The signal is still a successor for introspection and pruning, but it is only marked
+    /// dirty by the guard that the reaction feeding it forwards, when that reaction decides
+    /// the signal changed.
+    pub fn forwarded<T: Send + Sync + 'a>(signal: &SignalSync<'a, T>) -> Self {
+        WeakSignalRefSync {
+            forwarded: true,
+            ..Self::new(signal)
+        }
+    }
+
     pub fn upgrade(&self) -> Option<Box<dyn SignalExtSync<'a> + 'a>> {
         (self.upgrade)()
     }
@@ -85,826 +131,7274 @@ impl<'a> WeakSignalRefSync<'a> {
     pub fn is_alive(&self) -> bool {
         self.upgrade().is_some()
     }
+
+    /// Check whether this refers to the given signal
+    pub fn points_to<T: Send + Sync + 'a>(&self, signal: &SignalSync<'a, T>) -> bool {
+        self.addr == Arc::as_ptr(&signal.0) as *const () as usize
+    }
 }
 
-/// The inner part of the signal (thread-safe version)
-pub struct SignalGuardInnerSync<'a>(Box<dyn SignalExtSync<'a> + 'a>);
+/// A reaction registered on a signal (thread-safe version)
+#[derive(Clone)]
+pub(crate) enum ReactionSync<'a> {
+    /// A reaction that only updates the graph
+    Plain(Arc<dyn Fn() + Send + Sync + 'a>),
+    /// A reaction that may forward a guard into the batch it runs in
+    Forwarding(Arc<dyn Fn() -> Option<SignalGuardSync<'a>> + Send + Sync + 'a>),
+}
+
+impl<'a> ReactionSync<'a> {
+    /// Run the reaction, returning the guard it forwards, if any
+    pub fn run(&self) -> Option<SignalGuardSync<'a>> {
+        #[cfg(feature = "bench")]
+        crate::bench::record_reaction();
+        match self {
+            ReactionSync::Plain(react_fn) => {
+                react_fn();
+                None
+            }
+            ReactionSync::Forwarding(react_fn) => react_fn(),
+        }
+    }
+}
+
+/// The reactions registered on a signal (thread-safe version)
+pub(crate) struct ReactionsSync<'a> {
+    next_id: usize,
+    entries: Vec<(ReactionId, ReactionSync<'a>, Option<WeakSignalRefSync<'a>>)>,
+}
+
+impl<'a> ReactionsSync<'a> {
+    pub fn new() -> Self {
+        ReactionsSync {
+            next_id: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Register a reaction and return its identifier
+    pub fn push(&mut self, react_fn: Box<dyn Fn() + Send + Sync + 'a>) -> ReactionId {
+        self.insert(ReactionSync::Plain(Arc::from(react_fn)), None)
+    }
+
+    /// Register a reaction that feeds `owner` and return its identifier
+    ///
+    /// The reaction is removed by `retain_alive` once its owner has been dropped.
+    pub fn push_owned(
+        &mut self,
+        react_fn: Box<dyn Fn() + Send + Sync + 'a>,
+        owner: WeakSignalRefSync<'a>,
+    ) -> ReactionId {
+        self.insert(ReactionSync::Plain(Arc::from(react_fn)), Some(owner))
+    }
+
+    /// Register a reaction that forwards a guard and return its identifier
+    pub fn push_forwarding(
+        &mut self,
+        react_fn: Box<dyn Fn() -> Option<SignalGuardSync<'a>> + Send + Sync + 'a>,
+    ) -> ReactionId {
+        self.insert(ReactionSync::Forwarding(Arc::from(react_fn)), None)
+    }
+
+    /// Register a reaction that forwards a guard to `owner` and return its identifier
+    ///
+    /// The reaction is removed by `retain_alive` once its owner has been dropped.
+    pub fn push_forwarding_owned(
+        &mut self,
+        react_fn: Box<dyn Fn() -> Option<SignalGuardSync<'a>> + Send + Sync + 'a>,
+        owner: WeakSignalRefSync<'a>,
+    ) -> ReactionId {
+        self.insert(ReactionSync::Forwarding(Arc::from(react_fn)), Some(owner))
+    }
+
+    fn insert(
+        &mut self,
+        reaction: ReactionSync<'a>,
+        owner: Option<WeakSignalRefSync<'a>>,
+    ) -> ReactionId {
+        let id = ReactionId::new(self.next_id);
+        self.next_id += 1;
+        self.entries.push((id, reaction, owner));
+        id
+    }
+
+    /// Remove a reaction, returning whether it was still registered
+    pub fn remove(&mut self, id: ReactionId) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(entry_id, _, _)| *entry_id != id);
+        self.entries.len() != len
+    }
+
+    /// Remove the reactions whose owner has been dropped
+    pub fn retain_alive(&mut self) {
+        self.entries
+            .retain(|(_, _, owner)| owner.as_ref().is_none_or(|owner| owner.is_alive()));
+    }
+
+    /// Remove the reactions feeding `owner`, returning whether any was registered
+    pub fn remove_owned_by<U: Send + Sync + 'a>(&mut self, owner: &SignalSync<'a, U>) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(_, _, entry_owner)| {
+            !entry_owner
+                .as_ref()
+                .is_some_and(|entry_owner| entry_owner.points_to(owner))
+        });
+        self.entries.len() != len
+    }
+
+    /// Remove every reaction
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Take a snapshot of the reactions so they can run without holding the lock,
+    /// which lets a reaction register or remove reactions on the same signal
+    pub fn snapshot(&self) -> Vec<ReactionSync<'a>> {
+        self.entries
+            .iter()
+            .map(|(_, reaction, _)| reaction.clone())
+            .collect()
+    }
+}
+
+/// The inner part of the signal (thread-safe version), and whether it was reached backwards
+/// as a predecessor
+pub struct SignalGuardInnerSync<'a>(Box<dyn SignalExtSync<'a> + 'a>, bool);
 
 /// Guard that ensures reactions are processed when dropped (thread-safe version)
 #[allow(dead_code)]
 #[allow(unused_must_use)]
-pub struct SignalGuardSync<'a>(Vec<SignalGuardInnerSync<'a>>);
+pub struct SignalGuardSync<'a>(
+    Vec<SignalGuardInnerSync<'a>>,
+    Option<Box<dyn SignalExtSync<'a> + 'a>>,
+);
 
 impl<'a> SignalGuardSync<'a> {
     /// Combine two signal guards into one
+    ///
+    /// The combined guard reacts as if both sends were made at once: each signal reacts once,
+    /// after all the signals it depends on. The signals reached forwards, the ones sent to and
+    /// those derived from them, react first, in the order the guards were combined; the
+    /// predecessors that they write back to, like the source of a `contramap`, react last, again
+    /// in the order the guards were combined. This is the order of a single guard, so combining
+    /// guards never lets a write-back overtake a forward update.
     pub fn and(mut self, mut other: SignalGuardSync<'a>) -> SignalGuardSync<'a> {
         self.0.append(&mut other.0);
         self
     }
 }
 
-impl<'a> Drop for SignalGuardInnerSync<'a> {
-    fn drop(&mut self) {
+impl SignalGuardSync<'static> {
+    /// Settle the guard once the current propagation has settled, instead of right away
+    ///
+    /// A send made by a reaction normally reacts on the spot, in the middle of the propagation
+    /// running that reaction. Deferring its guard queues it instead: the deferred guards settle,
+    /// in the order they were deferred, after every signal of the current propagation has
+    /// reacted. Outside of a propagation, this is the same as dropping the guard.
+    ///
+    /// Only guards of `'static` signals can be deferred, as they are still held after the
+    /// reaction that sent to them has returned. This is also why sends made by reactions are not
+    /// queued by default: a signal created inside a reaction may have observers borrowing the
+    /// locals of that reaction, which are gone by the time the propagation settles.
+    pub fn defer(mut self) {
+        if PROPAGATING.with(Cell::get) {
+            let mut deferred = std::mem::take(&mut self.0);
+            deferred.sort_by_key(|inner| inner.1);
+            DEFERRED.with(|queue| queue.borrow_mut().push_back(deferred));
+        }
+    }
+}
+
+impl<'a> SignalGuardInnerSync<'a> {
+    /// Release the hold on the signal and react once it is no longer dirty
+    ///
+    /// Returns the guards forwarded by the reactions.
+    fn release(&self) -> Vec<SignalGuardInnerSync<'a>> {
         self.0.decrease_dirty();
         if self.0.get_dirty() == 0 {
-            self.0.react();
+            let forwarded = self.0.react();
             self.0.reset_explicitly_modified();
+            forwarded
+        } else {
+            Vec::new()
         }
     }
 }
 
 impl<'a> Drop for SignalGuardSync<'a> {
     fn drop(&mut self) {
-        // First drop all inner guards (triggers immediate reactions)
-        drop(std::mem::take(&mut self.0));
+        let mut pending: VecDeque<_> = std::mem::take(&mut self.0).into();
+        // Predecessors react last, even when they come from a guard combined before others;
+        // the sort is stable, so the order within both parts is kept
+        pending.make_contiguous().sort_by_key(|inner| inner.1);
+        // A send made by a reaction settles right away, inside the propagation running it
+        let outermost = !PROPAGATING.with(Cell::get);
+        let _propagation = outermost.then(Propagation::start);
+        settle(&mut pending);
+        if outermost {
+            // Settle the deferred sends of the reactions in the order they were made
+            while let Some(deferred) = DEFERRED.with(|queue| queue.borrow_mut().pop_front()) {
+                settle(&mut deferred.into());
+            }
+        }
+        // Hand the buffer back to the signal that caches it for its next send
+        if let Some(home) = self.1.take() {
+            home.return_guard_buffer(pending.into());
+        }
+        // With nothing to report it to, the panic of a reaction surfaces once the graph has settled
+        if outermost
+            && !thread::panicking()
+            && let Some(payload) = UNREPORTED_PANIC.with(|slot| slot.borrow_mut().take())
+        {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+thread_local! {
+    /// Whether a guard is settling on this thread
+    static PROPAGATING: Cell<bool> = const { Cell::new(false) };
+    /// Guards deferred by reactions, waiting for the current propagation to settle
+    static DEFERRED: RefCell<VecDeque<Vec<SignalGuardInnerSync<'static>>>> = const { RefCell::new(VecDeque::new()) };
+    /// The first panic of a reaction with no error sink to report it to
+    static UNREPORTED_PANIC: RefCell<Option<Box<dyn Any + Send>>> = const { RefCell::new(None) };
+}
+
+/// Map `items` with `f`, split into at most `chunks` chunks mapped on scoped threads
+///
+/// The results are in the order of `items`; a panic in `f` is raised again on this thread.
+fn map_in_chunks<T: Sync, U: Send, F>(items: &[T], chunks: usize, f: &F) -> Vec<U>
+where
+    F: Fn(&T) -> U + Sync,
+{
+    let chunk_len = items.len().div_ceil(chunks).max(1);
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_len)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    })
+}
+
+/// Hook receiving the trace events of every thread
+type TraceHookSync = Arc<dyn Fn(TraceEvent) + Send + Sync>;
+
+/// Whether a trace hook is installed, so tracing costs a single load otherwise
+static TRACING: AtomicBool = AtomicBool::new(false);
+
+/// The trace hook installed with `set_trace_hook_sync`, if any
+static TRACE_HOOK: RwLock<Option<TraceHookSync>> = RwLock::new(None);
+
+/// Install a hook observing the propagation of thread-safe signals (thread-safe version)
+///
+/// The hook is called with a `TraceEvent` whenever a send starts propagating, a signal is marked
+/// dirty and a signal reacts, on the thread doing the work. Unlike `set_trace_hook`, the hook is
+/// global: it observes the signals used on every thread, and replaces the hook previously
+/// installed from any thread. While no hook is installed, tracing is a single atomic load per
+/// event.
+///
+/// # Example
+/// ```rust
+/// # use agility::TraceEvent;
+/// # use agility::signal_sync::{SignalSync, clear_trace_hook_sync, set_trace_hook_sync};
+/// let a = SignalSync::new(1);
+/// let _b = a.map(|x| x * 2);
+/// set_trace_hook_sync(|event| {
+///     if let TraceEvent::React(id) = event {
+///         println!("signal {:x} reacted", id);
+///     }
+/// });
+/// a.send(2); // prints two lines, for `a` then `_b`
+/// clear_trace_hook_sync();
+/// ```
+pub fn set_trace_hook_sync(hook: impl Fn(TraceEvent) + Send + Sync + 'static) {
+    *TRACE_HOOK.write().unwrap() = Some(Arc::new(hook));
+    TRACING.store(true, Ordering::Release);
+}
+
+/// Remove the trace hook installed with `set_trace_hook_sync`, if any
+pub fn clear_trace_hook_sync() {
+    let mut hook = TRACE_HOOK.write().unwrap();
+    TRACING.store(false, Ordering::Release);
+    *hook = None;
+}
+
+/// Report an event to the trace hook, if one is installed
+fn trace(event: TraceEvent) {
+    if !TRACING.load(Ordering::Acquire) {
+        return;
+    }
+    // Clone the hook out of the lock first, so it may itself send or replace the hook
+    let hook = TRACE_HOOK.read().unwrap().clone();
+    if let Some(hook) = hook {
+        hook(event);
+    }
+}
+
+/// Marks the thread as propagating until dropped, including when a reaction panics
+struct Propagation;
+
+impl Propagation {
+    fn start() -> Self {
+        PROPAGATING.with(|propagating| propagating.set(true));
+        Propagation
+    }
+}
+
+impl Drop for Propagation {
+    fn drop(&mut self) {
+        PROPAGATING.with(|propagating| propagating.set(false));
+        if thread::panicking() {
+            DEFERRED.with(|queue| queue.borrow_mut().clear());
+        }
+    }
+}
+
+/// Release the inner guards in order (triggers immediate reactions);
+/// guards forwarded by reactions are queued so they settle in the same batch
+fn settle(pending: &mut VecDeque<SignalGuardInnerSync<'_>>) {
+    while let Some(inner) = pending.pop_front() {
+        pending.extend(inner.release());
+    }
+}
+
+/// A batch held open across a block, flushed when the scope is dropped
+///
+/// Every send made through the scope keeps its guard in the scope, so the reactions of all the
+/// signals sent to run once, together, when the scope is dropped or `flush`ed. Unlike the tuple
+/// idiom `(a.send(1), b.send(2))`, which only batches the sends written in a single expression,
+/// a scope is a value that can be filled over several statements and passed to other functions.
+/// `batch_sync` opens one for the duration of a closure.
+///
+/// # Example
+/// ```rust
+/// # use agility::signal_sync::{SignalSync, BatchScopeSync};
+/// let a = SignalSync::new(1);
+/// let b = SignalSync::new(2);
+/// let pair = a.combine(&b);
+/// let sum = pair.map(|(x, y)| x + y);
+/// let _observer = sum.map(|s| println!("sum: {}", s));
+/// let mut scope = BatchScopeSync::new();
+/// scope.send(&a, 10);
+/// scope.send(&b, 20);
+/// drop(scope); // prints "sum: 30" once
+/// ```
+#[derive(Default)]
+pub struct BatchScopeSync<'a> {
+    guard: Option<SignalGuardSync<'a>>,
+}
+
+impl<'a> BatchScopeSync<'a> {
+    /// Create an empty batch scope
+    pub fn new() -> Self {
+        BatchScopeSync { guard: None }
+    }
+
+    /// Send a new value to a signal, deferring its reactions to the end of the scope
+    pub fn send<T: Send + Sync + 'a>(
+        &mut self,
+        signal: &SignalSync<'a, T>,
+        new_value: T,
+    ) -> &mut Self {
+        self.hold(signal.send(new_value))
+    }
+
+    /// Send a modification to a signal, deferring its reactions to the end of the scope
+    pub fn send_with<T: Send + Sync + 'a, F>(
+        &mut self,
+        signal: &SignalSync<'a, T>,
+        f: F,
+    ) -> &mut Self
+    where
+        F: FnOnce(&mut T),
+    {
+        self.hold(signal.send_with(f))
+    }
+
+    /// Hold an existing guard in the scope until it ends
+    pub fn hold(&mut self, guard: SignalGuardSync<'a>) -> &mut Self {
+        self.guard = Some(match self.guard.take() {
+            Some(held) => held.and(guard),
+            None => guard,
+        });
+        self
+    }
+
+    /// End the scope now, running the reactions of every signal sent through it
+    pub fn flush(self) {}
+}
+
+/// Run a closure as a single batch, so every signal sent to in it reacts once, at the end
+///
+/// The closure receives a `BatchScopeSync` and every send made through it is held until `f`
+/// returns, like the tuple idiom `(a.send(1), b.send(2))` but spanning any number of statements;
+/// a stray `;` can't fire reactions early. The held guards live in the scope rather than in a
+/// thread-local, so they can't outlive the signals sent through it. To nest, pass the scope on
+/// rather than opening another batch.
+///
+/// # Example
+/// ```rust
+/// # use agility::signal_sync::{SignalSync, batch_sync};
+/// let a = SignalSync::new(1);
+/// let b = SignalSync::new(2);
+/// let sum = a.combine_map(&b, |x, y| x + y);
+/// let _observer = sum.map(|s| println!("sum: {}", s));
+/// batch_sync(|scope| {
+///     scope.send(&a, 10);
+///     scope.send(&b, 20);
+/// }); // prints "sum: 30" once
+/// ```
+pub fn batch_sync<'a, R>(f: impl FnOnce(&mut BatchScopeSync<'a>) -> R) -> R {
+    let mut scope = BatchScopeSync::new();
+    let result = f(&mut scope);
+    scope.flush();
+    result
+}
+
+/// Observer that does not keep its source alive and unregisters itself when dropped (thread-safe version)
+pub struct WeakObserverSync<'a> {
+    detach: Option<Box<dyn FnOnce() + Send + Sync + 'a>>,
+}
+
+impl<'a> Drop for WeakObserverSync<'a> {
+    fn drop(&mut self) {
+        if let Some(detach) = self.detach.take() {
+            detach();
+        }
+    }
+}
+
+/// Handle of a subscription made with `subscribe`, which unsubscribes when dropped (thread-safe version)
+pub struct SubscriptionSync<'a> {
+    detach: Option<Box<dyn FnOnce() + Send + Sync + 'a>>,
+}
+
+impl<'a> Drop for SubscriptionSync<'a> {
+    fn drop(&mut self) {
+        if let Some(detach) = self.detach.take() {
+            detach();
+        }
+    }
+}
+
+/// Guard returned by `freeze`, which unfreezes the signal when dropped (thread-safe version)
+pub struct FreezeGuardSync<'a> {
+    thaw: Option<Box<dyn FnOnce() + Send + Sync + 'a>>,
+}
+
+impl<'a> Drop for FreezeGuardSync<'a> {
+    fn drop(&mut self) {
+        if let Some(thaw) = self.thaw.take() {
+            thaw();
+        }
+    }
+}
+
+type CleanupSync<'a> = Box<dyn FnOnce() + Send + 'a>;
+
+/// Value returned by each run of an effect, cleaning up after it (thread-safe version)
+///
+/// An effect returning `()` has nothing to clean up. An effect returning a closure has it run
+/// once, right before the effect runs again and when its `EffectSync` is dropped.
+pub trait EffectCleanupSync<'a> {
+    /// Turn the value into the closure to run, if any
+    fn into_cleanup(self) -> Option<CleanupSync<'a>>;
+}
+
+impl<'a> EffectCleanupSync<'a> for () {
+    fn into_cleanup(self) -> Option<CleanupSync<'a>> {
+        None
+    }
+}
+
+impl<'a, F: FnOnce() + Send + 'a> EffectCleanupSync<'a> for F {
+    fn into_cleanup(self) -> Option<CleanupSync<'a>> {
+        Some(Box::new(self))
+    }
+}
+
+/// Handle of an effect made with `effect`, which stops it and runs its last cleanup when dropped (thread-safe version)
+pub struct EffectSync<'a> {
+    subscription: Option<SubscriptionSync<'a>>,
+    cleanup: Arc<Mutex<Option<CleanupSync<'a>>>>,
+}
+
+impl<'a> Drop for EffectSync<'a> {
+    fn drop(&mut self) {
+        // Unsubscribe first, so the effect can't run again after its last cleanup
+        drop(self.subscription.take());
+        let cleanup = self.cleanup.lock().unwrap().take();
+        if let Some(cleanup) = cleanup {
+            cleanup();
+        }
+    }
+}
+
+/// Blocking iterator over the values of a signal, returned by `watch`
+struct Watch<'a, T> {
+    receiver: Receiver<T>,
+    _subscription: SubscriptionSync<'a>,
+}
+
+impl<T> Iterator for Watch<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Receiver of the latest value of a signal, see `SignalSync::latest`
+pub struct LatestReceiver<'a, T> {
+    slot: Arc<LatestSlot<T>>,
+    _observer: WeakObserverSync<'a>,
+}
+
+/// The single slot shared by a `LatestReceiver` and the reaction filling it
+struct LatestSlot<T> {
+    state: Mutex<LatestState<T>>,
+    ready: Condvar,
+}
+
+struct LatestState<T> {
+    value: Option<T>,
+    closed: bool,
+}
+
+/// Closes the slot when the reaction filling it is dropped
+struct LatestCloser<T>(Arc<LatestSlot<T>>);
+
+impl<T> LatestSlot<T> {
+    fn publish(&self, value: T) {
+        self.state.lock().unwrap().value = Some(value);
+        self.ready.notify_one();
+    }
+}
+
+impl<T> Drop for LatestCloser<T> {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().closed = true;
+        self.0.ready.notify_all();
+    }
+}
+
+impl<'a, T> LatestReceiver<'a, T> {
+    /// Wait for a value newer than the last one received and take it
+    ///
+    /// Returns `None` once the signal is dropped and the last value was taken.
+    pub fn recv(&self) -> Option<T> {
+        let mut state = self.slot.state.lock().unwrap();
+        loop {
+            if let Some(value) = state.value.take() {
+                return Some(value);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.slot.ready.wait(state).unwrap();
+        }
+    }
+
+    /// Take the latest value if there is one newer than the last one received
+    pub fn try_recv(&self) -> Option<T> {
+        self.slot.state.lock().unwrap().value.take()
+    }
+}
+
+/// How `debounce` and `throttle` hold back the values of their source
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Timing {
+    /// Emit once the source has been quiet for the interval
+    Debounce,
+    /// Emit at most once per interval
+    Throttle,
+}
+
+/// The latest value held back by `debounce` or `throttle`, shared with its timer thread
+struct TimedSlot<T> {
+    state: Mutex<TimedState<T>>,
+    ready: Condvar,
+}
+
+struct TimedState<T> {
+    value: Option<T>,
+    /// When the held value may be emitted
+    due: Instant,
+}
+
+/// Signal whose value type has been erased (thread-safe version)
+///
+/// This allows storing differently-typed signals in the same collection. The concrete signal
+/// can be recovered with `downcast` or `downcast_ref`.
+pub struct BoxedSignalSync<'a> {
+    type_id: TypeId,
+    type_name: &'static str,
+    signal: Box<dyn SignalExtSync<'a> + 'a>,
+}
+
+/// Type alias for signals that live for the whole program (thread-safe version)
+pub type StaticSignalSync<T> = SignalSync<'static, T>;
+
+/// Type alias for erased signals that live for the whole program (thread-safe version)
+pub type StaticBoxedSignalSync = BoxedSignalSync<'static>;
+
+impl<'a> BoxedSignalSync<'a> {
+    /// Get the name of the erased value type
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Check whether the erased value type is `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Get a reference to the concrete signal if the value type is `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&SignalSync<'a, T>> {
+        if self.is::<T>() {
+            // SAFETY: the box was created by `erase` from a `SignalSync<'a, T>` with the same `TypeId`
+            let ptr = self.signal.as_ref() as *const (dyn SignalExtSync<'a> + 'a)
+                as *const SignalSync<'a, T>;
+            Some(unsafe { &*ptr })
+        } else {
+            None
+        }
+    }
+
+    /// Recover the concrete signal if the value type is `T`, or give back the erased signal
+    pub fn downcast<T: 'static>(self) -> Result<SignalSync<'a, T>, Self> {
+        if self.is::<T>() {
+            // SAFETY: the box was created by `erase` from a `SignalSync<'a, T>` with the same `TypeId`
+            let ptr = Box::into_raw(self.signal) as *mut SignalSync<'a, T>;
+            Ok(*unsafe { Box::from_raw(ptr) })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Clone for BoxedSignalSync<'_> {
+    fn clone(&self) -> Self {
+        BoxedSignalSync {
+            type_id: self.type_id,
+            type_name: self.type_name,
+            signal: self.signal.clone_box(),
+        }
+    }
+}
+
+/// Signal of any value type, used to declare the dependencies of a computed signal (thread-safe version)
+///
+/// It is implemented by `SignalSync` and `BoxedSignalSync`.
+pub trait ErasedSignalSync<'a> {
+    /// Get a type-erased handle to the signal
+    fn handle(&self) -> SignalHandleSync<'a>;
+}
+
+/// Type-erased handle to a signal (thread-safe version)
+pub struct SignalHandleSync<'a>(Box<dyn SignalExtSync<'a> + 'a>);
+
+impl<'a, T: Send + Sync + 'a> ErasedSignalSync<'a> for SignalSync<'a, T> {
+    fn handle(&self) -> SignalHandleSync<'a> {
+        SignalHandleSync(self.clone_box())
+    }
+}
+
+impl<'a> ErasedSignalSync<'a> for BoxedSignalSync<'a> {
+    fn handle(&self) -> SignalHandleSync<'a> {
+        SignalHandleSync(self.signal.clone_box())
+    }
+}
+
+/// Handler for the errors reported to an error sink
+type ErrorHandlerSync<'a> = Box<dyn Fn(&ReactionError) + Send + Sync + 'a>;
+
+/// The error handlers shared by a graph of signals (thread-safe version)
+pub(crate) struct ErrorSinkSync<'a> {
+    handlers: RwLock<Vec<ErrorHandlerSync<'a>>>,
+}
+
+impl<'a> ErrorSinkSync<'a> {
+    pub fn new() -> Self {
+        ErrorSinkSync {
+            handlers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Register a handler for the errors reported to the sink
+    pub fn subscribe(&self, handler: ErrorHandlerSync<'a>) {
+        self.handlers.write().unwrap().push(handler);
+    }
+
+    /// Report an error to every registered handler, returning whether there was any
+    pub fn report(&self, error: &ReactionError) -> bool {
+        let handlers = self.handlers.read().unwrap();
+        handlers.iter().for_each(|handler| handler(error));
+        !handlers.is_empty()
+    }
+}
+
+/// Error returned by `try_send` and `send_timeout` when the value of the signal stayed locked
+///
+/// It gives back the value that was not sent, so the caller can retry or discard it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrySendError<T>(pub T);
+
+impl<T> TrySendError<T> {
+    /// Get back the value that was not sent
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the value of the signal is locked")
     }
 }
 
+impl<T: fmt::Debug> std::error::Error for TrySendError<T> {}
+
+/// Custom equality predicate used for change detection
+type EqFnSync<'a, T> = Box<dyn Fn(&T, &T) -> bool + Send + Sync + 'a>;
+
 /// The inner part of the signal (thread-safe version)
 pub struct SignalInnerSync<'a, T> {
     pub(crate) value: Mutex<T>,
-    pub(crate) react_fns: RwLock<Vec<Box<dyn Fn() + Send + Sync + 'a>>>,
+    pub(crate) react_fns: RwLock<ReactionsSync<'a>>,
+    pub(crate) eager_react_fns: RwLock<ReactionsSync<'a>>,
     pub(crate) successors: RwLock<Vec<WeakSignalRefSync<'a>>>,
     pub(crate) predecessors: RwLock<Vec<WeakSignalRefSync<'a>>>,
     pub(crate) dirty: AtomicIsize,
     pub(crate) explicitly_modified: AtomicBool,
+    pub(crate) eagerly_reacted: AtomicBool,
+    pub(crate) frozen: AtomicUsize,
+    pub(crate) missed_while_frozen: AtomicBool,
+    pub(crate) sent_while_frozen: AtomicBool,
+    pub(crate) label: RwLock<Option<&'static str>>,
+    pub(crate) eq: RwLock<Option<EqFnSync<'a, T>>>,
+    pub(crate) guard_scratch: Mutex<Option<Vec<SignalGuardInnerSync<'a>>>>,
+    #[cfg(test)]
+    pub(crate) reactions: std::sync::atomic::AtomicUsize,
+    pub(crate) error_sink: RwLock<Option<Arc<ErrorSinkSync<'a>>>>,
 }
 
 /// A signal type that is thread-safe
 pub struct SignalSync<'a, T>(pub(crate) Arc<SignalInnerSync<'a, T>>);
 
 impl<'a, T: Send + Sync + 'a> SignalSync<'a, T> {
-    /// Helper: temporarily take a value from a Mutex using MaybeUninit swap
-    fn take_value<U>(mutex: &Mutex<U>) -> U {
-        let mut temp = unsafe { std::mem::MaybeUninit::<U>::uninit().assume_init() };
-        std::mem::swap(&mut *mutex.lock().unwrap(), &mut temp);
-        temp
-    }
-
     /// Create a new signal with the given initial value
     pub fn new(initial: T) -> Self {
         let inner = Arc::new(SignalInnerSync {
             value: Mutex::new(initial),
-            react_fns: RwLock::new(Vec::new()),
+            react_fns: RwLock::new(ReactionsSync::new()),
+            eager_react_fns: RwLock::new(ReactionsSync::new()),
             successors: RwLock::new(Vec::new()),
             predecessors: RwLock::new(Vec::new()),
             dirty: AtomicIsize::new(0),
             explicitly_modified: AtomicBool::new(false),
+            eagerly_reacted: AtomicBool::new(false),
+            frozen: AtomicUsize::new(0),
+            missed_while_frozen: AtomicBool::new(false),
+            sent_while_frozen: AtomicBool::new(false),
+            label: RwLock::new(None),
+            eq: RwLock::new(None),
+            guard_scratch: Mutex::new(None),
+            #[cfg(test)]
+            reactions: std::sync::atomic::AtomicUsize::new(0),
+            error_sink: RwLock::new(None),
         });
         SignalSync(inner)
     }
 
-    /// Send a new value to the signal
+    /// Create a signal fed by the values received on a channel
     ///
-    /// This will replace the current value of the signal with the new value.
-    /// The signals that depend on this signal will be notified and updated accordingly.
+    /// A worker thread receives the values sent on `receiver` and sends each one to the signal,
+    /// so producers written against plain channels can feed a reactive graph. The worker only
+    /// holds a weak reference to the signal: it stops when the channel disconnects, or at the
+    /// next value received once every clone of the signal has been dropped.
     ///
-    /// It returns a `SignalGuardSync` that ensures reactions are processed when dropped and
-    /// prevents premature reactions during multiple sends. (Batch updates)
     /// # Example
     /// ```rust
-    /// let signal = SignalSync::new(0);
-    /// signal.send(42); // sets the signal's value to 42
-    ///
-    /// signal.with(|v| println!("Signal value: {}", v));
-    /// (signal.send(66), signal.send(100));
-    /// // sets the signal's value to 100 and prints "Signal value: 100" only once
+    /// # use agility::signal_sync::SignalSync;
+    /// # use std::sync::mpsc;
+    /// let (sender, receiver) = mpsc::channel();
+    /// let latest = SignalSync::from_receiver(receiver, 0);
+    /// let _observer = latest.map(|x| println!("received {}", x));
+    /// sender.send(1).unwrap(); // prints "received 1" on the worker thread
     /// ```
-    pub fn send(&self, new_value: T) -> SignalGuardSync<'a> {
-        self.modify(|v| *v = new_value);
-        self.0.explicitly_modified.store(true, Ordering::Release);
-        self.guard()
+    pub fn from_receiver(receiver: Receiver<T>, initial: T) -> SignalSync<'static, T>
+    where
+        T: 'static,
+    {
+        let signal = SignalSync::new(initial);
+        let signal_weak = Arc::downgrade(&signal.0);
+        thread::spawn(move || {
+            for value in receiver {
+                let Some(signal) = signal_weak.upgrade() else {
+                    break;
+                };
+                SignalSync(signal).send(value);
+            }
+        });
+        signal
     }
 
-    /// Send a modification to the signal
+    /// Attach a human readable label to the signal
     ///
-    /// This will apply the provided function to modify the current value of the signal.
-    /// The signals that depend on this signal will be notified and updated accordingly.
+    /// The label is shared by all clones of the signal and is included in diagnostics
+    /// such as debug output, so large graphs can be inspected without relying on addresses.
     ///
-    /// It returns a `SignalGuardSync` that ensures reactions are processed when dropped and
-    /// prevents premature reactions during multiple sends. (Batch updates)
-    pub fn send_with<F>(&self, f: F) -> SignalGuardSync<'a>
-    where
-        F: FnOnce(&mut T),
-    {
-        self.modify(f);
-        self.guard()
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let temperature = SignalSync::new(20).with_label("temperature");
+    /// assert_eq!(temperature.label(), Some("temperature"));
+    /// ```
+    pub fn with_label(self, label: &'static str) -> Self {
+        *self.0.label.write().unwrap() = Some(label);
+        self
     }
 
-    /// Map the signal to a new signal
+    /// Get the label attached with `with_label`, if any
+    pub fn label(&self) -> Option<&'static str> {
+        *self.0.label.read().unwrap()
+    }
+
+    /// Get the id identifying the signal in the events of `set_trace_hook_sync`
     ///
-    /// This creates a new signal that depends on the current signal.
-    /// Changes to the source signal will propagate to the new signal.
+    /// This is the address of the state shared by all clones of the signal, so it is unique among
+    /// the live signals but may be reused once the signal is dropped.
+    pub fn trace_id(&self) -> usize {
+        self.addr()
+    }
+
+    /// Get a clone of the current value of the signal
+    ///
+    /// Unlike `map` or `with`, this does not create a derived signal or register a reaction,
+    /// so no graph edge is added. The lock is held only while cloning. If a thread panicked
+    /// while holding the lock, the value is read anyway; use `try_get` to detect it.
     ///
     /// # Example
     /// ```rust
-    /// let a = SignalSync::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// let _observer = b.map(|x| println!("b changed: {}", x));
-    /// a.send(5); // prints "b changed: 10"
+    /// # use agility::signal_sync::SignalSync;
+    /// let count = SignalSync::new(1);
+    /// count.send(2);
+    /// assert_eq!(count.get(), 2);
     /// ```
-    pub fn map<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    pub fn get(&self) -> T
     where
-        F: Fn(&T) -> U + Send + Sync + 'a,
+        T: Clone,
     {
-        self.map_ref::<U, F, WeakRefStrategySync>(f)
+        self.peek(T::clone)
     }
 
-    /// Map the signal to a new signal with strong references
+    /// Get a clone of the current value of the signal, failing if the lock is poisoned
     ///
-    /// This creates a new signal that depends on the current signal.
-    /// Changes to the source signal will propagate to the new signal.
-    /// This mapping uses strong references.
+    /// The error holds a clone of the value as it was left by the thread that panicked,
+    /// rather than the lock itself, so the lock is never held once `try_get` returns.
+    pub fn try_get(&self) -> Result<T, PoisonError<T>>
+    where
+        T: Clone,
+    {
+        self.0
+            .value
+            .lock()
+            .map(|value| value.clone())
+            .map_err(|poisoned| PoisonError::new(poisoned.into_inner().clone()))
+    }
+
+    /// Read the current value of the signal through a closure
+    ///
+    /// This locks the value, runs `f` on it and returns its result, without cloning the value,
+    /// creating a derived signal or registering a reaction. The lock is released before `peek`
+    /// returns, so it is never held across a later `send`; sending to the signal from inside
+    /// `f` deadlocks, since the lock is still held there. Like `get`, a poisoned lock is read
+    /// anyway.
     ///
     /// # Example
     /// ```rust
-    /// let a = SignalSync::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// b.with(|x| println!("b changed: {}", x));
-    /// a.send(5); // prints "b changed: 10"
+    /// # use agility::signal_sync::SignalSync;
+    /// let names = SignalSync::new(vec![String::from("ada"), String::from("grace")]);
+    /// let count = names.peek(|n| n.len());
+    /// names.send(vec![]); // fine, the lock has been released
+    /// assert_eq!(count, 2);
     /// ```
-    pub fn with<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
-    where
-        F: Fn(&T) -> U + Send + Sync + 'a,
-    {
-        self.map_ref::<U, F, StrongRefStrategySync>(f)
+    pub fn peek<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        let value = self.0.value.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&value)
+    }
+
+    /// Get how many times the signal has reacted, for tests
+    #[cfg(test)]
+    pub(crate) fn reaction_count(&self) -> usize {
+        self.0.reactions.load(std::sync::atomic::Ordering::Relaxed)
     }
 
-    fn map_ref<U: Send + Sync + 'a, F, S: RefStrategySync<'a>>(&self, f: F) -> SignalSync<'a, U>
+    /// Install a custom equality predicate used for change detection
+    ///
+    /// The predicate decides whether a new value is the same as the current one for
+    /// `send_if_changed` and `dedupe`, so change detection works for types that can't or
+    /// shouldn't implement `PartialEq`, like floats compared with a tolerance or structs with
+    /// ignored fields. Types that implement `PartialEq` can simply use `PartialEq::eq`.
+    /// Without a predicate, every value is considered a change.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1.0_f64).with_eq(|x, y| (x - y).abs() < 1e-9);
+    /// let _observer = a.map(|x| println!("a changed: {}", x));
+    /// a.send_if_changed(1.0 + 1e-12); // prints nothing
+    /// a.send_if_changed(2.0); // prints "a changed: 2"
+    /// ```
+    pub fn with_eq<F>(self, eq: F) -> Self
     where
-        F: Fn(&T) -> U + Send + Sync + 'a,
-        S: 'a,
+        F: Fn(&T, &T) -> bool + Send + Sync + 'a,
     {
-        let new_signal = SignalSync::new(f(&self.0.value.lock().unwrap()));
-        let result_new_signal = new_signal.clone();
-
-        let new_signal_ref = S::new_ref(&new_signal);
-        let source_ref = S::new_ref(self);
-
-        let react_fn = Box::new(move || {
-            if let Some(new_sig) = S::upgrade_ref(&new_signal_ref) {
-                if !new_sig.explicitly_modified.load(Ordering::Acquire) {
-                    if let Some(src) = S::upgrade_ref(&source_ref) {
-                        let new_value = f(&src.value.lock().unwrap());
-                        *new_sig.value.lock().unwrap() = new_value;
-                    }
-                }
-            }
-        });
+        *self.0.eq.write().unwrap() = Some(Box::new(eq));
+        self
+    }
 
-        self.0.react_fns.write().unwrap().push(react_fn);
+    /// Reuse the guard buffer of the signal across sends
+    ///
+    /// Every `send` collects the signals to update into a freshly allocated buffer. With this
+    /// enabled the signal keeps the buffer of its last send and reuses it, cleared, for the
+    /// next one, so a signal sent in a tight loop with a stable graph doesn't allocate it on
+    /// every send. A send made while the buffer is in use, e.g. from a reaction to the same
+    /// signal, falls back to a fresh buffer.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0).cache_last_guard();
+    /// let _observer = a.map(|x| x + 1);
+    /// for i in 0..1000 {
+    ///     a.send(i); // allocates the guard buffer only on the first send
+    /// }
+    /// ```
+    pub fn cache_last_guard(self) -> Self {
         self.0
-            .successors
-            .write()
+            .guard_scratch
+            .lock()
             .unwrap()
-            .push(WeakSignalRefSync::new(&new_signal));
+            .get_or_insert_with(Vec::new);
+        self
+    }
 
-        result_new_signal
+    /// Send a new value only if it differs from the current one
+    ///
+    /// The values are compared with the predicate installed by `with_eq`. If the new value is
+    /// the same, nothing is sent and `None` is returned, otherwise this behaves like `send`.
+    pub fn send_if_changed(&self, new_value: T) -> Option<SignalGuardSync<'a>> {
+        if self.is_unchanged(&self.0.value.lock().unwrap(), &new_value) {
+            return None;
+        }
+        Some(self.send(new_value))
     }
 
-    /// Map the signal contravariantly to a new signal
+    /// Create a signal that only propagates actual changes of the current signal
     ///
-    /// This creates a new signal that the current signal depends on.
-    /// Changes to the new signal will propagate back to the original signal.
-    /// It is inspired by the concept of contravariant functors in category theory.
+    /// The new signal is updated, and its observers notified, only when the value of the
+    /// current signal differs from the last propagated one according to the predicate
+    /// installed by `with_eq` on the current signal.
     ///
     /// # Example
     /// ```rust
-    /// let result = SignalSync::new(42);
-    /// let source = result.contramap(|x| x * 2);
-    /// result.with(|x| println!("result changed: {}", x));
-    /// source.with(|x| println!("source changed: {}", x));
-    /// source.send(100);
-    /// // prints "source changed: 100" and "result changed: 50"
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1).with_eq(PartialEq::eq);
+    /// let deduped = a.dedupe();
+    /// let _observer = deduped.map(|x| println!("deduped: {}", x));
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints "deduped: 2"
     /// ```
-    pub fn contramap<F, U>(&self, f: F) -> SignalSync<'a, U>
+    pub fn dedupe(&self) -> SignalSync<'a, T>
     where
-        F: Fn(&U) -> T + Send + Sync + 'a,
-        U: Default + Send + Sync + 'a,
+        T: Clone,
     {
-        let new_signal = SignalSync::new(U::default());
-        let result_new_signal = new_signal.clone();
-        let source_inner = Arc::downgrade(&self.0);
-        let new_signal_rc = Arc::downgrade(&new_signal.0);
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().clone());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
 
         let react_fn = Box::new(move || {
-            if let Some(new_sig) = new_signal_rc.upgrade() {
-                if new_sig.explicitly_modified.load(Ordering::Acquire) {
-                    let u_value_ref = new_sig.value.lock().unwrap();
-                    let t_value = f(&u_value_ref);
-                    drop(u_value_ref);
-
-                    if let Some(source) = source_inner.upgrade() {
-                        *source.value.lock().unwrap() = t_value;
-                        source.explicitly_modified.store(true, Ordering::Release);
-                    }
-                }
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            let source = SignalSync(src);
+            let value = source.0.value.lock().unwrap().clone();
+            if source.is_unchanged(&new_sig.value.lock().unwrap(), &value) {
+                return None;
             }
+            *new_sig.value.lock().unwrap() = value;
+            Some(SignalSync(new_sig).guard())
         });
-        new_signal.0.react_fns.write().unwrap().push(react_fn);
 
-        // Register self (source/result) as a predecessor of new_signal (backward dependency)
+        self.push_forwarded(react_fn, &new_signal);
         new_signal
-            .0
-            .predecessors
-            .write()
-            .unwrap()
-            .push(WeakSignalRefSync::new(self));
-
-        result_new_signal
     }
 
-    /// Map the signal bidirectionally to a new signal
+    /// Map each value to an inner signal and mirror the latest one
     ///
-    /// This creates a new signal that depends on the current signal,
-    /// and the current signal also depends on the new signal.
-    /// Changes to either signal will propagate to the other signal.
-    /// It is inspired by the concept of profunctors in category theory.
+    /// This creates a new signal that mirrors the inner signal returned by `f` for the current
+    /// value. Each change of the current signal calls `f` again and switches over to the new
+    /// inner signal, unsubscribing from the previous one so its later changes are ignored. The
+    /// inner signal in use is kept alive by the new signal. This is `map` followed by `flatten`,
+    /// without the intermediate signal of signals.
     ///
     /// # Example
     /// ```rust
-    /// let a = SignalSync::new(10);
-    /// let b = a.promap(|x| x * 2, |y| y / 2);
-    /// a.with(|x| println!("a changed: {}", x));
-    /// b.with(|x| println!("b changed: {}", x));
-    /// a.send(5); // prints "a changed: 5" and "b changed: 10"
-    /// b.send(50); // prints "b changed: 50" and "a changed: 25"
+    /// # use agility::signal_sync::SignalSync;
+    /// let rooms = vec![SignalSync::new("lobby: empty"), SignalSync::new("kitchen: empty")];
+    /// let selected = SignalSync::new(0);
+    /// let rooms_clone = rooms.clone();
+    /// let shown = selected.switch_map(move |i| rooms_clone[*i].clone());
+    /// let _observer = shown.map(|s| println!("{}", s)); // prints "lobby: empty"
+    /// selected.send(1); // prints "kitchen: empty"
+    /// rooms[0].send("lobby: busy"); // prints nothing, the lobby is no longer shown
+    /// rooms[1].send("kitchen: busy"); // prints "kitchen: busy"
     /// ```
-    pub fn promap<F, G, U>(&self, f: F, g: G) -> SignalSync<'a, U>
+    pub fn switch_map<U, F>(&self, f: F) -> SignalSync<'a, U>
     where
-        F: Fn(&T) -> U + Send + Sync + 'a,
-        G: Fn(&U) -> T + Send + Sync + 'a,
-        U: Default + Send + Sync + 'a,
+        U: Clone + Send + Sync + 'a,
+        F: Fn(&T) -> SignalSync<'a, U> + Send + Sync + 'a,
     {
-        let new_signal = SignalSync::new(U::default());
-        let result_new_signal = new_signal.clone();
-        let source_weak = Arc::downgrade(&self.0);
-        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let inner = f(&self.0.value.lock().unwrap());
+        let new_signal = SignalSync::new(inner.0.value.lock().unwrap().clone());
+        let mut current = None;
+        SignalSync::switch_inner(&mut current, &new_signal, inner);
+        let subscription = Mutex::new(current);
 
-        // Forward reaction: T -> U (covariant)
-        let source_inner = source_weak.clone();
-        let new_signal_rc = new_signal_weak.clone();
-        let forward_react_fn = Box::new(move || {
-            if let Some(new_sig) = new_signal_rc.upgrade() {
-                if !new_sig.explicitly_modified.load(Ordering::Acquire) {
-                    if let Some(source) = source_inner.upgrade() {
-                        let t_value = source.value.lock().unwrap();
-                        let u_value = f(&t_value);
-                        drop(t_value);
-                        *new_sig.value.lock().unwrap() = u_value;
-                    }
-                }
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let inner = f(&src.value.lock().unwrap());
+                SignalSync::switch_inner(
+                    &mut subscription.lock().unwrap(),
+                    &SignalSync(new_sig),
+                    inner,
+                );
             }
         });
+        self.push_derived(react_fn, &new_signal);
 
-        self.0.react_fns.write().unwrap().push(forward_react_fn);
-        self.0
-            .successors
-            .write()
-            .unwrap()
-            .push(WeakSignalRefSync::new(&new_signal));
+        new_signal
+    }
 
-        // Backward reaction: U -> T (contravariant)
-        let new_signal_rc_back = new_signal_weak.clone();
-        let source_inner_back = source_weak.clone();
+    /// Create a signal holding the previous and the current value of the current signal
+    ///
+    /// The new signal holds `(previous, current)` and is updated on each change of the current
+    /// signal, which makes it easy to react to transitions. It starts with the current value in
+    /// both slots, so the first change is reported as a transition from the initial value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let position = SignalSync::new(0);
+    /// let moves = position.pairwise();
+    /// let _observer = moves.map(|(from, to)| println!("moved from {} to {}", from, to));
+    /// position.send(3); // prints "moved from 0 to 3"
+    /// position.send(5); // prints "moved from 3 to 5"
+    /// ```
+    pub fn pairwise(&self) -> SignalSync<'a, (T, T)>
+    where
+        T: Clone,
+    {
+        let initial = self.0.value.lock().unwrap().clone();
+        let new_signal = SignalSync::new((initial.clone(), initial));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
 
-        let backward_react_fn = Box::new(move || {
-            if let Some(new_sig) = new_signal_rc_back.upgrade() {
-                if new_sig.explicitly_modified.load(Ordering::Acquire) {
-                    let u_value_ref = new_sig.value.lock().unwrap();
-                    let t_value = g(&u_value_ref);
-                    drop(u_value_ref);
-
-                    if let Some(source) = source_inner_back.upgrade() {
-                        *source.value.lock().unwrap() = t_value;
-                        source.explicitly_modified.store(true, Ordering::Release);
-                    }
-                }
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let current = src.value.lock().unwrap().clone();
+                let mut pair = new_sig.value.lock().unwrap();
+                pair.0 = std::mem::replace(&mut pair.1, current);
             }
         });
-        new_signal
-            .0
-            .react_fns
-            .write()
-            .unwrap()
-            .push(backward_react_fn);
+        self.push_derived(react_fn, &new_signal);
 
         new_signal
-            .0
-            .predecessors
-            .write()
-            .unwrap()
-            .push(WeakSignalRefSync::new(self));
-
-        result_new_signal
     }
 
-    /// Combine two signals into one
+    /// Create a signal collecting every value of the current signal
     ///
-    /// This combines two signals into a new signal that holds a tuple of their values.
-    /// Changes to either signal will propagate to the new combined signal.
+    /// The new signal holds a vector that starts with the current value, and each change of the
+    /// current signal pushes the new value onto it. The vector grows without bound; see
+    /// `accumulate_bounded` to keep only the most recent values.
     ///
     /// # Example
     /// ```rust
-    /// let a = SignalSync::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// let ab = a.combine(&b);
-    /// ab.with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let history = a.accumulate();
+    /// a.send(2);
+    /// a.send(3);
+    /// assert_eq!(history.get(), vec![1, 2, 3]);
     /// ```
-    pub fn combine<S>(&self, another: S) -> SignalSync<'a, (T, S::Inner)>
+    pub fn accumulate(&self) -> SignalSync<'a, Vec<T>>
     where
-        S: LiftableSync<'a>,
-        S::Inner: Send + Sync + 'a,
-        T: Send + Sync,
+        T: Clone,
     {
-        self.combine_ref::<S, WeakRefStrategySync>(another)
+        self.accumulate_with(vec![self.0.value.lock().unwrap().clone()], Vec::push)
     }
 
-    /// Combine two signals into one with strong references
+    /// Create a signal collecting the last `cap` values of the current signal
     ///
-    /// This combines two signals into a new signal that holds a tuple of their values.
-    /// Changes to either signal will propagate to the new combined signal.
-    /// This combination uses strong references.
+    /// This works like `accumulate`, but the values are kept in a `VecDeque`: once it holds
+    /// `cap` values, each new value pushes out the oldest one in constant time, so the queue
+    /// always holds the most recent values in order.
+    ///
+    /// # Panics
+    /// Panics if `cap` is zero.
     ///
     /// # Example
     /// ```rust
-    /// let a = SignalSync::new(10);
-    /// let b = a.map(|x| x * 2);
-    /// a.and(&b).with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let recent = a.accumulate_bounded(2);
+    /// a.send(2);
+    /// a.send(3);
+    /// assert_eq!(recent.get(), vec![2, 3]);
     /// ```
-    pub fn and<S>(&self, another: S) -> SignalSync<'a, (T, S::Inner)>
+    pub fn accumulate_bounded(&self, cap: usize) -> SignalSync<'a, VecDeque<T>>
     where
-        S: LiftableSync<'a>,
-        S::Inner: Send + Sync + 'a,
-        T: Send + Sync,
+        T: Clone,
     {
-        self.combine_ref::<S, StrongRefStrategySync>(another)
+        assert!(cap > 0, "the accumulate capacity must not be zero");
+        let initial = VecDeque::from([self.0.value.lock().unwrap().clone()]);
+        self.accumulate_with(initial, move |values, current| {
+            if values.len() == cap {
+                values.pop_front();
+            }
+            values.push_back(current);
+        })
     }
 
-    fn combine_ref<S, St: RefStrategySync<'a>>(&self, another: S) -> SignalSync<'a, (T, S::Inner)>
+    /// Create a signal from `initial`, adding each new value of the current signal with `push`
+    fn accumulate_with<C: Send + Sync + 'a>(
+        &self,
+        initial: C,
+        push: impl Fn(&mut C, T) + Send + Sync + 'a,
+    ) -> SignalSync<'a, C>
     where
-        S: LiftableSync<'a>,
-        S::Inner: Send + Sync + 'a,
-        T: Send + Sync,
-        St: 'a,
+        T: Clone,
     {
-        let another = another.as_ref();
-
-        // Take values using helper - no cloning!
-        let temp_val_0 = Self::take_value(&self.0.value);
-        let temp_val_1 = Self::take_value(&another.0.value);
-        let new_signal = SignalSync::new((temp_val_0, temp_val_1));
-
-        // Restore original values by swapping back from the new signal
-        std::mem::swap(
-            &mut *self.0.value.lock().unwrap(),
-            &mut new_signal.0.value.lock().unwrap().0,
-        );
-        std::mem::swap(
-            &mut *another.0.value.lock().unwrap(),
-            &mut new_signal.0.value.lock().unwrap().1,
-        );
-
-        let result_new_signal = new_signal.clone();
-
-        let new_signal_ref = St::new_ref(&new_signal);
-        let source_self_ref = St::new_ref(self);
-
-        let react_fn_self = Box::new(move || {
-            if let Some(new_sig) = St::upgrade_ref(&new_signal_ref) {
-                if !new_sig.explicitly_modified.load(Ordering::Acquire) {
-                    if let Some(source) = St::upgrade_ref(&source_self_ref) {
-                        // Swap values instead of cloning (during reaction only)
-                        std::mem::swap(
-                            &mut *source.value.lock().unwrap(),
-                            &mut new_sig.value.lock().unwrap().0,
-                        );
-                    }
-                }
-            }
-        });
+        let new_signal = SignalSync::new(initial);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
 
-        let new_signal_ref_2 = St::new_ref(&new_signal);
-        let source_another_ref_2 = St::new_ref(another);
-        let react_fn_another = Box::new(move || {
-            if let Some(new_sig) = St::upgrade_ref(&new_signal_ref_2) {
-                if !new_sig.explicitly_modified.load(Ordering::Acquire) {
-                    if let Some(source) = St::upgrade_ref(&source_another_ref_2) {
-                        // Swap values instead of cloning (during reaction only)
-                        std::mem::swap(
-                            &mut *source.value.lock().unwrap(),
-                            &mut new_sig.value.lock().unwrap().1,
-                        );
-                    }
-                }
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let current = src.value.lock().unwrap().clone();
+                push(&mut new_sig.value.lock().unwrap(), current);
             }
         });
+        self.push_derived(react_fn, &new_signal);
 
-        self.0.react_fns.write().unwrap().push(react_fn_self);
-        another.0.react_fns.write().unwrap().push(react_fn_another);
-        self.0
-            .successors
-            .write()
-            .unwrap()
-            .push(WeakSignalRefSync::new(&result_new_signal));
-        another
-            .0
-            .successors
-            .write()
-            .unwrap()
-            .push(WeakSignalRefSync::new(&result_new_signal));
-        result_new_signal
+        new_signal
     }
 
-    /// Extend the signal with a vector of signals
+    /// Create a signal that only propagates the values differing from the last propagated one
     ///
-    /// This creates a new signal that depends on the current signal and the provided signals.
-    /// Changes to any of the source signals will propagate to the new signal.
+    /// The new signal is updated, and its observers notified, only when the new value of the
+    /// current signal is not equal to the value it holds, so sending the same value twice
+    /// does not trigger downstream reactions again. See `distinct_by` for a custom comparison.
     ///
     /// # Example
     /// ```rust
+    /// # use agility::signal_sync::SignalSync;
     /// let a = SignalSync::new(1);
-    /// let b = SignalSync::new(2);
-    /// let c = SignalSync::new(3);
-    /// let d = a.extend(vec![b, c]);
-    /// d.with(|values| println!("d changed: {:?}", values));
-    /// a.send(10); // prints "d changed: [10, 2, 3]"
-    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// let distinct = a.distinct();
+    /// let _observer = distinct.map(|x| println!("distinct: {}", x));
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints "distinct: 2"
     /// ```
-    pub fn extend<S>(&self, others: impl IntoIterator<Item = S>) -> SignalSync<'a, Vec<T>>
+    pub fn distinct(&self) -> SignalSync<'a, T>
     where
-        S: LiftableSync<'a, Inner = T>,
-        T: Send + Sync,
+        T: Clone + PartialEq,
     {
-        self.extend_ref::<S, WeakRefStrategySync>(others)
+        self.distinct_by(PartialEq::eq)
     }
 
-    /// Extend the signal with a vector of signals with strong references
+    /// Create a signal that only propagates the values differing from the last propagated one
     ///
-    /// This creates a new signal that depends on the current signal and the provided signals.
-    /// Changes to any of the source signals will propagate to the new signal.
-    /// It uses strong references.
+    /// Like `distinct`, but two values are considered the same when `eq` returns true for them.
+    /// The suppressed updates never mark the new signal or its successors dirty, so they leave
+    /// nothing pending in the batch.
     ///
     /// # Example
     /// ```rust
-    /// let a = SignalSync::new(1);
-    /// let b = SignalSync::new(2);
-    /// let c = SignalSync::new(3);
-    /// a.follow(vec![b, c]).with(|values| println!("d changed: {:?}", values));
-    /// a.send(10); // prints "d changed: [10, 2, 3]"
-    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1.0_f64);
+    /// let rounded = a.distinct_by(|x, y| x.round() == y.round());
+    /// let _observer = rounded.map(|x| println!("rounded: {}", x));
+    /// a.send(1.2); // prints nothing
+    /// a.send(2.0); // prints "rounded: 2"
     /// ```
-    pub fn follow<S>(&self, others: impl IntoIterator<Item = S>) -> SignalSync<'a, Vec<T>>
+    pub fn distinct_by<F>(&self, eq: F) -> SignalSync<'a, T>
     where
-        S: LiftableSync<'a, Inner = T>,
-        T: Send + Sync,
+        T: Clone,
+        F: Fn(&T, &T) -> bool + Send + Sync + 'a,
     {
-        self.extend_ref::<S, StrongRefStrategySync>(others)
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().clone());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            let value = src.value.lock().unwrap().clone();
+            if eq(&new_sig.value.lock().unwrap(), &value) {
+                return None;
+            }
+            *new_sig.value.lock().unwrap() = value;
+            Some(SignalSync(new_sig).guard())
+        });
+
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
     }
 
-    fn extend_ref<S, St: RefStrategySync<'a>>(
-        &self,
-        others: impl IntoIterator<Item = S>,
-    ) -> SignalSync<'a, Vec<T>>
+    /// Create a signal that only takes the values of the current signal passing a predicate
+    ///
+    /// The new signal is updated, and its observers notified, only when `pred` returns true
+    /// for the new value of the current signal; otherwise it keeps its previous value and
+    /// does not propagate. The new signal starts with the current value, whether or not it
+    /// passes `pred`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let even = a.filter(|x| x % 2 == 0);
+    /// let _observer = even.map(|x| println!("even: {}", x));
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints "even: 2"
+    /// ```
+    pub fn filter<F>(&self, pred: F) -> SignalSync<'a, T>
     where
-        S: LiftableSync<'a, Inner = T>,
-        T: Send + Sync,
-        St: 'a,
+        T: Clone,
+        F: Fn(&T) -> bool + Send + Sync + 'a,
     {
-        let others_signals: Vec<SignalSync<'a, T>> =
-            others.into_iter().map(|s| s.as_ref().clone()).collect();
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().clone());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
 
-        // Collect values using helper - no cloning!
-        let all_signals: Vec<&SignalSync<'a, T>> =
-            iter::once(self).chain(others_signals.iter()).collect();
-        let temp_values: Vec<T> = all_signals
-            .iter()
-            .map(|s| Self::take_value(&s.0.value))
-            .collect();
-        let new_signal: SignalSync<'a, Vec<T>> = SignalSync::new(temp_values);
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            let value = src.value.lock().unwrap().clone();
+            if !pred(&value) {
+                return None;
+            }
+            *new_sig.value.lock().unwrap() = value;
+            Some(SignalSync(new_sig).guard())
+        });
 
-        // Restore original values by swapping back
-        for (index, signal) in all_signals.iter().enumerate() {
-            std::mem::swap(
-                &mut *signal.0.value.lock().unwrap(),
-                &mut new_signal.0.value.lock().unwrap()[index],
-            );
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
+    }
+
+    /// Create a signal that only takes the valid values of the current signal
+    ///
+    /// The new signal holds the last known good value: a value for which `valid` returns false
+    /// is not propagated, so a single bad reading such as a `NaN` can't poison the graph
+    /// downstream. Rejected values are reported to the error sink of the graph if one is
+    /// installed, see `on_reaction_error`. The new signal starts with the current value; if that
+    /// value is invalid it is reported as well, and the guarantee starts with the first valid one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let reading = SignalSync::new(1.0_f64);
+    /// let valid = reading.validated(|x| x.is_finite());
+    /// let _observer = valid.map(|x| println!("valid: {}", x)); // prints "valid: 1"
+    /// reading.send(f64::NAN); // prints nothing
+    /// reading.send(2.0); // prints "valid: 2"
+    /// ```
+    pub fn validated<F>(&self, valid: F) -> SignalSync<'a, T>
+    where
+        T: Clone + fmt::Debug,
+        F: Fn(&T) -> bool + Send + Sync + 'a,
+    {
+        let initial = self.0.value.lock().unwrap().clone();
+        if !valid(&initial) {
+            self.report_rejected(&initial);
         }
+        let new_signal = self.new_mapped(initial);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
 
-        let result_new_signal = new_signal.clone();
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let source = SignalSync(source_weak.upgrade()?);
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            let value = source.0.value.lock().unwrap().clone();
+            if !valid(&value) {
+                source.report_rejected(&value);
+                return None;
+            }
+            *new_sig.value.lock().unwrap() = value;
+            Some(SignalSync(new_sig).guard())
+        });
 
-        iter::once(self)
-            .chain(others_signals.iter())
-            .enumerate()
-            .for_each(|(index, signal)| {
-                let new_signal_ref = St::new_ref(&new_signal);
-                let source_ref = St::new_ref(signal);
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
+    }
 
-                let react_fn = Box::new(move || {
-                    if let Some(new_sig) = St::upgrade_ref(&new_signal_ref) {
-                        if !new_sig.explicitly_modified.load(Ordering::Acquire) {
-                            if let Some(source) = St::upgrade_ref(&source_ref) {
-                                // Swap values instead of cloning (during reaction only)
-                                std::mem::swap(
-                                    &mut new_sig.value.lock().unwrap()[index],
-                                    &mut *source.value.lock().unwrap(),
-                                );
-                            }
-                        }
-                    }
-                });
+    /// Handle the errors reported by reactions of the graph
+    ///
+    /// Signals derived with `map`, `with` and `try_map` share one error sink with the signal
+    /// they are derived from, so a handler registered on any of them sees the errors reported
+    /// anywhere in that subgraph, no matter whether it was registered before or after the
+    /// derivation.
+    ///
+    /// A reaction that panics is skipped and its panic is reported here as well, so a failing
+    /// observer does not keep the rest of the graph from updating.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let input = SignalSync::new(String::from("1"));
+    /// let parsed = input.try_map(|s| s.parse::<i32>());
+    /// input.on_reaction_error(|e| println!("{}", e));
+    /// input.send(String::from("x")); // prints "reaction error: invalid digit found in string"
+    /// ```
+    pub fn on_reaction_error<F>(&self, f: F)
+    where
+        F: Fn(&ReactionError) + Send + Sync + 'a,
+    {
+        self.error_sink().subscribe(Box::new(f));
+    }
 
-                signal.0.react_fns.write().unwrap().push(react_fn);
-                signal
-                    .0
-                    .successors
-                    .write()
-                    .unwrap()
-                    .push(WeakSignalRefSync::new(&new_signal));
-            });
+    /// Map the signal with a fallible function
+    ///
+    /// This works like `map`, but the new signal holds the `Result` of `f`. Every `Err` is
+    /// passed downstream as a value and also reported to the error sink of the graph, see
+    /// `on_reaction_error`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let input = SignalSync::new(String::from("1"));
+    /// let parsed = input.try_map(|s| s.parse::<i32>());
+    /// let _observer = parsed.map(|r| println!("parsed: {:?}", r));
+    /// input.send(String::from("2")); // prints "parsed: Ok(2)"
+    /// ```
+    pub fn try_map<U: Send + Sync + 'a, E, F>(&self, f: F) -> SignalSync<'a, Result<U, E>>
+    where
+        E: fmt::Display + Send + Sync + 'a,
+        F: Fn(&T) -> Result<U, E> + Send + Sync + 'a,
+    {
+        let sink = self.error_sink();
+        self.map(move |value| {
+            let result = f(value);
+            if let Err(e) = &result {
+                sink.report(&ReactionError::new(e.to_string()));
+            }
+            result
+        })
+    }
 
-        result_new_signal
+    /// Send a new value to the signal
+    ///
+    /// This will replace the current value of the signal with the new value.
+    /// The signals that depend on this signal will be notified and updated accordingly.
+    ///
+    /// It returns a `SignalGuardSync` that ensures reactions are processed when dropped and
+    /// prevents premature reactions during multiple sends. (Batch updates)
+    ///
+    /// A send made by a reaction reacts right away, within the current propagation. For a
+    /// `'static` signal, `SignalGuardSync::defer` holds its reactions until the propagation has settled.
+    ///
+    /// Signals react in topological order: a signal only reacts once every signal it depends on
+    /// has settled, so observers never see a mix of updated and stale inputs (no glitches),
+    /// however uneven the paths from the signal that changed.
+    ///
+    /// Each signal reacts at most once per propagation. When signals depend on each other in a
+    /// cycle, propagation stops at the first signal it would enter a second time, so the cycle
+    /// settles after one pass instead of recursing forever.
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let signal = SignalSync::new(0);
+    /// signal.send(42); // sets the signal's value to 42
+    ///
+    /// signal.with(|v| println!("Signal value: {}", v));
+    /// (signal.send(66), signal.send(100));
+    /// // sets the signal's value to 100 and prints "Signal value: 100" only once
+    /// ```
+    pub fn send(&self, new_value: T) -> SignalGuardSync<'a> {
+        self.modify(|v| *v = new_value);
+        self.0.explicitly_modified.store(true, Ordering::Release);
+        self.react_eager();
+        self.guard()
     }
 
-    /// Let this signal depend on another signal
+    /// Send a modification to the signal
     ///
-    /// This synchronizes the value of this signal with the value of the dependency signal.
-    /// Whenever the dependency signal changes, this signal will be updated to match its value.
+    /// This will apply the provided function to modify the current value of the signal.
+    /// The signals that depend on this signal will be notified and updated accordingly.
     ///
-    /// The returned signal is exactly the argument `dependency`. You can break the dependency chain
-    /// by dropping the returned signal if `dependency` is weakly referenced.
+    /// It returns a `SignalGuardSync` that ensures reactions are processed when dropped and
+    /// prevents premature reactions during multiple sends. (Batch updates)
+    pub fn send_with<F>(&self, f: F) -> SignalGuardSync<'a>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.modify(f);
+        self.react_eager();
+        self.guard()
+    }
+
+    /// Send a new value to the signal, returning the previous one
+    ///
+    /// This works like `send`, including the returned guard, but hands back the value it
+    /// replaced, to diff it against the new one or to reuse its allocation.
     ///
     /// # Example
     /// ```rust
+    /// # use agility::signal_sync::SignalSync;
     /// let a = SignalSync::new(1);
-    /// let mut b = SignalSync::new(2);
-    /// b = a.depend(b);
-    /// a.with(|v| println!("a changed: {}", v));
-    /// b.send(3); // prints "a changed: 3"
+    /// let (previous, _guard) = a.replace(2);
+    /// assert_eq!(previous, 1);
     /// ```
+    pub fn replace(&self, new_value: T) -> (T, SignalGuardSync<'a>) {
+        let previous = std::mem::replace(&mut *self.0.value.lock().unwrap(), new_value);
+        self.0.explicitly_modified.store(true, Ordering::Release);
+        self.react_eager();
+        (previous, self.guard())
+    }
+
+    /// Take the value out of the signal, leaving the default value in its place
     ///
-    /// The example above is analogous to:
+    /// This is `replace` with `T::default()`: the signals that depend on this signal are updated
+    /// with the default value.
+    ///
+    /// # Example
     /// ```rust
-    /// let a = SignalSync::new(1);
-    /// let b = a.map(|v| *v);
-    /// b.with(|v| println!("b changed: {}", v));
-    /// a.send(3); // prints "b changed: 3"
+    /// # use agility::signal_sync::SignalSync;
+    /// let log = SignalSync::new(vec![String::from("started")]);
+    /// let (lines, _guard) = log.take();
+    /// assert_eq!(lines, vec![String::from("started")]);
+    /// assert!(log.get().is_empty());
     /// ```
-    pub fn depend(&self, dependency: SignalSync<'a, T>) -> SignalSync<'a, T>
+    pub fn take(&self) -> (T, SignalGuardSync<'a>)
     where
-        T: Default + Send + Sync,
+        T: Default,
     {
-        let self_weak = Arc::downgrade(&self.0);
-        let dependency_weak = Arc::downgrade(&dependency.0);
+        self.replace(T::default())
+    }
+
+    /// Send a new value to the signal, unless its value is locked
+    ///
+    /// This works like `send`, but fails right away instead of blocking when the value is
+    /// locked by another thread or a reaction in progress, handing `new_value` back in the error.
+    /// Only acquiring the value is non-blocking: once the value is written the send is committed,
+    /// and its reactions run as for `send` when the guard drops, blocking on the locks of the
+    /// signals downstream if they have to. A poisoned lock panics, like `send`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// a.peek(|_| assert!(a.try_send(1).is_err())); // the value is locked by `peek`
+    /// assert!(a.try_send(2).is_ok());
+    /// assert_eq!(a.get(), 2);
+    /// ```
+    pub fn try_send(&self, new_value: T) -> Result<SignalGuardSync<'a>, TrySendError<T>> {
+        match self.lock_value_until(None) {
+            Some(value) => Ok(self.send_locked(value, new_value)),
+            None => Err(TrySendError(new_value)),
+        }
+    }
+
+    /// Send a new value to the signal, giving up if its value stays locked for `timeout`
+    ///
+    /// This works like `try_send`, but keeps retrying to acquire the value until `timeout` has
+    /// elapsed. As with `try_send`, the timeout only bounds the wait for the value of this signal,
+    /// not for the signals its reactions update.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// # use std::time::Duration;
+    /// let a = SignalSync::new(0);
+    /// a.send_timeout(1, Duration::from_millis(10)).unwrap();
+    /// assert_eq!(a.get(), 1);
+    /// ```
+    pub fn send_timeout(
+        &self,
+        new_value: T,
+        timeout: Duration,
+    ) -> Result<SignalGuardSync<'a>, TrySendError<T>> {
+        match self.lock_value_until(Some(Instant::now() + timeout)) {
+            Some(value) => Ok(self.send_locked(value, new_value)),
+            None => Err(TrySendError(new_value)),
+        }
+    }
+
+    /// Freeze the signal, holding back its reactions until the returned guard is dropped
+    ///
+    /// While frozen, the value of the signal still changes, but the signal does not react: its
+    /// observers do not run and the signals derived from it are not updated. When the guard is
+    /// dropped, the signal reacts once with its latest value if it was sent to or updated in the
+    /// meantime, however many times. Unlike `batch`, which groups the sends made in one place,
+    /// this holds back a single signal across any number of unrelated send sites. Freezes nest,
+    /// the signal reacts when the last guard is dropped.
+    /// A send to a frozen signal with predecessors, like the target of `promap`, is not written
+    /// back to them either until the signal thaws.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let _observer = a.map(|x| println!("a changed: {}", x));
+    /// let frozen = a.freeze();
+    /// a.send(1); // prints nothing
+    /// a.send(2); // prints nothing
+    /// drop(frozen); // prints "a changed: 2"
+    /// ```
+    pub fn freeze(&self) -> FreezeGuardSync<'a> {
+        self.0.frozen.fetch_add(1, Ordering::AcqRel);
+        let signal = self.clone();
+        FreezeGuardSync {
+            thaw: Some(Box::new(move || {
+                if signal.0.frozen.fetch_sub(1, Ordering::AcqRel) == 1
+                    && signal.0.missed_while_frozen.swap(false, Ordering::AcqRel)
+                {
+                    drop(signal.thaw_guard());
+                }
+            })),
+        }
+    }
+
+    /// Map the signal to a new signal
+    ///
+    /// This creates a new signal that depends on the current signal.
+    /// Changes to the source signal will propagate to the new signal.
+    /// `f` runs once right away to compute the initial value; see `map_lazy` to defer it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// let _observer = b.map(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "b changed: 10"
+    /// ```
+    pub fn map<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        self.map_ref::<U, F, WeakRefStrategySync>(f)
+    }
+
+    /// Map the signal to a new signal with strong references
+    ///
+    /// This creates a new signal that depends on the current signal.
+    /// Changes to the source signal will propagate to the new signal.
+    /// This mapping uses strong references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// b.with(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "b changed: 10"
+    /// ```
+    pub fn with<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        self.map_ref::<U, F, StrongRefStrategySync>(f)
+    }
+
+    /// Map the signal to a new signal, passing the value by copy
+    ///
+    /// This is a specialization of `map` for `Copy` values: the value is copied out of the signal
+    /// and the borrow is released before `f` runs, instead of keeping the source borrowed
+    /// for the duration of the call. As a consequence `f` may freely read the source signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.map_copied(|x| x * 2);
+    /// let _observer = b.map_copied(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "b changed: 10"
+    /// ```
+    pub fn map_copied<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        T: Copy,
+        F: Fn(T) -> U + Send + Sync + 'a,
+    {
+        let initial = *self.0.value.lock().unwrap();
+        let new_signal = self.new_mapped(f(initial));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = *src.value.lock().unwrap();
+                let new_value = f(value);
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, passing `f` an owned clone of the value
+    ///
+    /// This works like `map_copied` for `Clone` values: the value is cloned out of the signal and
+    /// the lock is released before `f` takes ownership of the clone, so closures that consume their
+    /// input need no `clone` of their own.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(String::from("agility"));
+    /// let b = a.map_cloned(|s| s.into_bytes());
+    /// a.send(String::from("signal"));
+    /// assert_eq!(b.get(), b"signal");
+    /// ```
+    pub fn map_cloned<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        T: Clone,
+        F: Fn(T) -> U + Send + Sync + 'a,
+    {
+        let initial = self.0.value.lock().unwrap().clone();
+        let new_signal = self.new_mapped(f(initial));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.lock().unwrap().clone();
+                let new_value = f(value);
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal into a value updated in place
+    ///
+    /// This works like `map`, but instead of building a new value on each change, `f` updates
+    /// the value of the new signal through a mutable reference, so a buffer keeps its allocation
+    /// across updates. The new signal starts with `init`, which `f` updates right away with the
+    /// current value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let samples = SignalSync::new(vec![1u8, 2, 3]);
+    /// let scaled = samples.map_into(Vec::with_capacity(1024), |input, output: &mut Vec<u16>| {
+    ///     output.clear();
+    ///     output.extend(input.iter().map(|&x| x as u16 * 256));
+    /// });
+    /// assert_eq!(scaled.get(), vec![256, 512, 768]);
+    /// ```
+    pub fn map_into<U: Send + Sync + 'a, F>(&self, mut init: U, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&T, &mut U) + Send + Sync + 'a,
+    {
+        f(&self.0.value.lock().unwrap(), &mut init);
+        let new_signal = self.new_mapped(init);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                f(
+                    &src.value.lock().unwrap(),
+                    &mut new_sig.value.lock().unwrap(),
+                );
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, deferring `f` to the first change
+    ///
+    /// Unlike `map`, which runs `f` on the current value to seed the new signal, this does not
+    /// run `f` at construction: the new signal holds `None` until the current signal first
+    /// changes, then `Some` of `f` applied to each new value. This suits an `f` with side effects
+    /// or a current value that is only a placeholder.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.map_lazy(|x| x * 2);
+    /// assert_eq!(b.get(), None);
+    /// a.send(5);
+    /// assert_eq!(b.get(), Some(10));
+    /// ```
+    pub fn map_lazy<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, Option<U>>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        let new_signal = self.new_mapped(None);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let new_value = f(&src.value.lock().unwrap());
+                *new_sig.value.lock().unwrap() = Some(new_value);
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, skipping `f` when the value did not actually change
+    ///
+    /// This works like `map`, but keeps the last value `f` was applied to and only reruns `f`
+    /// when the new value of the current signal differs from it, which saves an expensive `f`
+    /// when the signal propagates without a real change, like a send of the same value.
+    /// Where `dedupe` compares the outputs, this compares the inputs; observers of the new
+    /// signal are still notified.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(2);
+    /// let squared = a.map_memo(|x| {
+    ///     println!("computing");
+    ///     x * x
+    /// }); // prints "computing"
+    /// a.send(2); // prints nothing
+    /// a.send(3); // prints "computing"
+    /// ```
+    pub fn map_memo<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        T: Clone + PartialEq,
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        let initial = self.0.value.lock().unwrap().clone();
+        let new_signal = self.new_mapped(f(&initial));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let last_input = Mutex::new(initial);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.lock().unwrap().clone();
+                let mut last_input = last_input.lock().unwrap();
+                if *last_input == value {
+                    return;
+                }
+                let new_value = f(&value);
+                *last_input = value;
+                drop(last_input);
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal, forwarding the guard of a send made by the mapping
+    ///
+    /// This works like `map`, except that `f` also returns the guard of a `send` it makes to
+    /// another signal. Instead of settling on its own while the current batch is still
+    /// propagating, the forwarded guard joins the current batch, so the dirtiness of the
+    /// target is merged into the ongoing propagation and its observers run once, after the
+    /// reactions of the current signal. The guard returned at creation is dropped immediately.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let b = SignalSync::new(0);
+    /// let target = b.clone();
+    /// let _forward = a.map_forward(move |x| ((), Some(target.send(x * 10))));
+    /// let _observer_b = b.map(|x| println!("b changed: {}", x));
+    /// let _observer_a = a.map(|x| println!("a changed: {}", x));
+    /// a.send(1); // prints "a changed: 1" and then "b changed: 10"
+    /// ```
+    pub fn map_forward<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> (U, Option<SignalGuardSync<'a>>) + Send + Sync + 'a,
+    {
+        let (initial, _guard) = f(&self.0.value.lock().unwrap());
+        let new_signal = self.new_mapped(initial);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let (new_value, guard) = f(&src.value.lock().unwrap());
+                *new_sig.value.lock().unwrap() = new_value;
+                guard
+            } else {
+                None
+            }
+        });
+
+        self.0
+            .react_fns
+            .write()
+            .unwrap()
+            .push_forwarding_owned(react_fn, WeakSignalRefSync::new(&new_signal));
+        self.0
+            .successors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::new(&new_signal));
+
+        new_signal
+    }
+
+    /// Record every change of the signal into an append-only event log
+    ///
+    /// This creates a new signal holding `(epoch, value)` pairs, where the initial value is
+    /// recorded with epoch `0` and every settled change of the current signal appends the next
+    /// epoch. The log is a signal itself, so other parts of the graph can observe it.
+    /// See `event_log_bounded` to limit the number of retained events.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new("idle");
+    /// let log = a.event_log();
+    /// a.send("running");
+    /// a.send("done");
+    /// log.with(|events| println!("{:?}", events));
+    /// // prints "[(0, "idle"), (1, "running"), (2, "done")]"
+    /// ```
+    pub fn event_log(&self) -> SignalSync<'a, Vec<(u64, T)>>
+    where
+        T: Clone,
+    {
+        self.event_log_ref(None)
+    }
+
+    /// Record every change of the signal into an event log keeping the last `cap` events
+    ///
+    /// This is the same as `event_log`, but older events are discarded once more than `cap`
+    /// events have been recorded. Epochs keep counting, so gaps reveal discarded events.
+    pub fn event_log_bounded(&self, cap: usize) -> SignalSync<'a, Vec<(u64, T)>>
+    where
+        T: Clone,
+    {
+        self.event_log_ref(Some(cap))
+    }
+
+    fn event_log_ref(&self, cap: Option<usize>) -> SignalSync<'a, Vec<(u64, T)>>
+    where
+        T: Clone,
+    {
+        let mut initial = vec![(0, self.0.value.lock().unwrap().clone())];
+        if cap == Some(0) {
+            initial.clear();
+        }
+        let new_signal = SignalSync::new(initial);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let epoch = std::sync::atomic::AtomicU64::new(0);
+
+        let react_fn = Box::new(move || {
+            if let Some(log) = new_signal_weak.upgrade()
+                && !log.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let current = epoch.fetch_add(1, Ordering::SeqCst) + 1;
+                let value = src.value.lock().unwrap().clone();
+                let mut events = log.value.lock().unwrap();
+                events.push((current, value));
+                if let Some(cap) = cap
+                    && events.len() > cap
+                {
+                    let excess = events.len() - cap;
+                    events.drain(..excess);
+                }
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Map the signal to a new signal that reacts to every send
+    ///
+    /// Unlike `map`, which runs once per batch with the final value, the closure of an eager
+    /// map runs immediately on every `send`/`send_with` to the current signal, so it observes
+    /// all intermediate values of a batch. When the current signal is updated by propagation
+    /// instead of a direct send, the closure runs once when the batch settles.
+    /// Successors of the new signal are still batched as usual.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let _eager = a.map_eager(|x| println!("eager: {}", x));
+    /// let _batched = a.map(|x| println!("batched: {}", x));
+    /// (a.send(1), a.send(2));
+    /// // prints "eager: 1", "eager: 2" and then "batched: 2"
+    /// ```
+    pub fn map_eager<U: Send + Sync + 'a, F>(&self, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        let new_signal = self.new_mapped(f(&self.0.value.lock().unwrap()));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let eager_react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let new_value = f(&src.value.lock().unwrap());
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        });
+
+        self.0
+            .eager_react_fns
+            .write()
+            .unwrap()
+            .push_owned(eager_react_fn, WeakSignalRefSync::new(&new_signal));
+        self.0
+            .successors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::new(&new_signal));
+
+        new_signal
+    }
+
+    /// Observe the signal without keeping it alive
+    ///
+    /// This registers `f` to run with the new value whenever the signal changes, without
+    /// creating a derived signal. The returned `WeakObserverSync` holds only a weak reference to the
+    /// signal, and dropping it removes the reaction from the signal, so nothing is left behind.
+    /// The observer does not run at registration, only on subsequent changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let observer = a.observe_weak(|x| println!("a changed: {}", x));
+    /// a.send(1); // prints "a changed: 1"
+    /// drop(observer);
+    /// a.send(2); // prints nothing
+    /// ```
+    pub fn observe_weak<F>(&self, f: F) -> WeakObserverSync<'a>
+    where
+        F: Fn(&T) + Send + Sync + 'a,
+    {
+        WeakObserverSync {
+            detach: Some(self.observe_detachable(f)),
+        }
+    }
+
+    /// Subscribe to the changes of the signal
+    ///
+    /// This registers `f` to run with the new value whenever the signal changes and returns a
+    /// `SubscriptionSync` handle. Unlike keeping a derived signal from `map` alive, the lifetime of the
+    /// side effect is explicit: dropping the handle removes the reaction from the signal. The
+    /// handle does not keep the signal alive, and `f` does not run at subscription time.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let subscription = a.subscribe(|x| println!("a changed: {}", x));
+    /// a.send(1); // prints "a changed: 1"
+    /// drop(subscription);
+    /// a.send(2); // prints nothing
+    /// ```
+    pub fn subscribe<F>(&self, f: F) -> SubscriptionSync<'a>
+    where
+        F: Fn(&T) + Send + Sync + 'a,
+    {
+        SubscriptionSync {
+            detach: Some(self.observe_detachable(f)),
+        }
+    }
+
+    /// Subscribe to the signal, running `f` with the current value right away
+    ///
+    /// Like `subscribe`, but `f` also runs once with the current value at subscription time,
+    /// for observers that need the initial state as well as the changes. The value stays locked from the
+    /// first call until `f` is registered, so a concurrent send is either seen by the first call
+    /// or by the subscription, never missed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let _subscription = a.subscribe_now(|x| println!("a is {}", x)); // prints "a is 0"
+    /// a.send(1); // prints "a is 1"
+    /// ```
+    pub fn subscribe_now<F>(&self, f: F) -> SubscriptionSync<'a>
+    where
+        F: Fn(&T) + Send + Sync + 'a,
+    {
+        // Hold the lock until the reaction is registered, so no send can slip in between
+        let value = self.0.value.lock().unwrap();
+        f(&value);
+        let detach = self.observe_detachable(f);
+        drop(value);
+        SubscriptionSync {
+            detach: Some(detach),
+        }
+    }
+
+    /// Run a side effect on every change of the signal
+    ///
+    /// `f` runs with each new value until the returned `EffectSync` is dropped, without creating a
+    /// result signal to keep alive as `map` would. `f` may return a cleanup closure, such as
+    /// closing a connection it opened: the cleanup of a run is called right before the next run,
+    /// and the last one when the effect is dropped. Returning `()` means there is nothing to
+    /// clean up. Like `subscribe`, the effect does not run on the current value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let effect = a.effect(|x| {
+    ///     println!("open {}", x);
+    ///     let x = *x;
+    ///     move || println!("close {}", x)
+    /// });
+    /// a.send(1); // prints "open 1"
+    /// a.send(2); // prints "close 1" and then "open 2"
+    /// drop(effect); // prints "close 2"
+    /// a.send(3); // prints nothing
+    /// ```
+    pub fn effect<F, C>(&self, f: F) -> EffectSync<'a>
+    where
+        F: Fn(&T) -> C + Send + Sync + 'a,
+        C: EffectCleanupSync<'a>,
+    {
+        let cleanup = Arc::new(Mutex::new(None::<CleanupSync<'a>>));
+        let slot = cleanup.clone();
+        let subscription = self.subscribe(move |value| {
+            let previous = slot.lock().unwrap().take();
+            if let Some(previous) = previous {
+                previous();
+            }
+            let next = f(value).into_cleanup();
+            *slot.lock().unwrap() = next;
+        });
+        EffectSync {
+            subscription: Some(subscription),
+            cleanup,
+        }
+    }
+
+    /// Iterate over the values of the signal, blocking until each change
+    ///
+    /// Every change from the call onwards is queued, so a consumer thread can run a plain
+    /// `for value in signal.watch()` loop without missing values it is slow to take. The iterator
+    /// ends once the signal is dropped, and dropping the iterator unsubscribes it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let values = a.watch();
+    /// let consumer = std::thread::spawn(move || values.collect::<Vec<_>>());
+    /// a.send(1);
+    /// a.send(2);
+    /// drop(a);
+    /// assert_eq!(consumer.join().unwrap(), vec![1, 2]);
+    /// ```
+    pub fn watch(&self) -> impl Iterator<Item = T> + use<'a, T>
+    where
+        T: Clone,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let subscription = self.subscribe(move |value: &T| {
+            // The iterator may have been dropped already, which is not an error
+            let _ = sender.send(value.clone());
+        });
+        Watch {
+            receiver,
+            _subscription: subscription,
+        }
+    }
+
+    /// Subscribe to the signal with an async callback, spawned on a tokio runtime
+    ///
+    /// On every change, `f` is called with a clone of the new value and the future it returns
+    /// is spawned on the runtime behind `handle`, so slow work like a network call or a database
+    /// write never blocks the thread propagating the change. Only creating the future runs
+    /// inside the reaction, which stays fast as long as `f` does its work in the future itself.
+    /// The spawned tasks run concurrently and may complete out of order, so a task for an older
+    /// value can finish after the task for a newer one. Dropping the returned subscription stops
+    /// further spawns but does not cancel the tasks already running. Requires the `tokio` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let a = SignalSync::new(0);
+    /// let _subscription = a.subscribe_spawn(runtime.handle().clone(), |x| async move {
+    ///     println!("saving {}", x);
+    /// });
+    /// a.send(1); // prints "saving 1" once the runtime runs the task
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn subscribe_spawn<F, Fut>(
+        &self,
+        handle: tokio::runtime::Handle,
+        f: F,
+    ) -> SubscriptionSync<'a>
+    where
+        T: Clone,
+        F: Fn(T) -> Fut + Send + Sync + 'a,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.subscribe(move |value: &T| {
+            handle.spawn(f(value.clone()));
+        })
+    }
+
+    /// Register `f` as a reaction, returning the closure that removes it again
+    fn observe_detachable<F>(&self, f: F) -> Box<dyn FnOnce() + Send + Sync + 'a>
+    where
+        F: Fn(&T) + Send + Sync + 'a,
+    {
+        let source_weak = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(src) = source_weak.upgrade() {
+                f(&src.value.lock().unwrap());
+            }
+        });
+        let id = self.0.react_fns.write().unwrap().push(react_fn);
+
+        let source_weak = Arc::downgrade(&self.0);
+        Box::new(move || {
+            if let Some(src) = source_weak.upgrade() {
+                src.react_fns.write().unwrap().remove(id);
+            }
+        })
+    }
+
+    /// Run initialization once, on the first value the signal receives
+    ///
+    /// This registers `f` to run with the value of the first `send` or propagation reaching the
+    /// signal after registration, then removes the reaction so it never runs again. The current
+    /// value is treated as a placeholder and is never passed to `f`. This is useful for lazy
+    /// setup, like opening a connection when the configuration first arrives.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let config = SignalSync::new(String::new());
+    /// config.on_first(|url| println!("connecting to {}", url));
+    /// config.send(String::from("db://primary")); // prints "connecting to db://primary"
+    /// config.send(String::from("db://replica")); // prints nothing
+    /// ```
+    pub fn on_first(&self, f: impl FnOnce(&T) + Send + 'a) {
+        let source_weak = Arc::downgrade(&self.0);
+        let f = Mutex::new(Some(f));
+        let id = Arc::new(Mutex::new(None));
+        let reaction_id = id.clone();
+        let react_fn = Box::new(move || {
+            let Some(src) = source_weak.upgrade() else {
+                return;
+            };
+            if let Some(id) = reaction_id.lock().unwrap().take() {
+                src.react_fns.write().unwrap().remove(id);
+            }
+            if let Some(f) = f.lock().unwrap().take() {
+                f(&src.value.lock().unwrap());
+            }
+        });
+        let reaction = self.0.react_fns.write().unwrap().push(react_fn);
+        *id.lock().unwrap() = Some(reaction);
+    }
+
+    /// Observe the signal for as long as an owner is alive
+    ///
+    /// This registers `f` to run with the new value whenever the signal changes, like
+    /// `observe_weak`, but ties the observer to `owner` instead of a returned handle. The
+    /// observer only holds a weak reference to the owner: once the owner is dropped, `f` no longer
+    /// runs and the reaction removes itself on the next change. This gives "the observer dies
+    /// with its component" semantics without keeping a handle or unsubscribing manually.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// # use std::sync::Arc;
+    /// let value = SignalSync::new(0);
+    /// let widget = Arc::new(String::from("label"));
+    /// value.bind_to_owner(&widget, |x| println!("render: {}", x));
+    /// value.send(1); // prints "render: 1"
+    /// drop(widget);
+    /// value.send(2); // prints nothing
+    /// ```
+    pub fn bind_to_owner<O: Send + Sync + 'a>(
+        &self,
+        owner: &Arc<O>,
+        f: impl Fn(&T) + Send + Sync + 'a,
+    ) {
+        let owner_weak = Arc::downgrade(owner);
+        let source_weak = Arc::downgrade(&self.0);
+        let id = Arc::new(Mutex::new(None));
+        let reaction_id = id.clone();
+        let react_fn = Box::new(move || {
+            let Some(src) = source_weak.upgrade() else {
+                return;
+            };
+            if owner_weak.strong_count() > 0 {
+                f(&src.value.lock().unwrap());
+            } else if let Some(id) = reaction_id.lock().unwrap().take() {
+                src.react_fns.write().unwrap().remove(id);
+            }
+        });
+        let reaction = self.0.react_fns.write().unwrap().push(react_fn);
+        *id.lock().unwrap() = Some(reaction);
+    }
+
+    /// Remove the edges and reactions left behind by dropped signals
+    ///
+    /// Dead successors and predecessors, along with the reactions that fed derived signals which
+    /// have since been dropped, are normally only cleaned up lazily, the next time the signal
+    /// propagates. This removes them right away, so a long-lived source that rarely changes
+    /// does not accumulate stale entries.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let source = SignalSync::new(1);
+    /// for i in 0..100 {
+    ///     let _short_lived = source.map(move |x| x + i);
+    /// }
+    /// source.prune_dead(); // the reactions of the 100 mapped signals are gone
+    /// ```
+    pub fn prune_dead(&self) {
+        self.0.react_fns.write().unwrap().retain_alive();
+        self.0.eager_react_fns.write().unwrap().retain_alive();
+        self.0.successors.write().unwrap().retain(|s| s.is_alive());
+        self.0
+            .predecessors
+            .write()
+            .unwrap()
+            .retain(|s| s.is_alive());
+    }
+
+    /// Get the number of signals registered as derived from this signal
+    ///
+    /// This counts the successor edges as they are, including the ones left by dropped signals
+    /// that have not been pruned yet; see `live_successor_count`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = a.promap(|x| x + 1, |y| y - 1);
+    /// assert_eq!(a.successor_count(), 1);
+    /// assert_eq!(b.predecessor_count(), 1);
+    /// ```
+    pub fn successor_count(&self) -> usize {
+        self.0.successors.read().unwrap().len()
+    }
+
+    /// Get the number of signals this signal writes back to
+    ///
+    /// Only the derivations that propagate changes backwards, like `promap` and `contramap`,
+    /// register their source as a predecessor; `map` does not. Like `successor_count`, this
+    /// includes the edges to dropped signals not pruned yet.
+    pub fn predecessor_count(&self) -> usize {
+        self.0.predecessors.read().unwrap().len()
+    }
+
+    /// Get the number of live signals derived from this signal
+    ///
+    /// This runs `prune_dead` first, so the edges and reactions left by dropped signals are
+    /// removed and only the successors still alive are counted.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = a.map(|x| x + 1);
+    /// drop(b);
+    /// assert_eq!(a.successor_count(), 1);
+    /// assert_eq!(a.live_successor_count(), 0);
+    /// ```
+    pub fn live_successor_count(&self) -> usize {
+        self.prune_dead();
+        self.successor_count()
+    }
+
+    /// Render the graph reachable from the signal in Graphviz DOT format
+    ///
+    /// This walks the signals derived from this one, and the signals they write back to, and
+    /// emits a node for each with an edge for each dependency; write-back edges, like the ones
+    /// of `promap`, are dashed. Nodes are numbered in the order they are reached, starting with
+    /// this signal at `#0`, and show the label of the signal if it has one, as well as whether
+    /// it is dirty or explicitly modified. Each signal is visited once, so cycles are rendered
+    /// without looping. The graph is not frozen while it is walked, so concurrent changes to
+    /// the wiring may or may not show up.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1).with_label("a");
+    /// let _b = a.map(|x| x * 2);
+    /// println!("{}", a.to_dot()); // pipe into `dot -Tsvg` to draw the graph
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::default();
+        self.write_dot(&mut graph);
+        graph.finish()
+    }
+
+    /// Detach everything downstream of the signal
+    ///
+    /// This removes every reaction and successor registered on the signal, so signals derived
+    /// from it and observers of it stop being updated. Signals it is derived from are not
+    /// affected. This is useful to tear down a set of bindings at once, or to reset a graph
+    /// between test cases.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let source = SignalSync::new(1);
+    /// let _observer = source.map(|x| println!("source changed: {}", x));
+    /// source.clear_observers();
+    /// source.send(2); // prints nothing
+    /// ```
+    pub fn clear_observers(&self) {
+        self.0.react_fns.write().unwrap().clear();
+        self.0.eager_react_fns.write().unwrap().clear();
+        self.0.successors.write().unwrap().clear();
+    }
+
+    /// Receive the latest value of the signal, dropping the intermediate ones
+    ///
+    /// The returned receiver holds a single slot that every change of the signal overwrites
+    /// instead of queuing, so a slow consumer, typically on another thread, always gets the
+    /// freshest value and never falls behind a fast producer. The slot starts with the current
+    /// value. Dropping the receiver removes the reaction from the signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let latest = a.latest();
+    /// a.send(1);
+    /// a.send(2);
+    /// assert_eq!(latest.recv(), Some(2));
+    /// assert_eq!(latest.try_recv(), None);
+    /// ```
+    pub fn latest(&self) -> LatestReceiver<'a, T>
+    where
+        T: Clone,
+    {
+        let slot = Arc::new(LatestSlot {
+            state: Mutex::new(LatestState {
+                value: Some(self.0.value.lock().unwrap().clone()),
+                closed: false,
+            }),
+            ready: Condvar::new(),
+        });
+        let closer = LatestCloser(slot.clone());
+        let observer = self.observe_weak(move |value| closer.0.publish(value.clone()));
+        LatestReceiver {
+            slot,
+            _observer: observer,
+        }
+    }
+
+    /// Disconnect a signal derived from this one, without dropping either
+    ///
+    /// This removes the reactions through which this signal updates `derived`, along with the
+    /// edge to it, so the graph can be rewired at runtime without leaving closures behind on a
+    /// long-lived source. `derived` keeps its last value and its own observers. It covers the
+    /// signals derived with `map`, `combine`, `filter`, `zip` and the like; returns whether
+    /// anything was removed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let source = SignalSync::new(1);
+    /// let doubled = source.map(|x| x * 2);
+    /// assert!(source.disconnect(&doubled));
+    /// source.send(5);
+    /// assert_eq!(doubled.get(), 2);
+    /// ```
+    pub fn disconnect<U: Send + Sync + 'a>(&self, derived: &SignalSync<'a, U>) -> bool {
+        let removed = self.0.react_fns.write().unwrap().remove_owned_by(derived)
+            | self
+                .0
+                .eager_react_fns
+                .write()
+                .unwrap()
+                .remove_owned_by(derived);
+        self.0
+            .successors
+            .write()
+            .unwrap()
+            .retain(|s| !s.points_to(derived));
+        removed
+    }
+
+    /// Split the signal into several independent branches
+    ///
+    /// This creates `n` new signals that each track the current signal, so independent
+    /// observer chains can be built off each branch. Every branch only clones the current
+    /// value; the reaction that produced it is run once per update no matter how many
+    /// branches or observers hang off it. Like `map`, the branches are weakly referenced.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let shared = a.map(|x| x * 10); // runs once per update of `a`
+    /// let branches = shared.tee(2);
+    /// let _first = branches[0].map(|x| println!("first: {}", x));
+    /// let _second = branches[1].map(|x| println!("second: {}", x));
+    /// a.send(2); // prints "first: 20" and "second: 20"
+    /// ```
+    pub fn tee(&self, n: usize) -> Vec<SignalSync<'a, T>>
+    where
+        T: Clone,
+    {
+        (0..n).map(|_| self.map(T::clone)).collect()
+    }
+
+    /// Sample the signal whenever a clock signal changes
+    ///
+    /// This creates a new signal that takes the current value of this signal each time `clock`
+    /// changes, and ignores changes of this signal between clock ticks, which limits the rate at
+    /// which downstream observers see updates without any timer.
+    /// A `clock_tick` signal is a natural clock for this.
+    /// If this signal did not change between two ticks, the same value is emitted again;
+    /// observers that only care about changes should dedupe it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let value = SignalSync::new(0);
+    /// let clock = SignalSync::new(());
+    /// let sampled = value.sample_when(&clock);
+    /// let _observer = sampled.map(|x| println!("sampled: {}", x));
+    /// value.send(1);
+    /// value.send(2); // prints nothing
+    /// clock.send(()); // prints "sampled: 2"
+    /// clock.send(()); // prints "sampled: 2" again
+    /// ```
+    pub fn sample_when<B: Send + Sync + 'a>(&self, clock: &SignalSync<'a, B>) -> SignalSync<'a, T>
+    where
+        T: Clone,
+    {
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().clone());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let new_value = src.value.lock().unwrap().clone();
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        });
+
+        clock.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Sample the signal whenever a trigger signal changes
+    ///
+    /// This is `sample_when` for any `Liftable` trigger, so owned and borrowed signals can be
+    /// passed alike. The new signal takes the current value of this signal each time `trigger`
+    /// changes and ignores changes of this signal in between.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let value = SignalSync::new(0);
+    /// let trigger = SignalSync::new(());
+    /// let sampled = value.sample(&trigger);
+    /// let _observer = sampled.map(|x| println!("sampled: {}", x));
+    /// value.send(1); // prints nothing
+    /// trigger.send(()); // prints "sampled: 1"
+    /// ```
+    pub fn sample<S>(&self, trigger: S) -> SignalSync<'a, T>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Send + Sync + 'a,
+        T: Clone,
+    {
+        self.sample_when(trigger.as_ref())
+    }
+
+    /// Accumulate the values of the signal, starting over whenever a reset signal changes
+    ///
+    /// This creates a new signal holding an accumulator that starts at `init`. Each change of
+    /// this signal folds the new value into the accumulator with `f`, and each change of
+    /// `reset` sets the accumulator back to `init`, whatever its value. This is useful for
+    /// "sum since the last reset" or "events since the session started". Like `map`, the new
+    /// signal is weakly referenced by both this signal and `reset`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let clicks = SignalSync::new(());
+    /// let new_session = SignalSync::new(());
+    /// let count = clicks.scan_reset(0, |n, _| n + 1, &new_session);
+    /// let _observer = count.map(|n| println!("clicks this session: {}", n));
+    /// (clicks.send(()), clicks.send(())); // prints "clicks this session: 2"
+    /// new_session.send(()); // prints "clicks this session: 0"
+    /// clicks.send(()); // prints "clicks this session: 1"
+    /// ```
+    pub fn scan_reset<U, F, B>(&self, init: U, f: F, reset: &SignalSync<'a, B>) -> SignalSync<'a, U>
+    where
+        U: Clone + Send + Sync + 'a,
+        F: Fn(&U, &T) -> U + Send + Sync + 'a,
+        B: Send + Sync + 'a,
+    {
+        let new_signal = SignalSync::new(init.clone());
+
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let next = f(&new_sig.value.lock().unwrap(), &src.value.lock().unwrap());
+                *new_sig.value.lock().unwrap() = next;
+            }
+        });
+        self.push_derived(react_fn, &new_signal);
+
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let reset_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+            {
+                *new_sig.value.lock().unwrap() = init.clone();
+            }
+        });
+        reset.push_derived(reset_fn, &new_signal);
+
+        new_signal
+    }
+
+    /// Track the signal until a condition is met, then switch to another signal for good
+    ///
+    /// This creates a new signal that mirrors the current signal until `switch_when` returns
+    /// true for its value. At that point the new signal takes the value of `next`, stops
+    /// listening to the current signal and mirrors `next` from then on. If the condition already
+    /// holds for the initial value, the new signal mirrors `next` right away. This is useful for
+    /// phase-based logic, like showing a loading value until loaded and then the real value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let loading = SignalSync::new(0);
+    /// let data = SignalSync::new(String::from("data"));
+    /// let progress = loading.map(|p| format!("loading {}%", p));
+    /// let shown = progress.then(&data, |p| p == "loading 100%");
+    /// let _observer = shown.map(|s| println!("{}", s)); // prints "loading 0%"
+    /// loading.send(50); // prints "loading 50%"
+    /// loading.send(100); // prints "data"
+    /// data.send(String::from("more data")); // prints "more data"
+    /// ```
+    pub fn then<P>(&self, next: &SignalSync<'a, T>, switch_when: P) -> SignalSync<'a, T>
+    where
+        T: Clone,
+        P: Fn(&T) -> bool + Send + Sync + 'a,
+    {
+        if switch_when(&self.0.value.lock().unwrap()) {
+            let new_signal = SignalSync::new(next.0.value.lock().unwrap().clone());
+            next.mirror_into(&new_signal);
+            return new_signal;
+        }
+
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().clone());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let next_weak = Arc::downgrade(&next.0);
+        let id = Arc::new(Mutex::new(None));
+        let reaction_id = id.clone();
+
+        let react_fn = Box::new(move || {
+            let (Some(new_sig), Some(src)) = (new_signal_weak.upgrade(), source_weak.upgrade())
+            else {
+                return;
+            };
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return;
+            }
+            let value = src.value.lock().unwrap().clone();
+            if !switch_when(&value) {
+                *new_sig.value.lock().unwrap() = value;
+                return;
+            }
+
+            // Switch over to `next`: detach from the current signal and mirror `next`
+            if let Some(id) = reaction_id.lock().unwrap().take() {
+                src.react_fns.write().unwrap().remove(id);
+            }
+            let new_signal = SignalSync(new_sig);
+            src.successors
+                .write()
+                .unwrap()
+                .retain(|s| !s.points_to(&new_signal));
+            if let Some(next) = next_weak.upgrade() {
+                let next = SignalSync(next);
+                let value = next.0.value.lock().unwrap().clone();
+                *new_signal.0.value.lock().unwrap() = value;
+                next.mirror_into(&new_signal);
+            }
+        });
+
+        *id.lock().unwrap() = Some(self.push_derived(react_fn, &new_signal));
+
+        new_signal
+    }
+
+    /// Measure the update rate of the signal
+    ///
+    /// This creates a new signal reporting how many times per second the current signal was
+    /// updated. A timer thread counts the updates of each `window` and sends the computed rate
+    /// at the end of it, so the rate signal changes once per window. Dropping the returned
+    /// signal stops the timer, and the counting reaction left on the current signal does
+    /// nothing from then on.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// # use std::time::Duration;
+    /// let events = SignalSync::new(0);
+    /// let rate = events.rate(Duration::from_millis(100));
+    /// let _observer = rate.map(|r| println!("{} updates per second", r));
+    /// (0..5).for_each(|i| {
+    ///     events.send(i);
+    /// });
+    /// std::thread::sleep(Duration::from_millis(150)); // prints "50 updates per second"
+    /// ```
+    pub fn rate(&self, window: Duration) -> SignalSync<'static, f64> {
+        assert!(!window.is_zero(), "the rate window must not be zero");
+        let rate = SignalSync::new(0.0);
+        let rate_weak = Arc::downgrade(&rate.0);
+        let updates = Arc::new(AtomicU64::new(0));
+        let updates_weak = Arc::downgrade(&updates);
+
+        let react_fn = Box::new(move || {
+            if let Some(updates) = updates_weak.upgrade() {
+                updates.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        self.0.react_fns.write().unwrap().push(react_fn);
+
+        thread::spawn(move || {
+            loop {
+                thread::sleep(window);
+                let Some(rate) = rate_weak.upgrade() else {
+                    break;
+                };
+                let count = updates.swap(0, Ordering::Relaxed);
+                SignalSync(rate).send(count as f64 / window.as_secs_f64());
+            }
+        });
+        rate
+    }
+
+    /// Create a signal that only updates once the current signal has been quiet for `interval`
+    ///
+    /// Each change of the current signal restarts the countdown, and the new signal takes the
+    /// latest value once no change happened for `interval`, so a burst of sends results in a
+    /// single, trailing emission with its last value. The first value of a burst is not emitted
+    /// on its own (no leading emission), and a signal that keeps changing more often than
+    /// `interval` is never emitted. The new signal starts with the current value and is updated
+    /// from a timer thread, which stops within `interval` once the new signal is dropped.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// # use std::time::Duration;
+    /// let query = SignalSync::new(String::new());
+    /// let settled = query.debounce(Duration::from_millis(50));
+    /// let _observer = settled.map(|q| println!("searching for {:?}", q));
+    /// for q in ["r", "ru", "rus", "rust"] {
+    ///     query.send(q.to_string());
+    /// }
+    /// std::thread::sleep(Duration::from_millis(100)); // prints "searching for \"rust\"" once
+    /// ```
+    pub fn debounce(&self, interval: Duration) -> SignalSync<'static, T>
+    where
+        T: Clone + 'static,
+    {
+        self.timed(interval, Timing::Debounce)
+    }
+
+    /// Create a signal that updates at most once per `interval`
+    ///
+    /// The first change of the current signal is emitted right away (leading emission), and
+    /// starts an interval during which further changes are held back. At the end of the
+    /// interval the latest of them, if any, is emitted (trailing emission) and starts the next
+    /// interval, so no final value is lost and a signal changing continuously is emitted about
+    /// once per `interval`. The new signal starts with the current value and is updated from a
+    /// timer thread, which stops within `interval` once the new signal is dropped.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// # use std::time::Duration;
+    /// let position = SignalSync::new(0);
+    /// let sampled = position.throttle(Duration::from_millis(50));
+    /// let _observer = sampled.map(|p| println!("position: {}", p));
+    /// for p in 1..=100 {
+    ///     position.send(p);
+    /// }
+    /// std::thread::sleep(Duration::from_millis(100)); // prints "position: 1" and "position: 100"
+    /// ```
+    pub fn throttle(&self, interval: Duration) -> SignalSync<'static, T>
+    where
+        T: Clone + 'static,
+    {
+        self.timed(interval, Timing::Throttle)
+    }
+
+    /// Create a signal updated from a timer thread, as described by `debounce` and `throttle`
+    fn timed(&self, interval: Duration, timing: Timing) -> SignalSync<'static, T>
+    where
+        T: Clone + 'static,
+    {
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().clone());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let slot = Arc::new(TimedSlot {
+            state: Mutex::new(TimedState {
+                value: None,
+                due: Instant::now(),
+            }),
+            ready: Condvar::new(),
+        });
+
+        // The reaction only records the value, the timer thread decides when to emit it
+        let slot_weak = Arc::downgrade(&slot);
+        let source_weak = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(slot) = slot_weak.upgrade()
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.lock().unwrap().clone();
+                let mut state = slot.state.lock().unwrap();
+                state.value = Some(value);
+                if timing == Timing::Debounce {
+                    state.due = Instant::now() + interval;
+                }
+                slot.ready.notify_one();
+            }
+        });
+        self.0.react_fns.write().unwrap().push(react_fn);
+
+        thread::spawn(move || {
+            let mut state = slot.state.lock().unwrap();
+            loop {
+                if new_signal_weak.strong_count() == 0 {
+                    break;
+                }
+                let now = Instant::now();
+                if state.value.is_none() {
+                    // Wake up now and then to notice when the new signal is dropped
+                    state = slot.ready.wait_timeout(state, interval).unwrap().0;
+                } else if now < state.due {
+                    let wait = state.due - now;
+                    state = slot.ready.wait_timeout(state, wait).unwrap().0;
+                } else {
+                    let value = state.value.take();
+                    if timing == Timing::Throttle {
+                        state.due = now + interval;
+                    }
+                    drop(state);
+                    if let (Some(value), Some(new_sig)) = (value, new_signal_weak.upgrade()) {
+                        SignalSync(new_sig).send(value);
+                    }
+                    state = slot.state.lock().unwrap();
+                }
+            }
+        });
+        new_signal
+    }
+
+    /// Erase the value type of the signal
+    ///
+    /// The erased signal keeps the signal and its graph alive like a clone would, and can be
+    /// stored alongside signals of other types. Use `downcast` to get the concrete signal back.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::{BoxedSignalSync, SignalSync};
+    /// let signals: Vec<BoxedSignalSync> = vec![SignalSync::new(1).erase(), SignalSync::new("one").erase()];
+    /// let number = signals[0].downcast_ref::<i32>().unwrap();
+    /// number.send(2);
+    /// assert!(signals[1].downcast_ref::<i32>().is_none());
+    /// ```
+    pub fn erase(self) -> BoxedSignalSync<'a>
+    where
+        T: Send + Sync + 'static,
+    {
+        BoxedSignalSync {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            signal: Box::new(self),
+        }
+    }
+
+    /// Create an independent copy of the signal seeded with the current value
+    ///
+    /// Unlike `clone`, which shares the same inner signal and therefore the same graph, the
+    /// fork is a brand new signal with its own empty successors, predecessors and reactions.
+    /// It does NOT track the original: later changes to either signal are not seen by the other.
+    /// This is useful to edit a snapshot of a live value and commit it back later.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let draft = a.fork();
+    /// draft.send(2); // `a` still holds 1
+    /// a.send(3); // `draft` still holds 2
+    /// ```
+    pub fn fork(&self) -> Self
+    where
+        T: Clone,
+    {
+        SignalSync::new(self.0.value.lock().unwrap().clone())
+    }
+
+    fn map_ref<U: Send + Sync + 'a, F, S: RefStrategySync<'a> + 'a>(
+        &self,
+        f: F,
+    ) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        let new_signal = self.new_mapped(f(&self.0.value.lock().unwrap()));
+        let result_new_signal = new_signal.clone();
+
+        let new_signal_ref = S::new_ref(&new_signal);
+        let source_ref = S::new_ref(self);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = S::upgrade_ref(&new_signal_ref)
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = S::upgrade_ref(&source_ref)
+            {
+                let new_value = f(&src.value.lock().unwrap());
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        });
+
+        self.push_derived(react_fn, &new_signal);
+
+        result_new_signal
+    }
+
+    /// Map the signal contravariantly to a new signal
+    ///
+    /// This creates a new signal that the current signal depends on.
+    /// Changes to the new signal will propagate back to the original signal.
+    /// It is inspired by the concept of contravariant functors in category theory.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let result = SignalSync::new(42);
+    /// let source = result.contramap(|x| x * 2);
+    /// result.with(|x| println!("result changed: {}", x));
+    /// source.with(|x| println!("source changed: {}", x));
+    /// source.send(100);
+    /// // prints "source changed: 100" and "result changed: 50"
+    /// ```
+    pub fn contramap<F, U>(&self, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&U) -> T + Send + Sync + 'a,
+        U: Default + Send + Sync + 'a,
+    {
+        self.contramap_with(U::default(), f)
+    }
+
+    /// Map the signal contravariantly to a new signal with an explicit initial value
+    ///
+    /// This is the same as `contramap`, but the new signal starts with `initial`
+    /// instead of `U::default()`, so it works for types without a `Default` implementation.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let result = SignalSync::new(42);
+    /// let source = result.contramap_with(84, |x| x / 2);
+    /// result.with(|x| println!("result changed: {}", x));
+    /// source.send(100); // prints "result changed: 50"
+    /// ```
+    pub fn contramap_with<F, U>(&self, initial: U, f: F) -> SignalSync<'a, U>
+    where
+        F: Fn(&U) -> T + Send + Sync + 'a,
+        U: Send + Sync + 'a,
+    {
+        let new_signal = SignalSync::new(initial);
+        let result_new_signal = new_signal.clone();
+        let source_inner = Arc::downgrade(&self.0);
+        let new_signal_rc = Arc::downgrade(&new_signal.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_rc.upgrade()
+                && new_sig.explicitly_modified.load(Ordering::Acquire)
+            {
+                let u_value_ref = new_sig.value.lock().unwrap();
+                let t_value = f(&u_value_ref);
+                drop(u_value_ref);
+
+                if let Some(source) = source_inner.upgrade() {
+                    *source.value.lock().unwrap() = t_value;
+                    source.explicitly_modified.store(true, Ordering::Release);
+                }
+            }
+        });
+        new_signal.0.react_fns.write().unwrap().push(react_fn);
+
+        // Register self (source/result) as a predecessor of new_signal (backward dependency)
+        new_signal
+            .0
+            .predecessors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::new(self));
+
+        result_new_signal
+    }
+
+    /// Map the signal bidirectionally to a new signal
+    ///
+    /// This creates a new signal that depends on the current signal,
+    /// and the current signal also depends on the new signal.
+    /// Changes to either signal will propagate to the other signal.
+    /// It is inspired by the concept of profunctors in category theory.
+    ///
+    /// The new signal starts with `f` applied to the current value, so it is correct before
+    /// anything is sent.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.promap(|x| x * 2, |y| y / 2);
+    /// a.with(|x| println!("a changed: {}", x));
+    /// b.with(|x| println!("b changed: {}", x));
+    /// a.send(5); // prints "a changed: 5" and "b changed: 10"
+    /// b.send(50); // prints "b changed: 50" and "a changed: 25"
+    /// ```
+    pub fn promap<F, G, U>(&self, f: F, g: G) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+        G: Fn(&U) -> T + Send + Sync + 'a,
+        U: Send + Sync + 'a,
+    {
+        let initial = f(&self.0.value.lock().unwrap());
+        self.promap_with(initial, f, g)
+    }
+
+    /// Map the signal bidirectionally to a new signal with an explicit initial value
+    ///
+    /// This is the same as `promap`, but the new signal starts with `initial`
+    /// instead of `f` applied to the current value, for when that is expensive or not wanted.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.promap_with(20, |x| x * 2, |y| y / 2);
+    /// a.with(|x| println!("a changed: {}", x));
+    /// b.send(50); // prints "a changed: 25"
+    /// ```
+    pub fn promap_with<F, G, U>(&self, initial: U, f: F, g: G) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+        G: Fn(&U) -> T + Send + Sync + 'a,
+        U: Send + Sync + 'a,
+    {
+        self.promap_by(initial, f, g, |t_value, new_t_value| *t_value = new_t_value)
+    }
+
+    /// Project the signal to one of its parts, writing the changes of the part back
+    ///
+    /// This is a lens built on `promap`: the new signal holds `get` applied to the current value
+    /// and follows its changes, while sending to the new signal updates the current value in
+    /// place through `set` and notifies its other observers.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// #[derive(Debug)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    /// let user = SignalSync::new(User { name: String::from("Ann"), age: 30 });
+    /// let age = user.project(|u| u.age, |u, age| u.age = age);
+    /// user.with(|u| println!("user changed: {:?}", u));
+    /// age.send(31); // prints "user changed: User { name: \"Ann\", age: 31 }"
+    /// ```
+    pub fn project<U, Get, Set>(&self, get: Get, set: Set) -> SignalSync<'a, U>
+    where
+        U: Clone + Send + Sync + 'a,
+        Get: Fn(&T) -> U + Send + Sync + 'a,
+        Set: Fn(&mut T, U) + Send + Sync + 'a,
+    {
+        let initial = get(&self.0.value.lock().unwrap());
+        self.promap_by(initial, get, U::clone, set)
+    }
+
+    fn promap_by<F, G, H, U, V>(&self, initial: U, f: F, g: G, apply: H) -> SignalSync<'a, U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'a,
+        G: Fn(&U) -> V + Send + Sync + 'a,
+        H: Fn(&mut T, V) + Send + Sync + 'a,
+        U: Send + Sync + 'a,
+    {
+        let new_signal = SignalSync::new(initial);
+        let result_new_signal = new_signal.clone();
+        let source_weak = Arc::downgrade(&self.0);
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+
+        // Forward reaction: T -> U (covariant)
+        let source_inner = source_weak.clone();
+        let new_signal_rc = new_signal_weak.clone();
+        let forward_react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_rc.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(source) = source_inner.upgrade()
+            {
+                let t_value = source.value.lock().unwrap();
+                let u_value = f(&t_value);
+                drop(t_value);
+                *new_sig.value.lock().unwrap() = u_value;
+            }
+        });
+
+        self.push_derived(forward_react_fn, &new_signal);
+
+        // Backward reaction: U -> T (contravariant)
+        let new_signal_rc_back = new_signal_weak.clone();
+        let source_inner_back = source_weak.clone();
+
+        let backward_react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_rc_back.upgrade()
+                && new_sig.explicitly_modified.load(Ordering::Acquire)
+            {
+                let u_value_ref = new_sig.value.lock().unwrap();
+                let t_value = g(&u_value_ref);
+                drop(u_value_ref);
+
+                if let Some(source) = source_inner_back.upgrade() {
+                    apply(&mut source.value.lock().unwrap(), t_value);
+                    source.explicitly_modified.store(true, Ordering::Release);
+                }
+            }
+        });
+        new_signal
+            .0
+            .react_fns
+            .write()
+            .unwrap()
+            .push(backward_react_fn);
+
+        new_signal
+            .0
+            .predecessors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::new(self));
+
+        result_new_signal
+    }
+
+    /// Combine two signals into one
+    ///
+    /// This combines two signals into a new signal that holds a tuple of their values.
+    /// Changes to either signal will propagate to the new combined signal.
+    /// To transform the pair right away, prefer `combine_map`, which skips the tuple signal.
+    /// The values are cloned into the tuple, since the sources and the combined signal each
+    /// hold one; moving them instead would leave a source with no valid value to hold.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// let ab = a.combine(&b);
+    /// ab.with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// ```
+    pub fn combine<S>(&self, another: S) -> SignalSync<'a, (T, S::Inner)>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Send + Sync + Clone + 'a,
+        T: Send + Sync + Clone,
+    {
+        self.combine_ref::<S, WeakRefStrategySync>(another)
+    }
+
+    /// Combine two signals into one with strong references
+    ///
+    /// This combines two signals into a new signal that holds a tuple of their values.
+    /// Changes to either signal will propagate to the new combined signal.
+    /// This combination uses strong references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(10);
+    /// let b = a.map(|x| x * 2);
+    /// a.and(&b).with(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+    /// a.send(5); // prints "c changed: 5 + 10 = 15"
+    /// ```
+    pub fn and<S>(&self, another: S) -> SignalSync<'a, (T, S::Inner)>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Send + Sync + Clone + 'a,
+        T: Send + Sync + Clone,
+    {
+        self.combine_ref::<S, StrongRefStrategySync>(another)
+    }
+
+    fn combine_ref<S, St: RefStrategySync<'a> + 'a>(
+        &self,
+        another: S,
+    ) -> SignalSync<'a, (T, S::Inner)>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Send + Sync + Clone + 'a,
+        T: Send + Sync + Clone,
+    {
+        let another = another.as_ref();
+
+        let new_signal = SignalSync::new((
+            self.0.value.lock().unwrap().clone(),
+            another.0.value.lock().unwrap().clone(),
+        ));
+
+        let result_new_signal = new_signal.clone();
+
+        let new_signal_ref = St::new_ref(&new_signal);
+        let source_self_ref = St::new_ref(self);
+
+        let react_fn_self = Box::new(move || {
+            if let Some(new_sig) = St::upgrade_ref(&new_signal_ref)
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(source) = St::upgrade_ref(&source_self_ref)
+            {
+                let value = source.value.lock().unwrap().clone();
+                new_sig.value.lock().unwrap().0 = value;
+            }
+        });
+
+        let new_signal_ref_2 = St::new_ref(&new_signal);
+        let source_another_ref_2 = St::new_ref(another);
+        let react_fn_another = Box::new(move || {
+            if let Some(new_sig) = St::upgrade_ref(&new_signal_ref_2)
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(source) = St::upgrade_ref(&source_another_ref_2)
+            {
+                let value = source.value.lock().unwrap().clone();
+                new_sig.value.lock().unwrap().1 = value;
+            }
+        });
+
+        self.push_derived(react_fn_self, &result_new_signal);
+        another.push_derived(react_fn_another, &result_new_signal);
+        result_new_signal
+    }
+
+    /// Combine two signals and transform their values in a single reaction
+    ///
+    /// This is the fused form of `a.combine(&b).map(f)`: the new signal holds `f` applied to
+    /// the values of both signals and is recomputed by reading both sources directly whenever
+    /// either changes, without an intermediate tuple signal or an extra reaction.
+    /// Like `map`, the new signal is weakly referenced by both sources.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let width = SignalSync::new(2);
+    /// let height = SignalSync::new(3);
+    /// let area = width.combine_map(&height, |w, h| w * h);
+    /// let _observer = area.map(|a| println!("area: {}", a));
+    /// width.send(4); // prints "area: 12"
+    /// (width.send(5), height.send(5)); // prints "area: 25" once
+    /// ```
+    pub fn combine_map<B, U, F>(&self, other: &SignalSync<'a, B>, f: F) -> SignalSync<'a, U>
+    where
+        B: Send + Sync + 'a,
+        U: Send + Sync + 'a,
+        F: Fn(&T, &B) -> U + Send + Sync + 'a,
+    {
+        self.combine_map_ref::<B, U, F, WeakRefStrategySync>(other, f)
+    }
+
+    fn combine_map_ref<B, U, F, St: RefStrategySync<'a> + 'a>(
+        &self,
+        other: &SignalSync<'a, B>,
+        f: F,
+    ) -> SignalSync<'a, U>
+    where
+        B: Send + Sync + 'a,
+        U: Send + Sync + 'a,
+        F: Fn(&T, &B) -> U + Send + Sync + 'a,
+    {
+        let new_signal = SignalSync::new(f(
+            &self.0.value.lock().unwrap(),
+            &other.0.value.lock().unwrap(),
+        ));
+        let f = Arc::new(f);
+
+        // Shared so the reaction can be cloned for both sources
+        let new_signal_ref = Arc::new(St::new_ref(&new_signal));
+        let self_weak = Arc::downgrade(&self.0);
+        let other_weak = Arc::downgrade(&other.0);
+        let react_fn = move || {
+            if let Some(new_sig) = St::upgrade_ref(&new_signal_ref)
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let (Some(left), Some(right)) = (self_weak.upgrade(), other_weak.upgrade())
+            {
+                let new_value = f(&left.value.lock().unwrap(), &right.value.lock().unwrap());
+                *new_sig.value.lock().unwrap() = new_value;
+            }
+        };
+        self.push_derived(Box::new(react_fn.clone()), &new_signal);
+        other.push_derived(Box::new(react_fn), &new_signal);
+
+        new_signal
+    }
+
+    /// Combine with any liftable signal and transform both values into one
+    ///
+    /// This is `combine_map` for any `LiftableSync`, so owned and borrowed signals can be passed alike.
+    /// The new signal holds `f` applied to both values, with no intermediate tuple signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(2);
+    /// let sum = a.combine_with(b.clone(), |x, y| x + y);
+    /// let _observer = sum.map(|s| println!("sum: {}", s)); // prints "sum: 3"
+    /// b.send(5); // prints "sum: 6"
+    /// ```
+    pub fn combine_with<S, U, F>(&self, other: S, f: F) -> SignalSync<'a, U>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Send + Sync + 'a,
+        U: Send + Sync + 'a,
+        F: Fn(&T, &S::Inner) -> U + Send + Sync + 'a,
+    {
+        self.combine_map(other.as_ref(), f)
+    }
+
+    /// Combine two signals into one that emits only once both have changed
+    ///
+    /// Unlike `combine`, the new signal does not notify its observers until both signals have
+    /// been sent a value since it was created; from then on it emits the latest pair whenever
+    /// either changes, like `combineLatest` in RxJS. Until then it holds the current values.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(0);
+    /// let b = SignalSync::new(0);
+    /// let latest = a.combine_latest(&b);
+    /// let _observer = latest.map(|(x, y)| println!("latest: {} {}", x, y));
+    /// a.send(1); // prints nothing
+    /// b.send(2); // prints "latest: 1 2"
+    /// a.send(3); // prints "latest: 3 2"
+    /// ```
+    pub fn combine_latest<S>(&self, other: S) -> SignalSync<'a, (T, S::Inner)>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Clone + Send + Sync + 'a,
+        T: Clone,
+    {
+        let other = other.as_ref();
+        let new_signal = SignalSync::new((
+            self.0.value.lock().unwrap().clone(),
+            other.0.value.lock().unwrap().clone(),
+        ));
+
+        // The values sent to each signal since construction, the pair is emitted once both are set
+        let latest = Arc::new(Mutex::new((None::<T>, None::<S::Inner>)));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let latest_emit = latest.clone();
+        let emit = Arc::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            let pair = match &*latest_emit.lock().unwrap() {
+                (Some(left), Some(right)) => (left.clone(), right.clone()),
+                _ => return None,
+            };
+            *new_sig.value.lock().unwrap() = pair;
+            Some(SignalSync(new_sig).guard())
+        });
+
+        let self_weak = Arc::downgrade(&self.0);
+        let (latest_self, emit_self) = (latest.clone(), emit.clone());
+        let react_fn_self = Box::new(move || {
+            let src = self_weak.upgrade()?;
+            latest_self.lock().unwrap().0 = Some(src.value.lock().unwrap().clone());
+            emit_self()
+        });
+        self.push_forwarded(react_fn_self, &new_signal);
+
+        let other_weak = Arc::downgrade(&other.0);
+        let react_fn_other = Box::new(move || {
+            let src = other_weak.upgrade()?;
+            latest.lock().unwrap().1 = Some(src.value.lock().unwrap().clone());
+            emit()
+        });
+        other.push_forwarded(react_fn_other, &new_signal);
+
+        new_signal
+    }
+
+    /// Pair the changes of two signals in order, like zipping two iterators
+    ///
+    /// The nth change of this signal is paired with the nth change of `other`: the new values of
+    /// each signal are queued, and the new signal emits a pair whenever both queues hold a value,
+    /// taking the oldest of each. Unlike `combine_latest`, no value is paired twice or skipped.
+    /// A signal reacting several times before the other reacts once has all its values buffered,
+    /// so if one signal consistently changes faster than the other, its queue grows without
+    /// bound. Until the first pair, the new signal holds the current values without notifying.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let requests = SignalSync::new(0);
+    /// let responses = SignalSync::new(String::new());
+    /// let exchanges = requests.zip(&responses);
+    /// let _observer = exchanges.map(|(id, body)| println!("{}: {}", id, body));
+    /// requests.send(1); // prints nothing
+    /// requests.send(2); // prints nothing
+    /// responses.send(String::from("one")); // prints "1: one"
+    /// responses.send(String::from("two")); // prints "2: two"
+    /// ```
+    pub fn zip<S>(&self, other: S) -> SignalSync<'a, (T, S::Inner)>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Clone + Send + Sync + 'a,
+        T: Clone,
+    {
+        let other = other.as_ref();
+        let new_signal = SignalSync::new((
+            self.0.value.lock().unwrap().clone(),
+            other.0.value.lock().unwrap().clone(),
+        ));
+
+        // The values of each signal not paired yet, oldest first
+        let queues = Arc::new(Mutex::new((
+            VecDeque::<T>::new(),
+            VecDeque::<S::Inner>::new(),
+        )));
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let queues_emit = queues.clone();
+        let emit = Arc::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let pair = {
+                let mut queues = queues_emit.lock().unwrap();
+                if queues.0.is_empty() || queues.1.is_empty() {
+                    return None;
+                }
+                (queues.0.pop_front()?, queues.1.pop_front()?)
+            };
+            // The pair is consumed even when the signal was sent to directly, to keep the pace
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            *new_sig.value.lock().unwrap() = pair;
+            Some(SignalSync(new_sig).guard())
+        });
+
+        let self_weak = Arc::downgrade(&self.0);
+        let (queues_self, emit_self) = (queues.clone(), emit.clone());
+        let react_fn_self = Box::new(move || {
+            let src = self_weak.upgrade()?;
+            let value = src.value.lock().unwrap().clone();
+            queues_self.lock().unwrap().0.push_back(value);
+            emit_self()
+        });
+        self.push_forwarded(react_fn_self, &new_signal);
+
+        let other_weak = Arc::downgrade(&other.0);
+        let react_fn_other = Box::new(move || {
+            let src = other_weak.upgrade()?;
+            let value = src.value.lock().unwrap().clone();
+            queues.lock().unwrap().1.push_back(value);
+            emit()
+        });
+        other.push_forwarded(react_fn_other, &new_signal);
+
+        new_signal
+    }
+
+    /// Extend the signal with a vector of signals
+    ///
+    /// This creates a new signal that depends on the current signal and the provided signals.
+    /// Changes to any of the source signals will propagate to the new signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(2);
+    /// let c = SignalSync::new(3);
+    /// let d = a.extend(vec![&b, &c]);
+    /// d.with(|values| println!("d changed: {:?}", values));
+    /// a.send(10); // prints "d changed: [10, 2, 3]"
+    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// ```
+    pub fn extend<S>(&self, others: impl IntoIterator<Item = S>) -> SignalSync<'a, Vec<T>>
+    where
+        S: LiftableSync<'a, Inner = T>,
+        T: Send + Sync + Clone,
+    {
+        self.extend_ref::<S, WeakRefStrategySync>(others)
+    }
+
+    /// Extend the signal with a vector of signals with strong references
+    ///
+    /// This creates a new signal that depends on the current signal and the provided signals.
+    /// Changes to any of the source signals will propagate to the new signal.
+    /// It uses strong references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(2);
+    /// let c = SignalSync::new(3);
+    /// a.follow(vec![&b, &c]).with(|values| println!("d changed: {:?}", values));
+    /// a.send(10); // prints "d changed: [10, 2, 3]"
+    /// (b.send(20), c.send(30)); // prints "d changed: [10, 20, 30]"
+    /// ```
+    pub fn follow<S>(&self, others: impl IntoIterator<Item = S>) -> SignalSync<'a, Vec<T>>
+    where
+        S: LiftableSync<'a, Inner = T>,
+        T: Send + Sync + Clone,
+    {
+        self.extend_ref::<S, StrongRefStrategySync>(others)
+    }
+
+    fn extend_ref<S, St: RefStrategySync<'a> + 'a>(
+        &self,
+        others: impl IntoIterator<Item = S>,
+    ) -> SignalSync<'a, Vec<T>>
+    where
+        S: LiftableSync<'a, Inner = T>,
+        T: Send + Sync + Clone,
+    {
+        let others_signals: Vec<SignalSync<'a, T>> =
+            others.into_iter().map(|s| s.as_ref().clone()).collect();
+
+        let values: Vec<T> = iter::once(self)
+            .chain(others_signals.iter())
+            .map(|s| s.0.value.lock().unwrap().clone())
+            .collect();
+        let new_signal: SignalSync<'a, Vec<T>> = SignalSync::new(values);
+
+        let result_new_signal = new_signal.clone();
+
+        iter::once(self)
+            .chain(others_signals.iter())
+            .enumerate()
+            .for_each(|(index, signal)| {
+                let new_signal_ref = St::new_ref(&new_signal);
+                let source_ref = St::new_ref(signal);
+
+                let react_fn = Box::new(move || {
+                    if let Some(new_sig) = St::upgrade_ref(&new_signal_ref)
+                        && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                        && let Some(source) = St::upgrade_ref(&source_ref)
+                    {
+                        let value = source.value.lock().unwrap().clone();
+                        new_sig.value.lock().unwrap()[index] = value;
+                    }
+                });
+
+                signal.push_derived(react_fn, &new_signal);
+            });
+
+        result_new_signal
+    }
+
+    /// Extend the signal with other signals, reporting which element changed
+    ///
+    /// Like `extend`, this creates a signal holding the values of this signal followed by the
+    /// values of `others`, but paired with the index of the element that changed last, so
+    /// observers can update incrementally instead of diffing the whole vector. The index starts
+    /// at 0; when several elements change in one batch, the last one to settle is reported.
+    /// Values are cloned into the new signal, and like `extend` it is weakly referenced.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(2);
+    /// let c = SignalSync::new(3);
+    /// let list = a.extend_tracked([&b, &c]);
+    /// let _observer = list.map(|(i, values)| println!("item {} is now {}", i, values[*i]));
+    /// c.send(30); // prints "item 2 is now 30"
+    /// a.send(10); // prints "item 0 is now 10"
+    /// ```
+    pub fn extend_tracked<S>(
+        &self,
+        others: impl IntoIterator<Item = S>,
+    ) -> SignalSync<'a, (usize, Vec<T>)>
+    where
+        S: LiftableSync<'a, Inner = T>,
+        T: Clone,
+    {
+        let signals: Vec<SignalSync<'a, T>> = iter::once(self.clone())
+            .chain(others.into_iter().map(|s| s.as_ref().clone()))
+            .collect();
+        let values: Vec<T> = signals
+            .iter()
+            .map(|s| s.0.value.lock().unwrap().clone())
+            .collect();
+        let new_signal = SignalSync::new((0, values));
+
+        for (index, signal) in signals.iter().enumerate() {
+            let new_signal_weak = Arc::downgrade(&new_signal.0);
+            let source_weak = Arc::downgrade(&signal.0);
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                    && let Some(src) = source_weak.upgrade()
+                {
+                    let value = src.value.lock().unwrap().clone();
+                    let mut tracked = new_sig.value.lock().unwrap();
+                    tracked.0 = index;
+                    tracked.1[index] = value;
+                }
+            });
+            signal.push_derived(react_fn, &new_signal);
+        }
+
+        new_signal
+    }
+
+    /// Let this signal depend on another signal
+    ///
+    /// This synchronizes the value of this signal with the value of the dependency signal.
+    /// Whenever the dependency signal changes, this signal will be updated to match its value.
+    ///
+    /// The returned signal is exactly the argument `dependency`. You can break the dependency chain
+    /// by dropping the returned signal if `dependency` is weakly referenced.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let mut b = SignalSync::new(2);
+    /// b = a.depend(b);
+    /// a.with(|v| println!("a changed: {}", v));
+    /// b.send(3); // prints "a changed: 3"
+    /// ```
+    ///
+    /// The example above is analogous to:
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = a.map(|v| *v);
+    /// b.with(|v| println!("b changed: {}", v));
+    /// a.send(3); // prints "b changed: 3"
+    /// ```
+    pub fn depend(&self, dependency: SignalSync<'a, T>) -> SignalSync<'a, T> {
+        let self_weak = Arc::downgrade(&self.0);
+        let dependency_weak = Arc::downgrade(&dependency.0);
+
+        let react_fn = Box::new(move || {
+            if let Some(dep) = dependency_weak.upgrade()
+                && let Some(target) = self_weak.upgrade()
+                && !target.explicitly_modified.load(Ordering::Acquire)
+            {
+                // Swap values instead of cloning
+                std::mem::swap(
+                    &mut *target.value.lock().unwrap(),
+                    &mut *dep.value.lock().unwrap(),
+                );
+            }
+        });
+
+        dependency.push_derived(react_fn, self);
+        dependency
+    }
+
+    /// Apply a modification function to the stored value (thread-safe)
+    pub(crate) fn modify(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.0.value.lock().unwrap();
+        f(&mut value);
+    }
+
+    /// Lock the value, retrying until `deadline` if there is one, or trying once otherwise
+    fn lock_value_until(&self, deadline: Option<Instant>) -> Option<MutexGuard<'_, T>> {
+        loop {
+            match self.0.value.try_lock() {
+                Ok(value) => return Some(value),
+                Err(TryLockError::Poisoned(poisoned)) => panic!("{}", poisoned),
+                Err(TryLockError::WouldBlock) => {}
+            }
+            let remaining = deadline?.checked_duration_since(Instant::now())?;
+            thread::sleep(remaining.min(Duration::from_micros(100)));
+        }
+    }
+
+    /// Finish a send once the value has been locked
+    fn send_locked(&self, mut value: MutexGuard<'_, T>, new_value: T) -> SignalGuardSync<'a> {
+        *value = new_value;
+        drop(value);
+        self.0.explicitly_modified.store(true, Ordering::Release);
+        self.react_eager();
+        self.guard()
+    }
+
+    /// Create the signal of a `map` variant, reporting to the same error sink as this signal
+    ///
+    /// Every `map_*` variant seeds its new signal through this, so a failing mapping reaches the
+    /// handlers installed with `on_reaction_error` whichever variant built it. The label and the
+    /// equality predicate describe this signal's own values and are not carried over.
+    fn new_mapped<U: Send + Sync + 'a>(&self, initial: U) -> SignalSync<'a, U> {
+        let new_signal = SignalSync::new(initial);
+        *new_signal.0.error_sink.write().unwrap() = Some(self.error_sink());
+        new_signal
+    }
+
+    /// Get the error sink of the graph, creating it if needed
+    fn error_sink(&self) -> Arc<ErrorSinkSync<'a>> {
+        self.0
+            .error_sink
+            .write()
+            .unwrap()
+            .get_or_insert_with(|| Arc::new(ErrorSinkSync::new()))
+            .clone()
+    }
+
+    /// Report a rejected value to the error sink of the graph, if one is installed
+    fn report_rejected(&self, value: &T)
+    where
+        T: fmt::Debug,
+    {
+        if let Some(sink) = self.0.error_sink.read().unwrap().as_ref() {
+            sink.report(&ReactionError::new(format!(
+                "rejected invalid value: {:?}",
+                value
+            )));
+        }
+    }
+
+    /// Check with the predicate installed by `with_eq` whether two values are the same
+    fn is_unchanged(&self, current: &T, new_value: &T) -> bool {
+        self.0
+            .eq
+            .read()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|eq| eq(current, new_value))
+    }
+
+    /// Feed this signal into one field of `lifted`, used by the code `#[derive(LiftSync)]`
+    /// generates and by the tuple `LiftIntoSync` implementations
+    ///
+    /// On every change the value of this signal is cloned into the field selected by `field`,
+    /// unless `lifted` was explicitly modified in the same propagation.
+    #[doc(hidden)]
+    pub fn lift_field<U: Send + Sync + 'a>(
+        &self,
+        lifted: &SignalSync<'a, U>,
+        field: fn(&mut U) -> &mut T,
+    ) where
+        T: Clone,
+    {
+        let lifted_ref = Arc::downgrade(&lifted.0);
+        let self_ref = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let (Some(lifted), Some(src)) = (lifted_ref.upgrade(), self_ref.upgrade())
+                && !lifted.explicitly_modified.load(Ordering::Acquire)
+            {
+                let value = src.value.lock().unwrap().clone();
+                *field(&mut lifted.value.lock().unwrap()) = value;
+            }
+        });
+        self.push_derived(react_fn, lifted);
+    }
+
+    /// Keep one field of `lifted` and this signal in sync both ways, used by the code
+    /// `#[derive(LiftSync)]` generates for `lift_bidirectional`
+    ///
+    /// Changes of this signal are cloned into the field selected by `field`, and when `lifted`
+    /// is sent to, that field is cloned back into this signal, which then propagates to its
+    /// own dependents like with `promap`. Unlike `lift_field`, both sides keep their values.
+    #[doc(hidden)]
+    pub fn bind_field<U: Send + Sync + 'a>(
+        &self,
+        lifted: &SignalSync<'a, U>,
+        field: fn(&mut U) -> &mut T,
+    ) where
+        T: Clone,
+    {
+        let lifted_weak = Arc::downgrade(&lifted.0);
+        let self_weak = Arc::downgrade(&self.0);
+
+        // Forward reaction: this signal -> field of lifted
+        let (lifted_ref, self_ref) = (lifted_weak.clone(), self_weak.clone());
+        let forward_react_fn = Box::new(move || {
+            if let (Some(lifted), Some(src)) = (lifted_ref.upgrade(), self_ref.upgrade())
+                && !lifted.explicitly_modified.load(Ordering::Acquire)
+            {
+                let value = src.value.lock().unwrap().clone();
+                *field(&mut lifted.value.lock().unwrap()) = value;
+            }
+        });
+        self.push_derived(forward_react_fn, lifted);
+
+        // Backward reaction: field of lifted -> this signal
+        let backward_react_fn = Box::new(move || {
+            if let Some(lifted) = lifted_weak.upgrade()
+                && lifted.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = self_weak.upgrade()
+            {
+                let value = field(&mut lifted.value.lock().unwrap()).clone();
+                *src.value.lock().unwrap() = value;
+                src.explicitly_modified.store(true, Ordering::Release);
+            }
+        });
+        lifted.0.react_fns.write().unwrap().push(backward_react_fn);
+        lifted
+            .0
+            .predecessors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::new(self));
+    }
+
+    /// Register a reaction feeding `derived` along with the successor edge to it
+    ///
+    /// The reaction is owned by `derived`, so `prune_dead` drops it once `derived` is gone.
+    fn push_derived<U: Send + Sync + 'a>(
+        &self,
+        react_fn: Box<dyn Fn() + Send + Sync + 'a>,
+        derived: &SignalSync<'a, U>,
+    ) -> ReactionId {
+        let id = self
+            .0
+            .react_fns
+            .write()
+            .unwrap()
+            .push_owned(react_fn, WeakSignalRefSync::new(derived));
+        self.0
+            .successors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::new(derived));
+        id
+    }
+
+    /// Register a reaction forwarding the guard of `derived` along with the successor edge to it
+    ///
+    /// Unlike the edge of `push_derived`, propagation does not follow this one: `derived` is
+    /// only marked dirty by the guard the reaction forwards, so it can skip an update. The edge
+    /// still shows `derived` to `successor_count` and `to_dot`, and the reaction is owned by
+    /// `derived`, so both are pruned once it is gone.
+    fn push_forwarded<U: Send + Sync + 'a>(
+        &self,
+        react_fn: Box<dyn Fn() -> Option<SignalGuardSync<'a>> + Send + Sync + 'a>,
+        derived: &SignalSync<'a, U>,
+    ) -> ReactionId {
+        let id = self
+            .0
+            .react_fns
+            .write()
+            .unwrap()
+            .push_forwarding_owned(react_fn, WeakSignalRefSync::new(derived));
+        self.0
+            .successors
+            .write()
+            .unwrap()
+            .push(WeakSignalRefSync::forwarded(derived));
+        id
+    }
+
+    /// Run a reaction, catching a panic so the rest of the graph still settles
+    ///
+    /// A panicking reaction is skipped for this propagation and the panic is reported to the
+    /// error sink of the signal, see `on_reaction_error`. When no handler is installed, the first
+    /// such panic is raised again once the outermost guard has settled the graph.
+    fn run_isolated(&self, react_fn: &ReactionSync<'a>) -> Option<SignalGuardSync<'a>> {
+        match panic::catch_unwind(AssertUnwindSafe(|| react_fn.run())) {
+            Ok(forwarded) => forwarded,
+            Err(payload) => {
+                // Whatever the reaction held locked is left consistent enough to keep using
+                self.clear_poison_around();
+                let sink = self.0.error_sink.read().unwrap().clone();
+                let reported = sink.is_some_and(|sink| {
+                    sink.report(&ReactionError::new(format!(
+                        "reaction panicked: {}",
+                        panic_message(&*payload)
+                    )))
+                });
+                if !reported {
+                    UNREPORTED_PANIC.with(|slot| {
+                        slot.borrow_mut().get_or_insert(payload);
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// Clear the poison a panicking reaction may have left on this signal and its neighbours
+    ///
+    /// A reaction locks the values of the signals it reads and writes: its own signal, the
+    /// successors it feeds, including forwarded ones, and the predecessors it writes back to.
+    fn clear_poison_around(&self) {
+        self.0.value.clear_poison();
+        for refs in [&self.0.successors, &self.0.predecessors] {
+            let neighbours: Vec<_> = refs
+                .read()
+                .unwrap()
+                .iter()
+                .filter_map(|s| s.upgrade())
+                .collect();
+            neighbours.iter().for_each(|signal| signal.clear_poison());
+        }
+    }
+
+    /// Keep `target` updated with the value of this signal, returning the reaction doing it
+    fn mirror_into(&self, target: &SignalSync<'a, T>) -> ReactionId
+    where
+        T: Clone,
+    {
+        let target_weak = Arc::downgrade(&target.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = target_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let value = src.value.lock().unwrap().clone();
+                *new_sig.value.lock().unwrap() = value;
+            }
+        });
+
+        self.push_derived(react_fn, target)
+    }
+
+    /// Make `target` mirror `inner` instead of the inner signal it currently mirrors
+    ///
+    /// The reaction and the successor edge of the previous inner signal are removed, so its
+    /// later changes no longer reach `target`.
+    fn switch_inner(current: &mut Option<(Self, ReactionId)>, target: &Self, inner: Self)
+    where
+        T: Clone,
+    {
+        if let Some((previous, id)) = current.take() {
+            previous.0.react_fns.write().unwrap().remove(id);
+            previous
+                .0
+                .successors
+                .write()
+                .unwrap()
+                .retain(|s| !s.points_to(target));
+        }
+        *target.0.value.lock().unwrap() = inner.0.value.lock().unwrap().clone();
+        let id = inner.mirror_into(target);
+        *current = Some((inner, id));
+    }
+
+    /// Run the eager reactions right after a direct modification
+    fn react_eager(&self) {
+        self.0.eagerly_reacted.store(true, Ordering::Release);
+        let eager_react_fns = self.0.eager_react_fns.read().unwrap().snapshot();
+        eager_react_fns.iter().for_each(|react_fn| {
+            self.run_isolated(react_fn);
+        });
+    }
+
+    fn mark_dirty(&self) {
+        trace(TraceEvent::MarkDirty(self.addr()));
+        self.0.dirty.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Drop the dead references and call `callback` on the live ones
+    ///
+    /// Forwarded references are skipped, their signals are marked dirty by the guards their
+    /// reactions forward. Returns whether any dead reference was dropped.
+    fn collect_and_iterate<F>(
+        &self,
+        refs: &RwLock<Vec<WeakSignalRefSync<'a>>>,
+        mut callback: F,
+    ) -> bool
+    where
+        F: FnMut(&dyn SignalExtSync<'a>),
+    {
+        let (pruned, signals_to_process): (bool, Vec<_>) = {
+            let mut refs_write = refs.write().unwrap();
+            let len = refs_write.len();
+            refs_write.retain(|s| s.is_alive());
+            (
+                refs_write.len() != len,
+                refs_write
+                    .iter()
+                    .filter(|s| !s.forwarded)
+                    .filter_map(|s| s.upgrade())
+                    .collect(),
+            )
+        };
+
+        for signal in signals_to_process {
+            callback(&*signal);
+        }
+        pruned
+    }
+
+    fn collect_guards(&self, result: &mut Vec<SignalGuardInnerSync<'a>>) {
+        trace(TraceEvent::Propagate(self.addr()));
+        // Collect every signal once, in topological order, so each one reacts once per send
+        // and only after all the signals it depends on
+        let mut visited = HashSet::new();
+        let start = result.len();
+        self.collect_guards_recursive(result, &mut visited);
+        result[start..].reverse();
+        // A frozen signal holds back its predecessors too, they are updated once it thaws
+        if self.0.frozen.load(Ordering::Acquire) > 0 {
+            self.0.sent_while_frozen.store(true, Ordering::Release);
+            return;
+        }
+        self.collect_and_iterate(&self.0.predecessors, |signal| {
+            signal.collect_predecessors_recursive(result, &mut visited);
+        });
+    }
+
+    /// Collect the guard of a signal thawing with changes it held back
+    ///
+    /// A send held back from the predecessors is made again, to write it back to them. Without
+    /// one, the signal was only updated by its sources, so its predecessors are left out.
+    fn thaw_guard(&self) -> SignalGuardSync<'a> {
+        if self.0.sent_while_frozen.swap(false, Ordering::AcqRel) {
+            self.0.explicitly_modified.store(true, Ordering::Release);
+            return self.guard();
+        }
+        let mut result = Vec::new();
+        self.collect_guards_recursive(&mut result, &mut HashSet::new());
+        result.reverse();
+        SignalGuardSync(result, None)
+    }
+
+    /// Identify the signal by the address of its inner part, shared by all its clones
+    fn addr(&self) -> usize {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+
+    /// Create a signal computed from explicitly declared dependencies
+    ///
+    /// This creates a new signal holding the result of `f`, which is recomputed whenever any
+    /// of `deps` changes. Dependencies are declared rather than tracked: `f` may read anything
+    /// it captured, but only changes of the declared signals trigger a recomputation. This is
+    /// the general multi-input computed signal, without nesting `combine`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new("x");
+    /// let recomputed = std::sync::atomic::AtomicUsize::new(0);
+    /// let count = SignalSync::computed_from(&[&a, &b], move || {
+    ///     recomputed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1
+    /// });
+    /// let _observer = count.map(|n| println!("recomputed {} times", n));
+    /// a.send(2); // prints "recomputed 2 times"
+    /// b.send("y"); // prints "recomputed 3 times"
+    /// ```
+    pub fn computed_from<F>(deps: &[&dyn ErasedSignalSync<'a>], f: F) -> SignalSync<'a, T>
+    where
+        F: Fn() -> T + Send + Sync + 'a,
+    {
+        let f = Arc::new(f);
+        let new_signal = SignalSync::new(f());
+
+        for dep in deps {
+            let SignalHandleSync(dep) = dep.handle();
+            let new_signal_weak = Arc::downgrade(&new_signal.0);
+            let f = f.clone();
+
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                {
+                    let new_value = f();
+                    *new_sig.value.lock().unwrap() = new_value;
+                }
+            });
+
+            dep.push_reaction(react_fn, WeakSignalRefSync::new(&new_signal));
+            dep.push_successor(WeakSignalRefSync::new(&new_signal));
+        }
+
+        new_signal
+    }
+
+    /// Reduce a collection of signals into a single aggregate signal
+    ///
+    /// This creates a new signal holding the fold of the current values of all `signals`,
+    /// starting from `init`. The fold is recomputed whenever any of the source signals changes.
+    /// The sources are weakly referenced; a dropped source no longer contributes to the fold.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(2);
+    /// let sum = SignalSync::reduce(vec![a.clone(), b.clone()], 0, |acc, x| acc + x);
+    /// sum.with(|total| println!("sum changed: {}", total));
+    /// a.send(10); // prints "sum changed: 12"
+    /// ```
+    pub fn reduce<U, F>(signals: Vec<SignalSync<'a, T>>, init: U, f: F) -> SignalSync<'a, U>
+    where
+        U: Clone + Send + Sync + 'a,
+        F: Fn(U, &T) -> U + Send + Sync + 'a,
+    {
+        let sources: Vec<_> = signals.iter().map(|s| Arc::downgrade(&s.0)).collect();
+        let fold = Arc::new(move || {
+            sources
+                .iter()
+                .filter_map(|source| source.upgrade())
+                .fold(init.clone(), |acc, source| {
+                    f(acc, &source.value.lock().unwrap())
+                })
+        });
+
+        let new_signal = SignalSync::new(fold());
+
+        for signal in &signals {
+            let new_signal_weak = Arc::downgrade(&new_signal.0);
+            let fold = fold.clone();
+
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                {
+                    let new_value = fold();
+                    *new_sig.value.lock().unwrap() = new_value;
+                }
+            });
+
+            signal.push_derived(react_fn, &new_signal);
+        }
+
+        new_signal
+    }
+
+    /// Lift an array of liftable items into a signal of an array
+    ///
+    /// This creates a new signal that depends on the provided liftable items.
+    /// Changes to any of the source signals will propagate to the new signal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(2);
+    /// let c = SignalSync::new(3);
+    /// let abc = SignalSync::<i32>::lift_from_array([&a, &b, &c]);
+    /// abc.with(|values| println!("abc changed: {:?}", values));
+    /// (a.send(10), b.send(20), c.send(30)); // prints "abc changed: [10, 20, 30]"
+    /// ```
+    pub fn lift_from_array<S, const N: usize>(items: [S; N]) -> SignalSync<'a, [S::Inner; N]>
+    where
+        S: LiftableSync<'a>,
+        S::Inner: Send + Sync + Clone + 'a,
+    {
+        let signals: [SignalSync<'a, S::Inner>; N] =
+            std::array::from_fn(|i| items[i].as_ref().clone());
+
+        let initial: [S::Inner; N] =
+            std::array::from_fn(|i| signals[i].0.value.lock().unwrap().clone());
+        let new_signal: SignalSync<'a, [S::Inner; N]> = SignalSync::new(initial);
+
+        let result_new_signal = new_signal.clone();
+
+        for (index, signal) in signals.iter().enumerate() {
+            let new_signal_weak = Arc::downgrade(&new_signal.0);
+            let source_for_closure = Arc::downgrade(&signal.0);
+
+            let react_fn = Box::new(move || {
+                if let Some(new_sig) = new_signal_weak.upgrade()
+                    && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                    && let Some(source) = source_for_closure.upgrade()
+                {
+                    let value = source.value.lock().unwrap().clone();
+                    new_sig.value.lock().unwrap()[index] = value;
+                }
+            });
+
+            signal.push_derived(react_fn, &new_signal);
+        }
+
+        result_new_signal
+    }
+}
+
+impl<'a, T: Send + Sync + 'a> SignalSync<'a, Vec<T>> {
+    /// Map the elements of the signal in parallel
+    ///
+    /// This creates a new signal holding `f` applied to each element, like mapping the vector
+    /// with `map`, but on each change the elements are split into one chunk per available core
+    /// and the chunks are mapped on scoped threads. The results are collected back in the order
+    /// of the elements, so the output is the same as a sequential map whatever the scheduling;
+    /// only the order in which `f` runs on the elements varies. This pays off when `f` is
+    /// CPU-bound and the vector is large, as spawning the threads costs more than mapping a few
+    /// cheap elements. It uses standard threads only, so no feature is required. A panic in `f`
+    /// is raised again on the thread running the reaction.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let numbers = SignalSync::new((0..1000u64).collect::<Vec<_>>());
+    /// let squares = numbers.map_parallel(|x| x * x);
+    /// assert_eq!(squares.get()[999], 998001);
+    /// ```
+    pub fn map_parallel<U, F>(&self, f: F) -> SignalSync<'a, Vec<U>>
+    where
+        U: Send + Sync + 'a,
+        F: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        self.map(move |items| {
+            let threads = thread::available_parallelism().map_or(1, |n| n.get());
+            map_in_chunks(items, threads, &f)
+        })
+    }
+
+    /// Group the elements of the signal by a key
+    ///
+    /// This creates a new signal holding a map from each key to the elements that produced it,
+    /// in their original order. The grouping is recomputed whenever the source signal changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let words = SignalSync::new(vec!["apple", "avocado", "banana"]);
+    /// let groups = words.group_by(|w| w.chars().next().unwrap());
+    /// let _observer = groups.map(|g| println!("a: {:?}, b: {:?}", g[&'a'], g[&'b']));
+    /// words.send(vec!["blueberry", "apricot"]); // prints "a: ["apricot"], b: ["blueberry"]"
+    /// ```
+    pub fn group_by<K, F>(&self, key: F) -> SignalSync<'a, HashMap<K, Vec<T>>>
+    where
+        K: Eq + Hash + Send + Sync + 'a,
+        T: Clone,
+        F: Fn(&T) -> K + Send + Sync + 'a,
+    {
+        self.map(move |items| {
+            let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+            for item in items {
+                groups.entry(key(item)).or_default().push(item.clone());
+            }
+            groups
+        })
+    }
+
+    /// Diff the elements of the signal by key on every change
+    ///
+    /// This creates a new signal holding the list of changes, as `ListChange`s, that turn the
+    /// previous vector into the new one, instead of the vector itself. Elements are identified
+    /// across changes by `key`, which should be unique within the vector, so a reordered element
+    /// is reported as a `Move` rather than a removal and an insertion. The initial value of the
+    /// new signal inserts every element.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let todos = SignalSync::new(vec![(1, "write"), (2, "test"), (3, "ship")]);
+    /// let changes = todos.diff_keyed(|(id, _)| *id);
+    /// let _observer = changes.map(|c| println!("{:?}", c));
+    /// todos.send(vec![(3, "ship"), (1, "write"), (2, "test")]);
+    /// // prints "[Move { key: 3, from: 2, to: 0 }]"
+    /// ```
+    pub fn diff_keyed<K, F>(&self, key: F) -> SignalSync<'a, Vec<ListChange<K, T>>>
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'a,
+        T: Clone + PartialEq,
+        F: Fn(&T) -> K + Send + Sync + 'a,
+    {
+        let previous: Mutex<Vec<(K, T)>> = Mutex::new(Vec::new());
+        self.map(move |items| {
+            let next: Vec<(K, T)> = items.iter().map(|item| (key(item), item.clone())).collect();
+            let mut previous = previous.lock().unwrap();
+            let changes = diff_keyed_changes(&previous, &next);
+            *previous = next;
+            changes
+        })
+    }
+
+    /// Map the elements of the signal by key, remapping only the elements that changed
+    ///
+    /// This creates a new signal holding `map` applied to each element, like mapping the vector,
+    /// but the results are cached by `key` across changes: on each change, `map` only runs for
+    /// the elements whose key is new or whose value differs from the last time, and the cached
+    /// result is reused for the others, whatever their new position. This saves an expensive
+    /// `map` when a long list changes one element at a time. Keys should be unique within the
+    /// vector; the cache only keeps the keys of the current vector.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let todos = SignalSync::new(vec![(1, "write"), (2, "test")]);
+    /// let rows = todos.map_keyed(|(id, _)| *id, |(id, text)| {
+    ///     println!("rendering {}", id);
+    ///     format!("<li>{}</li>", text)
+    /// }); // prints "rendering 1" and "rendering 2"
+    /// todos.send(vec![(2, "test"), (1, "write"), (3, "ship")]); // prints "rendering 3"
+    /// ```
+    pub fn map_keyed<K, U, KeyFn, MapFn>(&self, key: KeyFn, map: MapFn) -> SignalSync<'a, Vec<U>>
+    where
+        K: Eq + Hash + Send + Sync + 'a,
+        U: Clone + Send + Sync + 'a,
+        T: Clone + PartialEq,
+        KeyFn: Fn(&T) -> K + Send + Sync + 'a,
+        MapFn: Fn(&T) -> U + Send + Sync + 'a,
+    {
+        let cache: Mutex<HashMap<K, (T, U)>> = Mutex::new(HashMap::new());
+        self.map(move |items| {
+            let mut previous = std::mem::take(&mut *cache.lock().unwrap());
+            let mut next_cache = HashMap::with_capacity(items.len());
+            let mapped = items
+                .iter()
+                .map(|item| {
+                    let k = key(item);
+                    let result = match previous.remove(&k) {
+                        Some((cached_item, result)) if cached_item == *item => result,
+                        _ => map(item),
+                    };
+                    next_cache.insert(k, (item.clone(), result.clone()));
+                    result
+                })
+                .collect();
+            *cache.lock().unwrap() = next_cache;
+            mapped
+        })
+    }
+
+    /// Bind each element of the signal to its own child signal, in both directions
+    ///
+    /// This creates a signal holding one child signal per element. Sending to a child writes
+    /// the value back into the vector at the child's index, and changes of the vector are
+    /// pushed down to the children. When the length of the vector changes, children are
+    /// added or removed at the end and the list of children is updated; the child at a given
+    /// index stays the same as long as that index exists. A child that was removed is unbound
+    /// and sending to it no longer affects the vector. A direct `send` to the vector wins over
+    /// child edits made in the same batch. Children must not be sent to while the list of
+    /// children is locked, e.g. from a closure mapping over it, as the write back needs the lock.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let items = SignalSync::new(vec![1, 2, 3]);
+    /// let children = items.bind_elements();
+    /// let _observer = children.map(|c| println!("{} children", c.len()));
+    /// items.send(vec![4, 5]); // prints "2 children"
+    /// ```
+    pub fn bind_elements(&self) -> SignalSync<'a, Vec<SignalSync<'a, T>>>
+    where
+        T: Clone,
+    {
+        let children = SignalSync::new(Vec::new());
+        let parent_weak = Arc::downgrade(&self.0);
+        let children_weak = Arc::downgrade(&children.0);
+        let from_child = Arc::new(AtomicBool::new(false));
+
+        let initial = self
+            .0
+            .value
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                Self::bind_element(
+                    &parent_weak,
+                    &children_weak,
+                    &from_child,
+                    index,
+                    value.clone(),
+                )
+            })
+            .collect();
+        *children.0.value.lock().unwrap() = initial;
+
+        let react_fn = Box::new(move || {
+            // A single element was written back by a child, the others are unchanged
+            if from_child.swap(false, Ordering::AcqRel) {
+                return None;
+            }
+            let parent = parent_weak.upgrade()?;
+            let list = children_weak.upgrade()?;
+
+            let values = parent.value.lock().unwrap();
+            let mut guards = Vec::new();
+            let mut bound = list.value.lock().unwrap();
+            let resized = bound.len() != values.len();
+            bound.truncate(values.len());
+            for (index, value) in values.iter().enumerate() {
+                if let Some(child) = bound.get(index) {
+                    child.modify(|v| *v = value.clone());
+                    guards.push(child.guard());
+                } else {
+                    bound.push(Self::bind_element(
+                        &parent_weak,
+                        &children_weak,
+                        &from_child,
+                        index,
+                        value.clone(),
+                    ));
+                }
+            }
+            drop(bound);
+            drop(values);
+            if resized {
+                guards.push(SignalSync(list).guard());
+            }
+            guards.into_iter().reduce(SignalGuardSync::and)
+        });
+
+        self.0
+            .react_fns
+            .write()
+            .unwrap()
+            .push_forwarding_owned(react_fn, WeakSignalRefSync::new(&children));
+        children
+    }
+
+    /// Create a child signal that writes back into the vector at `index`
+    fn bind_element(
+        parent: &Weak<SignalInnerSync<'a, Vec<T>>>,
+        list: &Weak<SignalInnerSync<'a, Vec<SignalSync<'a, T>>>>,
+        from_child: &Arc<AtomicBool>,
+        index: usize,
+        value: T,
+    ) -> SignalSync<'a, T>
+    where
+        T: Clone,
+    {
+        let child = SignalSync::new(value);
+        let child_weak = Arc::downgrade(&child.0);
+        let parent = parent.clone();
+        let list = list.clone();
+        let from_child = from_child.clone();
+
+        let react_fn = Box::new(move || {
+            let child = child_weak.upgrade()?;
+            let parent = parent.upgrade()?;
+            let list = list.upgrade()?;
+            if !child.explicitly_modified.load(Ordering::Acquire)
+                || parent.explicitly_modified.load(Ordering::Acquire)
+            {
+                return None;
+            }
+            // Only a child that is still bound at its index writes back
+            let still_bound = list
+                .value
+                .lock()
+                .unwrap()
+                .get(index)
+                .is_some_and(|bound| Arc::ptr_eq(&bound.0, &child));
+            if !still_bound {
+                return None;
+            }
+            let value = child.value.lock().unwrap().clone();
+            from_child.store(true, Ordering::Release);
+            Some(SignalSync(parent).send_with(|items| items[index] = value))
+        });
+
+        child.0.react_fns.write().unwrap().push_forwarding(react_fn);
+        child
+    }
+}
+
+impl<'a, T: Send + Sync + 'a, E: Send + Sync + 'a> SignalSync<'a, Result<T, E>> {
+    /// Split the signal into one signal for the `Ok` values and one for the `Err` values
+    ///
+    /// Each of the new signals holds the latest value of its variant and only emits when the
+    /// source holds that variant; it keeps its previous value while the source holds the other
+    /// variant. A signal holds `None` until the source first holds its variant.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let parsed = SignalSync::new("1".parse::<i32>());
+    /// let (ok, err) = parsed.split_result();
+    /// let _ok = ok.map(|v| println!("ok: {:?}", v)); // prints "ok: Some(1)"
+    /// let _err = err.map(|e| println!("err: {:?}", e)); // prints "err: None"
+    /// parsed.send("x".parse::<i32>()); // prints "err: Some(ParseIntError { kind: InvalidDigit })" only
+    /// ```
+    pub fn split_result(&self) -> (SignalSync<'a, Option<T>>, SignalSync<'a, Option<E>>)
+    where
+        T: Clone,
+        E: Clone,
+    {
+        let (ok_signal, err_signal) = match &*self.0.value.lock().unwrap() {
+            Ok(value) => (SignalSync::new(Some(value.clone())), SignalSync::new(None)),
+            Err(error) => (SignalSync::new(None), SignalSync::new(Some(error.clone()))),
+        };
+        let ok_weak = Arc::downgrade(&ok_signal.0);
+        let err_weak = Arc::downgrade(&err_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            let src = source_weak.upgrade()?;
+            let value = src.value.lock().unwrap();
+            match &*value {
+                Ok(value) => {
+                    let ok = ok_weak.upgrade()?;
+                    *ok.value.lock().unwrap() = Some(value.clone());
+                    Some(SignalSync(ok).guard())
+                }
+                Err(error) => {
+                    let err = err_weak.upgrade()?;
+                    *err.value.lock().unwrap() = Some(error.clone());
+                    Some(SignalSync(err).guard())
+                }
+            }
+        });
+
+        self.0.react_fns.write().unwrap().push_forwarding(react_fn);
+        (ok_signal, err_signal)
+    }
+
+    /// Create a signal that only takes the `Ok` values of the current signal
+    ///
+    /// The new signal is updated, and its observers notified, only when the current signal
+    /// holds an `Ok` value; an `Err` is skipped and the new signal keeps the last successful
+    /// value. It holds `None` until the current signal first holds an `Ok` value. Together with
+    /// `try_map`, this keeps validation pipelines reactive while errors go to the error sink.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let input = SignalSync::new(String::from("1"));
+    /// let parsed = input.try_map(|s| s.parse::<i32>());
+    /// let valid = parsed.filter_ok();
+    /// let _observer = valid.map(|v| println!("valid: {:?}", v)); // prints "valid: Some(1)"
+    /// input.send(String::from("x")); // prints nothing
+    /// input.send(String::from("2")); // prints "valid: Some(2)"
+    /// ```
+    pub fn filter_ok(&self) -> SignalSync<'a, Option<T>>
+    where
+        T: Clone,
+    {
+        let new_signal = SignalSync::new(self.0.value.lock().unwrap().as_ref().ok().cloned());
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+
+        let react_fn = Box::new(move || {
+            let new_sig = new_signal_weak.upgrade()?;
+            let src = source_weak.upgrade()?;
+            if new_sig.explicitly_modified.load(Ordering::Acquire) {
+                return None;
+            }
+            let value = src.value.lock().unwrap().as_ref().ok().cloned()?;
+            *new_sig.value.lock().unwrap() = Some(value);
+            Some(SignalSync(new_sig).guard())
+        });
+
+        self.push_forwarded(react_fn, &new_signal);
+        new_signal
+    }
+}
+
+impl<'a, A: Send + Sync + 'a, B: Send + Sync + 'a> SignalSync<'a, (A, B)> {
+    /// Split a signal of pairs into one signal per component
+    ///
+    /// This is the inverse of `combine`: each of the new signals holds its component of the
+    /// current pair and is updated whenever the current signal changes, even when only the
+    /// other component did. Chain `distinct` on a half to skip the unchanged values.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let pair = SignalSync::new((1, "one"));
+    /// let (number, name) = pair.split();
+    /// let _number = number.map(|n| println!("number: {}", n)); // prints "number: 1"
+    /// let _name = name.map(|s| println!("name: {}", s)); // prints "name: one"
+    /// pair.send((2, "two")); // prints "number: 2" and "name: two"
+    /// ```
+    pub fn split(&self) -> (SignalSync<'a, A>, SignalSync<'a, B>)
+    where
+        A: Clone,
+        B: Clone,
+    {
+        (self.map(|(a, _)| a.clone()), self.map(|(_, b)| b.clone()))
+    }
+}
+
+impl<'a, T: Send + Sync + 'a> SignalSync<'a, SignalSync<'a, T>> {
+    /// Flatten a signal of signals into a signal mirroring the current inner signal
+    ///
+    /// This creates a new signal that holds the value of the inner signal currently held by
+    /// this signal, and follows its changes. When this signal switches to another inner signal,
+    /// the new signal takes its value and unsubscribes from the previous inner signal, so stale
+    /// updates of the previous inner signal don't leak through.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use agility::signal_sync::SignalSync;
+    /// let a = SignalSync::new(1);
+    /// let b = SignalSync::new(10);
+    /// let current = SignalSync::new(a.clone());
+    /// let flat = current.flatten();
+    /// let _observer = flat.map(|x| println!("flat: {}", x)); // prints "flat: 1"
+    /// a.send(2); // prints "flat: 2"
+    /// current.send(b.clone()); // prints "flat: 10"
+    /// a.send(3); // prints nothing
+    /// ```
+    pub fn flatten(&self) -> SignalSync<'a, T>
+    where
+        T: Clone,
+    {
+        let inner = self.0.value.lock().unwrap().clone();
+        let new_signal = SignalSync::new(inner.0.value.lock().unwrap().clone());
+        let mut current = None;
+        SignalSync::switch_inner(&mut current, &new_signal, inner);
+        let subscription = Mutex::new(current);
+
+        let new_signal_weak = Arc::downgrade(&new_signal.0);
+        let source_weak = Arc::downgrade(&self.0);
+        let react_fn = Box::new(move || {
+            if let Some(new_sig) = new_signal_weak.upgrade()
+                && !new_sig.explicitly_modified.load(Ordering::Acquire)
+                && let Some(src) = source_weak.upgrade()
+            {
+                let inner = src.value.lock().unwrap().clone();
+                SignalSync::switch_inner(
+                    &mut subscription.lock().unwrap(),
+                    &SignalSync(new_sig),
+                    inner,
+                );
+            }
+        });
+        self.push_derived(react_fn, &new_signal);
+
+        new_signal
+    }
+}
+
+impl<'a, T: Send + Sync + 'a> SignalExtSync<'a> for SignalSync<'a, T> {
+    fn react(&self) -> Vec<SignalGuardInnerSync<'a>> {
+        if self.0.frozen.load(Ordering::Acquire) > 0 {
+            self.0.missed_while_frozen.store(true, Ordering::Release);
+            return Vec::new();
+        }
+        trace(TraceEvent::React(self.addr()));
+        #[cfg(test)]
+        self.0
+            .reactions
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        // Eager reactions already ran if the value was modified directly
+        if !self.0.eagerly_reacted.swap(false, Ordering::AcqRel) {
+            let eager_react_fns = self.0.eager_react_fns.read().unwrap().snapshot();
+            eager_react_fns.iter().for_each(|react_fn| {
+                self.run_isolated(react_fn);
+            });
+        }
+        let react_fns = self.0.react_fns.read().unwrap().snapshot();
+        react_fns
+            .iter()
+            .filter_map(|react_fn| self.run_isolated(react_fn))
+            .flat_map(|mut guard| std::mem::take(&mut guard.0))
+            .collect()
+    }
+    fn guard(&self) -> SignalGuardSync<'a> {
+        // Reuse the cached buffer if enabled; a reentrant send finds it taken and allocates
+        let cached = self
+            .0
+            .guard_scratch
+            .lock()
+            .unwrap()
+            .as_mut()
+            .map(std::mem::take);
+        let home = cached.is_some().then(|| self.clone_box());
+        let mut result = cached.unwrap_or_default();
+        self.collect_guards(&mut result);
+        SignalGuardSync(result, home)
+    }
+    fn clone_box(&self) -> Box<dyn SignalExtSync<'a> + 'a> {
+        Box::new(SignalSync(Arc::clone(&self.0)))
+    }
+    fn decrease_dirty(&self) {
+        self.0.dirty.fetch_sub(1, Ordering::SeqCst);
+    }
+    fn get_dirty(&self) -> isize {
+        self.0.dirty.load(Ordering::SeqCst)
+    }
+    fn reset_explicitly_modified(&self) {
+        self.0.explicitly_modified.store(false, Ordering::Release);
+    }
+    fn clear_poison(&self) {
+        self.0.value.clear_poison();
+    }
+    fn return_guard_buffer(&self, mut buffer: Vec<SignalGuardInnerSync<'a>>) {
+        buffer.clear();
+        if let Some(scratch) = self.0.guard_scratch.lock().unwrap().as_mut()
+            && buffer.capacity() > scratch.capacity()
+        {
+            *scratch = buffer;
+        }
+    }
+    fn write_dot(&self, graph: &mut DotGraph) -> usize {
+        let (id, new) = graph.node(
+            self.addr(),
+            *self.0.label.read().unwrap(),
+            self.get_dirty() != 0,
+            self.0.explicitly_modified.load(Ordering::Acquire),
+        );
+        if !new {
+            return id;
+        }
+        // Upgrade first, so no lock is held while the rest of the graph is walked
+        let successors: Vec<_> = self
+            .0
+            .successors
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(WeakSignalRefSync::upgrade)
+            .collect();
+        for signal in successors {
+            let to = signal.write_dot(graph);
+            graph.edge(id, to, false);
+        }
+        let predecessors: Vec<_> = self
+            .0
+            .predecessors
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(WeakSignalRefSync::upgrade)
+            .collect();
+        for signal in predecessors {
+            let to = signal.write_dot(graph);
+            graph.edge(id, to, true);
+        }
+        id
+    }
+    fn push_reaction(
+        &self,
+        react_fn: Box<dyn Fn() + Send + Sync + 'a>,
+        owner: WeakSignalRefSync<'a>,
+    ) -> ReactionId {
+        self.0
+            .react_fns
+            .write()
+            .unwrap()
+            .push_owned(react_fn, owner)
+    }
+    fn push_successor(&self, successor: WeakSignalRefSync<'a>) {
+        self.0.successors.write().unwrap().push(successor);
+    }
+    fn collect_guards_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInnerSync<'a>>,
+        visited: &mut HashSet<usize>,
+    ) {
+        // A signal reached through several paths, like the bottom of a diamond, is collected once;
+        // this also cuts cycles, which settle after a single pass
+        if !visited.insert(self.addr()) {
+            return;
+        }
+        self.mark_dirty();
+        // A frozen signal holds back its successors as well, they are updated once it thaws
+        if self.0.frozen.load(Ordering::Acquire) > 0 {
+            result.push(SignalGuardInnerSync(self.clone_box(), false));
+            return;
+        }
+        let mut successors = Vec::new();
+        let pruned = self.collect_and_iterate(&self.0.successors, |signal| {
+            successors.push(signal.clone_box());
+        });
+        if pruned {
+            // The reactions that fed the dropped successors are dead as well
+            self.0.react_fns.write().unwrap().retain_alive();
+            self.0.eager_react_fns.write().unwrap().retain_alive();
+        }
+        // Post-order, reversed by `collect_guards`; visit successors backwards to keep their order
+        for signal in successors.iter().rev() {
+            signal.collect_guards_recursive(result, visited);
+        }
+        result.push(SignalGuardInnerSync(self.clone_box(), false));
+    }
+    fn collect_predecessors_recursive(
+        &self,
+        result: &mut Vec<SignalGuardInnerSync<'a>>,
+        visited: &mut HashSet<usize>,
+    ) {
+        if !visited.insert(self.addr()) {
+            return;
+        }
+        self.mark_dirty();
+        result.push(SignalGuardInnerSync(self.clone_box(), true));
+        // Collect predecessors last so they drop last (react last)
+        self.collect_and_iterate(&self.0.predecessors, |signal| {
+            signal.collect_predecessors_recursive(result, visited);
+        });
+    }
+}
+
+impl<T> Clone for SignalSync<'_, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, T> AsRef<SignalSync<'a, T>> for SignalSync<'a, T> {
+    fn as_ref(&self) -> &SignalSync<'a, T> {
+        self
+    }
+}
+
+/// Creates a signal holding the default value of `T`
+impl<'a, T: Default + Send + Sync + 'a> Default for SignalSync<'a, T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Creates a signal holding the given value, like `SignalSync::new`
+impl<'a, T: Send + Sync + 'a> From<T> for SignalSync<'a, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Compares the current values of two signals
+///
+/// This compares snapshots of the values, not the identity of the signals: two unrelated
+/// signals holding equal values are equal, and may stop being so after a send. Both values are
+/// locked in a fixed order, so comparing a signal with itself or comparing the same pair from two
+/// threads at once cannot deadlock; comparing from inside a reaction of either signal can.
+impl<T: PartialEq + Send + Sync> PartialEq for SignalSync<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            // Not simply `true`, so values unequal to themselves such as NaN stay unequal
+            let value = self.0.value.lock().unwrap();
+            return PartialEq::eq(&*value, &*value);
+        }
+        let (first, second) = if Arc::as_ptr(&self.0) < Arc::as_ptr(&other.0) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let first = first.0.value.lock().unwrap();
+        let second = second.0.value.lock().unwrap();
+        *first == *second
+    }
+}
+
+/// Formats the label, the current value and the wiring of the signal, for diagnostics
+///
+/// Locks are only tried, so formatting never blocks: a value locked elsewhere, for example by
+/// a reaction in progress, is shown as `<locked>`.
+impl<T: fmt::Debug + Send + Sync> fmt::Debug for SignalSync<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("SignalSync");
+        if let Some(label) = self.0.label.try_read().ok().and_then(|label| *label) {
+            debug.field("label", &label);
+        }
+        match self.0.value.try_lock() {
+            Ok(value) => debug.field("value", &*value),
+            Err(_) => debug.field("value", &format_args!("<locked>")),
+        };
+        let alive = |refs: &RwLock<Vec<WeakSignalRefSync<'_>>>| {
+            refs.try_read()
+                .map(|refs| refs.iter().filter(|s| s.is_alive()).count())
+                .ok()
+        };
+        debug
+            .field("successors", &alive(&self.0.successors))
+            .field("predecessors", &alive(&self.0.predecessors))
+            .field("dirty", &self.0.dirty.load(Ordering::Acquire))
+            .finish()
+    }
+}
+
+/// Formats the current value of the signal, or `<locked>` if it is locked elsewhere
+impl<T: fmt::Display + Send + Sync> fmt::Display for SignalSync<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.value.try_lock() {
+            Ok(value) => value.fmt(f),
+            Err(_) => f.write_str("<locked>"),
+        }
+    }
+}
+
+/// Implement arithmetic operators on references to signals
+///
+/// The result stays equal to the operation applied to both operands, like `combine_map`, but
+/// is strongly referenced by its operands, like `and`, so expressions such as
+/// `&(&a + &b) * &c` keep working after the intermediate signals are dropped.
+macro_rules! impl_signal_ops {
+    ($($op:ident::$method:ident),*) => {$(
+        impl<'a, T> ops::$op for &SignalSync<'a, T>
+        where
+            T: Copy + Send + Sync + ops::$op<Output = T> + 'a,
+        {
+            type Output = SignalSync<'a, T>;
+
+            fn $method(self, rhs: Self) -> SignalSync<'a, T> {
+                self.combine_map_ref::<T, T, _, StrongRefStrategySync>(rhs, |x, y| ops::$op::$method(*x, *y))
+            }
+        }
+    )*};
+}
+
+impl_signal_ops!(Add::add, Sub::sub, Mul::mul, Div::div);
+
+#[cfg(test)]
+#[allow(clippy::unnecessary_operation)]
+mod tests {
+
+    use crate::api::LiftIntoSync;
+
+    use super::*;
+
+    #[test]
+    fn test_signal_sync_basic() {
+        let a = SignalSync::new(0);
+        let _a = a.map(|x| println!("a changed: {}", x));
+        (a.send(100), a.send(5));
+    }
+
+    #[test]
+    fn test_signal_sync_map() {
+        let a = SignalSync::new(0);
+        let b = a.map(|x| x * 2);
+        let _b = b.map(|x| println!("b changed: {}", x));
+        drop(b);
+        a.send(100);
+    }
+
+    #[test]
+    fn test_signal_sync_combine() {
+        let a = SignalSync::new(0);
+        let b = a.map(|x| x * 2);
+        let _ab = a
+            .and(&b)
+            .map(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
+        (a.send(5), a.send(100));
+    }
+
+    #[test]
+    fn test_signal5() {
+        let result = SignalSync::new(42);
+        let source1 = result.contramap(|x| x + 1);
+        let source2 = source1.contramap(|x| x * 2);
+
+        let _observer_1 = result.map(|x| println!("result changed: {}", x));
+        let _observer_2 = source1.map(|x| println!("source1 changed: {}", x));
+        let _observer_3 = source2.map(|x| println!("source2 changed: {}", x));
+
+        println!("--- Sending to source1 ---");
+        source1.send(100);
+        println!("--- Sending to source2 ---");
+        source2.send(200);
+        println!("--- Sending to source1 and source2 ---");
+        (source1.send(300), source2.send(400));
+    }
+
+    #[test]
+    fn test_depend_sync() {
+        let mut a = SignalSync::new(10);
+        let mut b = SignalSync::new(10);
+        let c = SignalSync::new(10);
+
+        let _observer_a = a.map(|x| println!("a changed: {}", x));
+        let _observer_b = b.map(|x| println!("b changed: {}", x));
+        let _observer_c = c.map(|x| println!("c changed: {}", x));
+
+        b = c.depend(b);
+        a = b.depend(a);
+
+        (a.send(42), b.send(88));
+    }
+
+    #[test]
+    fn test_group_by_sync() {
+        let numbers = SignalSync::new(vec![1, 2, 3, 4]);
+        let groups = numbers.group_by(|x| x % 2 == 0);
+        assert_eq!(groups.0.value.lock().unwrap()[&true], vec![2, 4]);
+
+        numbers.send(vec![5, 7, 8]);
+        assert_eq!(groups.0.value.lock().unwrap()[&true], vec![8]);
+        assert_eq!(groups.0.value.lock().unwrap()[&false], vec![5, 7]);
+    }
+
+    #[test]
+    fn test_tee_shares_computation_sync() {
+        use std::sync::atomic::AtomicUsize;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        let a = SignalSync::new(1);
+        let shared = a.map(move |x| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            x * 10
+        });
+        let branches = shared.tee(3);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        a.send(2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        for branch in &branches {
+            assert_eq!(*branch.0.value.lock().unwrap(), 20);
+        }
+    }
+
+    #[test]
+    fn test_map_eager_sync() {
+        let seen_eager = Arc::new(Mutex::new(Vec::new()));
+        let seen_batched = Arc::new(Mutex::new(Vec::new()));
+        let a = SignalSync::new(0);
+        let eager_log = seen_eager.clone();
+        let _eager = a.map_eager(move |x| eager_log.lock().unwrap().push(*x));
+        let batched_log = seen_batched.clone();
+        let _batched = a.map(move |x| batched_log.lock().unwrap().push(*x));
+
+        (a.send(1), a.send(2), a.send(3));
+        assert_eq!(*seen_eager.lock().unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(*seen_batched.lock().unwrap(), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_label_sync() {
+        let a = SignalSync::new(0);
+        assert_eq!(a.label(), None);
+        let a = a.with_label("a");
+        let b = a.clone();
+        assert_eq!(b.label(), Some("a"));
+    }
+
+    #[test]
+    fn test_contramap_promap_with_sync() {
+        struct Meters(u32);
+
+        let centimeters = SignalSync::new(300);
+        let meters = centimeters.contramap_with(Meters(3), |m| m.0 * 100);
+        assert_eq!(meters.0.value.lock().unwrap().0, 3);
+        meters.send(Meters(5));
+        assert_eq!(*centimeters.0.value.lock().unwrap(), 500);
+
+        let doubled = centimeters.promap_with(Meters(10), |cm| Meters(cm * 2), |m| m.0 / 2);
+        centimeters.send(40);
+        assert_eq!(doubled.0.value.lock().unwrap().0, 80);
+        doubled.send(Meters(60));
+        assert_eq!(*centimeters.0.value.lock().unwrap(), 30);
+    }
+
+    #[test]
+    fn test_reduce_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(5);
+        let sum = SignalSync::reduce(vec![a.clone(), b.clone()], 0, |acc, x| acc + x);
+        assert_eq!(*sum.0.value.lock().unwrap(), 6);
+
+        (a.send(10), b.send(20));
+        assert_eq!(*sum.0.value.lock().unwrap(), 30);
+    }
+
+    #[test]
+    fn test_observe_weak_sync() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let a = SignalSync::new(0);
+        let log = seen.clone();
+        let observer = a.observe_weak(move |x| log.lock().unwrap().push(*x));
+        assert_eq!(Arc::strong_count(&a.0), 1);
+
+        a.send(1);
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+
+        drop(observer);
+        assert!(a.0.react_fns.read().unwrap().snapshot().is_empty());
+        a.send(2);
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_map_copied_sync() {
+        let a = SignalSync::new(1);
+        let a_clone = a.clone();
+        // The source is not locked while the closure runs
+        let b = a.map_copied(move |x| x + *a_clone.0.value.lock().unwrap());
+        assert_eq!(*b.0.value.lock().unwrap(), 2);
+
+        a.send(21);
+        assert_eq!(*b.0.value.lock().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_event_log_sync() {
+        let a = SignalSync::new(0);
+        let log = a.event_log();
+        let bounded = a.event_log_bounded(1);
+
+        a.send(1);
+        a.send(2);
+        assert_eq!(*log.0.value.lock().unwrap(), vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(*bounded.0.value.lock().unwrap(), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_map_forward_sync() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let sum = SignalSync::reduce(vec![a.clone(), b.clone()], 0, |acc, x| acc + x);
+
+        let target = b.clone();
+        let _forward = a.map_forward(move |x| ((), Some(target.send(x * 10))));
+        let log = seen.clone();
+        let _observer_b = b.map(move |x| log.lock().unwrap().push(format!("b: {}", x)));
+        let log = seen.clone();
+        let _observer_a = a.map(move |x| log.lock().unwrap().push(format!("a: {}", x)));
+        let log = seen.clone();
+        let _observer_sum = sum.map(move |x| log.lock().unwrap().push(format!("sum: {}", x)));
+        seen.lock().unwrap().clear();
+
+        a.send(1);
+        assert_eq!(*b.0.value.lock().unwrap(), 10);
+        assert_eq!(*sum.0.value.lock().unwrap(), 11);
+        assert_eq!(*seen.lock().unwrap(), vec!["a: 1", "b: 10", "sum: 11"]);
+    }
+
+    #[test]
+    fn test_fork_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x * 10);
+        let draft = a.fork();
+        let draft_b = draft.map(|x| x + 1);
+        assert_eq!(*draft.0.value.lock().unwrap(), 1);
+
+        draft.send(2);
+        assert_eq!(*a.0.value.lock().unwrap(), 1);
+        assert_eq!(*b.0.value.lock().unwrap(), 10);
+        assert_eq!(*draft_b.0.value.lock().unwrap(), 3);
+
+        a.send(3);
+        assert_eq!(*b.0.value.lock().unwrap(), 30);
+        assert_eq!(*draft.0.value.lock().unwrap(), 2);
+        assert_eq!(*draft_b.0.value.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_erase_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x * 10);
+        let signals: Vec<BoxedSignalSync> =
+            vec![a.erase(), SignalSync::new(String::from("one")).erase()];
+        assert!(signals[0].is::<i32>());
+        assert_eq!(signals[1].type_name(), "alloc::string::String");
+        assert!(signals[1].downcast_ref::<i32>().is_none());
+
+        signals[0].downcast_ref::<i32>().unwrap().send(2);
+        assert_eq!(*b.0.value.lock().unwrap(), 20);
+
+        let erased = signals[1].clone();
+        let Err(erased) = erased.downcast::<i32>() else {
+            panic!("downcast to the wrong type succeeded");
+        };
+        let Ok(text) = erased.downcast::<String>() else {
+            panic!("downcast to the erased type failed");
+        };
+        text.send(String::from("two"));
+        assert_eq!(
+            *signals[1]
+                .downcast_ref::<String>()
+                .unwrap()
+                .0
+                .value
+                .lock()
+                .unwrap(),
+            "two"
+        );
+    }
+
+    #[test]
+    fn test_sample_when_sync() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let value = SignalSync::new(0);
+        let clock = SignalSync::new(0);
+        let sampled = value.sample_when(&clock);
+        let log = seen.clone();
+        let _observer = sampled.map(move |x| log.lock().unwrap().push(*x));
+
+        value.send(1);
+        value.send(2);
+        assert_eq!(*sampled.0.value.lock().unwrap(), 0);
+        clock.send(1);
+        assert_eq!(*sampled.0.value.lock().unwrap(), 2);
+        // `value` did not change between the ticks, so the same value is emitted again
+        clock.send(2);
+        (value.send(3), clock.send(3));
+        assert_eq!(*seen.lock().unwrap(), vec![0, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_try_map_sync() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let input = SignalSync::new(String::from("1"));
+        let doubled = input.map(|s| s.repeat(2));
+        let log = errors.clone();
+        doubled.on_reaction_error(move |e| log.lock().unwrap().push(e.message().to_string()));
+        let parsed = doubled.try_map(|s| s.parse::<i32>());
+        assert_eq!(*parsed.0.value.lock().unwrap(), Ok(11));
+
+        input.send(String::from("2"));
+        assert_eq!(*parsed.0.value.lock().unwrap(), Ok(22));
+        input.send(String::from("x"));
+        assert!(parsed.0.value.lock().unwrap().is_err());
+        assert_eq!(
+            *errors.lock().unwrap(),
+            vec!["invalid digit found in string"]
+        );
+
+        // a handler registered upstream sees errors of the whole subgraph
+        let log = errors.clone();
+        input.on_reaction_error(move |e| log.lock().unwrap().push(e.to_string()));
+        input.send(String::from("y"));
+        assert_eq!(errors.lock().unwrap().len(), 3);
+        assert_eq!(
+            errors.lock().unwrap()[2],
+            "reaction error: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_bind_elements_sync() {
+        let items = SignalSync::new(vec![1, 2, 3]);
+        let children = items.bind_elements();
+        let child_updates = Arc::new(Mutex::new(0));
+        let counter = child_updates.clone();
+        let _child_observer = children.0.value.lock().unwrap()[0].map(move |_| {
+            *counter.lock().unwrap() += 1;
+        });
+        let lengths = children.map(|c| c.len());
+
+        // Editing a child writes back without touching the other children
+        let child = children.0.value.lock().unwrap()[1].clone();
+        child.send(20);
+        assert_eq!(*items.0.value.lock().unwrap(), vec![1, 20, 3]);
+        assert_eq!(*child_updates.lock().unwrap(), 1);
+
+        // Editing the vector updates the children and resizes them
+        items.send(vec![5, 6]);
+        assert_eq!(*lengths.0.value.lock().unwrap(), 2);
+        let first = children.0.value.lock().unwrap()[0].clone();
+        assert_eq!(*first.0.value.lock().unwrap(), 5);
+        assert_eq!(*child.0.value.lock().unwrap(), 6);
+        assert_eq!(*child_updates.lock().unwrap(), 2);
+
+        let removed = child.clone();
+        items.send(vec![7]);
+        removed.send(60);
+        assert_eq!(*items.0.value.lock().unwrap(), vec![7]);
+
+        items.send(vec![7, 8, 9]);
+        assert_eq!(*lengths.0.value.lock().unwrap(), 3);
+        let added = children.0.value.lock().unwrap()[2].clone();
+        (added.send(90), first.send(70));
+        assert_eq!(*items.0.value.lock().unwrap(), vec![70, 8, 90]);
+    }
+
+    #[test]
+    fn test_with_eq_sync() {
+        #[derive(Clone)]
+        struct Reading {
+            value: f64,
+            timestamp: u64,
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let reading = SignalSync::new(Reading {
+            value: 1.0,
+            timestamp: 0,
+        })
+        .with_eq(|a, b| (a.value - b.value).abs() < 1e-9);
+        let deduped = reading.dedupe();
+        let log = seen.clone();
+        let _observer = deduped.map(move |r| log.lock().unwrap().push(r.timestamp));
+        let sends = Arc::new(Mutex::new(0));
+        let counter = sends.clone();
+        let _sends = reading.map(move |_| *counter.lock().unwrap() += 1);
+
+        reading.send(Reading {
+            value: 1.0 + 1e-12,
+            timestamp: 1,
+        });
+        assert!(
+            reading
+                .send_if_changed(Reading {
+                    value: 1.0,
+                    timestamp: 2,
+                })
+                .is_none()
+        );
+        reading.send_if_changed(Reading {
+            value: 2.0,
+            timestamp: 3,
+        });
+        assert_eq!(*seen.lock().unwrap(), vec![0, 3]);
+        assert_eq!(*sends.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_split_result_sync() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let result: SignalSync<Result<i32, String>> = SignalSync::new(Err(String::from("empty")));
+        let (ok, err) = result.split_result();
+        let log = seen.clone();
+        let _ok = ok.map(move |v| log.lock().unwrap().push(format!("ok: {:?}", v)));
+        let log = seen.clone();
+        let _err = err.map(move |e| log.lock().unwrap().push(format!("err: {:?}", e)));
+        seen.lock().unwrap().clear();
+
+        result.send(Ok(1));
+        result.send(Ok(2));
+        result.send(Err(String::from("failed")));
+        assert_eq!(*ok.0.value.lock().unwrap(), Some(2));
+        assert_eq!(*err.0.value.lock().unwrap(), Some(String::from("failed")));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["ok: Some(1)", "ok: Some(2)", "err: Some(\"failed\")"]
+        );
+    }
+
+    #[test]
+    fn test_rate_sync() {
+        let events = SignalSync::new(0);
+        let rate = events.rate(Duration::from_millis(200));
+        for i in 1..=10 {
+            events.send(i);
+        }
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(*rate.0.value.lock().unwrap(), 50.0);
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(*rate.0.value.lock().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_then_sync() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let loading = SignalSync::new(0);
+        let data = SignalSync::new(-1);
+        let shown = loading.then(&data, |p| *p >= 100);
+        let log = seen.clone();
+        let _observer = shown.map(move |v| log.lock().unwrap().push(*v));
+
+        loading.send(50);
+        data.send(7); // not tracked yet
+        loading.send(100);
+        assert_eq!(*shown.0.value.lock().unwrap(), 7);
+        loading.send(20); // no longer tracked
+        data.send(8);
+        assert_eq!(*seen.lock().unwrap(), vec![0, 50, 7, 8]);
+        assert!(loading.0.react_fns.read().unwrap().snapshot().is_empty());
+
+        let ready = SignalSync::new(100).then(&data, |p| *p >= 100);
+        data.send(9);
+        assert_eq!(*ready.0.value.lock().unwrap(), 9);
+    }
+
+    #[test]
+    fn test_cache_last_guard_sync() {
+        let a = SignalSync::new(0).cache_last_guard();
+        let b = a.map(|x| x + 1);
+        let _c = b.map(|x| x * 2);
+        a.send(1);
+        let buffer =
+            a.0.guard_scratch
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|v| (v.as_ptr(), v.capacity()));
+        assert!(buffer.is_some_and(|(_, capacity)| capacity >= 3));
+        a.send(2);
+        assert_eq!(
+            a.0.guard_scratch
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|v| (v.as_ptr(), v.capacity())),
+            buffer
+        );
+        assert_eq!(*b.0.value.lock().unwrap(), 3);
+
+        // A send forwarded while the buffer is in use gets its own buffer
+        let source = a.clone();
+        let _reentrant = b.map_forward(move |x| ((), (*x == 6).then(|| source.send(6))));
+        a.send(5);
+        assert_eq!(*b.0.value.lock().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_computed_from_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(String::from("x"));
+        let erased = SignalSync::new(10).erase();
+        let (a_read, b_read) = (a.clone(), b.clone());
+        let c_read = erased.downcast_ref::<i32>().unwrap().clone();
+        let summary = SignalSync::computed_from(&[&a, &b, &erased], move || {
+            format!(
+                "{}{}{}",
+                a_read.0.value.lock().unwrap(),
+                b_read.0.value.lock().unwrap(),
+                c_read.0.value.lock().unwrap()
+            )
+        });
+        assert_eq!(*summary.0.value.lock().unwrap(), "1x10");
+
+        a.send(2);
+        assert_eq!(*summary.0.value.lock().unwrap(), "2x10");
+        (
+            b.send(String::from("y")),
+            erased.downcast_ref::<i32>().unwrap().send(20),
+        );
+        assert_eq!(*summary.0.value.lock().unwrap(), "2y20");
+    }
+
+    #[test]
+    fn test_reaction_counts_sync() {
+        let a = SignalSync::new(1);
+        let doubled = a.map(|x| x * 2);
+        let tripled = a.with(|x| x * 3);
+        a.send(2);
+        assert_eq!(a.reaction_count(), 1);
+        assert_eq!(doubled.reaction_count(), 1);
+        assert_eq!(tripled.reaction_count(), 1);
+
+        // A batch reacts once
+        (a.send(3), a.send(4));
+        assert_eq!(a.reaction_count(), 2);
+        assert_eq!(doubled.reaction_count(), 2);
+
+        // Both branches of a diamond settle before the join reacts, once
+        let joined = doubled.combine(&tripled);
+        a.send(5);
+        assert_eq!(joined.reaction_count(), 1);
+
+        let b = SignalSync::new(10);
+        let lifted = (&a, &b).lift();
+        let extended = a.extend([&b]);
+        (a.send(6), b.send(11));
+        assert_eq!(lifted.reaction_count(), 1);
+        assert_eq!(extended.reaction_count(), 1);
+        b.send(12);
+        assert_eq!(lifted.reaction_count(), 2);
+        assert_eq!(extended.reaction_count(), 2);
+
+        let promapped = b.promap(|x| x + 1, |x| x - 1);
+        promapped.send(20);
+        assert_eq!(promapped.reaction_count(), 1);
+        assert_eq!(b.reaction_count(), 3);
+        b.send(30);
+        assert_eq!(promapped.reaction_count(), 2);
+
+        let dependency = SignalSync::new(0);
+        let depending = b.depend(dependency.clone());
+        dependency.send(1);
+        assert_eq!(depending.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_latest_sync() {
+        let a = SignalSync::new(0);
+        let latest = a.latest();
+        assert_eq!(latest.try_recv(), Some(0));
+        for i in 1..=100 {
+            a.send(i);
+        }
+        assert_eq!(latest.try_recv(), Some(100));
+        assert_eq!(latest.try_recv(), None);
+
+        let consumer = thread::spawn(move || {
+            let mut received = Vec::new();
+            while let Some(value) = latest.recv() {
+                received.push(value);
+            }
+            received
+        });
+        thread::sleep(Duration::from_millis(50));
+        a.send(101);
+        thread::sleep(Duration::from_millis(50));
+        drop(a);
+        assert_eq!(consumer.join().unwrap(), vec![101]);
+
+        let b = SignalSync::new(0);
+        drop(b.latest());
+        assert!(b.0.react_fns.read().unwrap().snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_validated_sync() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let reading = SignalSync::new(1.0_f64);
+        let log = errors.clone();
+        reading.on_reaction_error(move |e| log.lock().unwrap().push(e.message().to_string()));
+        let valid = reading.validated(|x| x.is_finite());
+        let doubled = valid.map(|x| x * 2.0);
+
+        reading.send(f64::NAN);
+        assert_eq!(*valid.0.value.lock().unwrap(), 1.0);
+        assert_eq!(*doubled.0.value.lock().unwrap(), 2.0);
+        assert_eq!(doubled.reaction_count(), 0);
+        reading.send(f64::INFINITY);
+        reading.send(3.0);
+        assert_eq!(*doubled.0.value.lock().unwrap(), 6.0);
+        assert_eq!(doubled.reaction_count(), 1);
+        assert_eq!(
+            *errors.lock().unwrap(),
+            vec!["rejected invalid value: NaN", "rejected invalid value: inf"]
+        );
+
+        // A handler installed on the validated signal afterwards sees the rejections too
+        let count = SignalSync::new(1);
+        let positive = count.validated(|x| *x > 0);
+        let log = errors.clone();
+        positive.on_reaction_error(move |e| log.lock().unwrap().push(e.message().to_string()));
+        count.send(-1);
+        assert_eq!(
+            errors.lock().unwrap().last().unwrap(),
+            "rejected invalid value: -1"
+        );
+    }
+
+    #[test]
+    fn test_combine_mixed_owned_and_borrowed_sync() {
+        let (a, b) = (SignalSync::new(1), SignalSync::new(2));
+        let (c, d) = (SignalSync::new(3), SignalSync::new(4));
+        let owned = a.combine(b.clone());
+        let nested = c.combine(&&d);
+        (a.send(10), b.send(20));
+        (c.send(30), d.send(40));
+        assert_eq!(*owned.0.value.lock().unwrap(), (10, 20));
+        assert_eq!(*nested.0.value.lock().unwrap(), (30, 40));
+    }
+
+    #[test]
+    fn test_extend_mixed_owned_and_borrowed_sync() {
+        let a = SignalSync::new(1);
+        let owned = [SignalSync::new(2), SignalSync::new(3)];
+        let from_iter = a.extend(owned.iter());
+        let b = SignalSync::new(4);
+        let borrowed = [SignalSync::new(5), SignalSync::new(6)];
+        let refs = [&borrowed[0], &borrowed[1]];
+        let from_refs = b.extend(refs.iter());
+        (a.send(10), owned[0].send(20), owned[1].send(30));
+        (b.send(40), borrowed[0].send(50), borrowed[1].send(60));
+        assert_eq!(*from_iter.0.value.lock().unwrap(), vec![10, 20, 30]);
+        assert_eq!(*from_refs.0.value.lock().unwrap(), vec![40, 50, 60]);
+    }
+
+    #[test]
+    fn test_lift_mixed_owned_and_borrowed_sync() {
+        let (a, b) = (SignalSync::new(1), SignalSync::new(2));
+        let pair = (a.clone(), &b).lift();
+        let (c, d) = (SignalSync::new(3), SignalSync::new(4));
+        let refs = [&c, &d];
+        let list = refs.iter().collect::<Vec<_>>().lift();
+        let (e, f) = (SignalSync::new(5), SignalSync::new(6));
+        let array = [&&e, &&f].lift();
+        (a.send(10), b.send(20));
+        (c.send(30), d.send(40));
+        (e.send(50), f.send(60));
+        assert_eq!(*pair.0.value.lock().unwrap(), (10, 20));
+        assert_eq!(*list.0.value.lock().unwrap(), vec![30, 40]);
+        assert_eq!(*array.0.value.lock().unwrap(), [50, 60]);
+    }
+
+    #[test]
+    fn test_on_first_sync() {
+        let config = SignalSync::new(0);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        config.on_first(move |x| seen_clone.lock().unwrap().push(*x));
+        assert!(seen.lock().unwrap().is_empty());
+        config.send(1);
+        config.send(2);
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+        assert_eq!(config.0.react_fns.read().unwrap().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_on_first_through_propagation_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x * 10);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        b.on_first(move |x| seen_clone.lock().unwrap().push(*x));
+        a.send(2);
+        a.send(3);
+        assert_eq!(*seen.lock().unwrap(), vec![20]);
+    }
+
+    #[test]
+    fn test_diff_keyed_reorder_sync() {
+        let items = SignalSync::new(vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+        let changes = items.diff_keyed(|(id, _)| *id);
+        assert_eq!(changes.0.value.lock().unwrap().len(), 4);
+        items.send(vec![(2, 'b'), (3, 'c'), (4, 'd'), (1, 'a')]);
+        assert_eq!(
+            *changes.0.value.lock().unwrap(),
+            vec![ListChange::Move {
+                key: 1,
+                from: 0,
+                to: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_batch_scope_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(2);
+        let pair = a.combine(&b);
+        let sum = pair.map(|(x, y)| x + y);
+        let mut scope = BatchScopeSync::new();
+        scope.send(&a, 10).send_with(&b, |v| *v = 20);
+        assert_eq!(sum.reaction_count(), 0);
+        let c = SignalSync::new(3);
+        fn fill<'a>(scope: &mut BatchScopeSync<'a>, c: &SignalSync<'a, i32>) {
+            scope.send(c, 30);
+        }
+        fill(&mut scope, &c);
+        scope.flush();
+        assert_eq!(*sum.0.value.lock().unwrap(), 30);
+        assert_eq!(sum.reaction_count(), 1);
+        assert_eq!(*c.0.value.lock().unwrap(), 30);
+    }
+
+    #[test]
+    fn test_prune_dead_sync() {
+        let source = SignalSync::new(1);
+        let kept = source.map(|x| x + 1);
+        for i in 0..10 {
+            let _dropped = source.map(move |x| x + i);
+        }
+        assert_eq!(source.0.react_fns.read().unwrap().snapshot().len(), 11);
+        source.prune_dead();
+        assert_eq!(source.0.react_fns.read().unwrap().snapshot().len(), 1);
+        assert_eq!(source.0.successors.read().unwrap().len(), 1);
+        source.send(2);
+        assert_eq!(*kept.0.value.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_clear_observers_sync() {
+        let source = SignalSync::new(1);
+        let doubled = source.map(|x| x * 2);
+        let tripled = source.map(|x| x * 3);
+        source.send(2);
+        source.clear_observers();
+        source.send(3);
+        assert_eq!(*doubled.0.value.lock().unwrap(), 4);
+        assert_eq!(*tripled.0.value.lock().unwrap(), 6);
+        assert_eq!(doubled.reaction_count(), 1);
+        assert_eq!(*source.0.value.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_scan_reset_sync() {
+        let source = SignalSync::new(0);
+        let reset = SignalSync::new(());
+        let sum = source.scan_reset(0, |acc, x| acc + x, &reset);
+        assert_eq!(*sum.0.value.lock().unwrap(), 0);
+        source.send(1);
+        source.send(2);
+        assert_eq!(*sum.0.value.lock().unwrap(), 3);
+        reset.send(());
+        assert_eq!(*sum.0.value.lock().unwrap(), 0);
+        source.send(4);
+        assert_eq!(*sum.0.value.lock().unwrap(), 4);
+        (reset.send(()), reset.send(()));
+        assert_eq!(*sum.0.value.lock().unwrap(), 0);
+        assert_eq!(sum.reaction_count(), 5);
+    }
+
+    #[test]
+    fn test_bind_to_owner_sync() {
+        let value = SignalSync::new(0);
+        let owner = Arc::new(());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        value.bind_to_owner(&owner, move |x| seen_clone.lock().unwrap().push(*x));
+        value.send(1);
+        drop(owner);
+        value.send(2);
+        value.send(3);
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+        assert_eq!(value.0.react_fns.read().unwrap().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_combine_map_sync() {
+        let width = SignalSync::new(2);
+        let height = SignalSync::new(3);
+        let area = width.combine_map(&height, |w, h| w * h);
+        assert_eq!(*area.0.value.lock().unwrap(), 6);
+        width.send(4);
+        assert_eq!(*area.0.value.lock().unwrap(), 12);
+        (width.send(5), height.send(5));
+        assert_eq!(*area.0.value.lock().unwrap(), 25);
+        assert_eq!(area.reaction_count(), 2);
+        assert_eq!(width.0.successors.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_send_from_reaction_is_deferred_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let b_clone = b.clone();
+        let _forward = a.observe_weak(move |x| {
+            b_clone.send(x * 10).defer();
+        });
+        let log_b = log.clone();
+        let _observe_b = b.observe_weak(move |x| log_b.lock().unwrap().push(format!("b {}", x)));
+        let log_a = log.clone();
+        let _observe_a = a.observe_weak(move |x| log_a.lock().unwrap().push(format!("a {}", x)));
+        a.send(1);
+        a.send(2);
+        assert_eq!(*log.lock().unwrap(), vec!["a 1", "b 10", "a 2", "b 20"]);
+        assert_eq!(b.reaction_count(), 2);
+    }
+
+    #[test]
+    fn test_send_from_reaction_settles_inline_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let b_clone = b.clone();
+        let _forward = a.observe_weak(move |x| {
+            b_clone.send(x * 10);
+        });
+        let log_b = log.clone();
+        let _observe_b = b.observe_weak(move |x| log_b.lock().unwrap().push(format!("b {}", x)));
+        let log_a = log.clone();
+        let _observe_a = a.observe_weak(move |x| log_a.lock().unwrap().push(format!("a {}", x)));
+        a.send(1);
+        a.send(2);
+        assert_eq!(*log.lock().unwrap(), vec!["b 10", "a 1", "b 20", "a 2"]);
+    }
+
+    #[test]
+    fn test_send_from_reaction_to_local_signal_sync() {
+        // A signal local to a reaction, whose reactions borrow data local to it, has settled
+        // before the reaction returns
+        let a = SignalSync::new(0);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        // The observer outlives the reaction, so it would still run if the send were deferred
+        let owner = Arc::new(());
+        let _forward = a.observe_weak(move |x| {
+            let words = vec![String::from("word"); *x];
+            let local = SignalSync::new(0);
+            let words_ref = &words;
+            let seen_clone = seen_clone.clone();
+            local.bind_to_owner(&owner, move |v| {
+                let v: Vec<_> = words_ref.iter().take(*v).collect();
+                seen_clone.lock().unwrap().push(v.len());
+            });
+            local.send(*x);
+        });
+        a.send(2);
+        a.send(3);
+        assert_eq!(*seen.lock().unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_sends_from_reaction_settle_in_order_sync() {
+        let source = SignalSync::new(0);
+        let first = SignalSync::new(0);
+        let second = SignalSync::new(0);
+        let sum = first.combine_map(&second, |x, y| x + y);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (first_clone, second_clone) = (first.clone(), second.clone());
+        let _forward = source.observe_weak(move |x| {
+            first_clone.send(*x).defer();
+            second_clone.send(*x * 2).defer();
+        });
+        let log_first = log.clone();
+        let _observe_first = first.observe_weak(move |x| log_first.lock().unwrap().push(*x));
+        let log_second = log.clone();
+        let _observe_second = second.observe_weak(move |x| log_second.lock().unwrap().push(*x));
+        let (chained, first_clone) = (SignalSync::new(0), first.clone());
+        let chained_clone = chained.clone();
+        let _chain = second.observe_weak(move |x| {
+            chained_clone
+                .send(*x + *first_clone.0.value.lock().unwrap())
+                .defer();
+        });
+        source.send(1);
+        assert_eq!(*log.lock().unwrap(), vec![1, 2]);
+        assert_eq!(*sum.0.value.lock().unwrap(), 3);
+        assert_eq!(*chained.0.value.lock().unwrap(), 3);
+        // Both sends of the reaction are settled before `sum` reacts, so it fires once
+        assert_eq!(sum.reaction_count(), 1);
+        assert_eq!(chained.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_get_and_peek_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x * 2);
+        a.send(5);
+        assert_eq!(a.get(), 5);
+        assert_eq!(b.peek(|x| *x + 1), 11);
+        assert_eq!(a.0.react_fns.read().unwrap().snapshot().len(), 1);
+        let len = a.peek(|x| x.to_string().len());
+        a.send(len as i32);
+        assert_eq!(b.get(), 2);
+        let worker = b.clone();
+        assert_eq!(thread::spawn(move || worker.get()).join().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_try_get_poisoned_sync() {
+        let a = SignalSync::new(1);
+        assert_eq!(a.try_get().ok(), Some(1));
+        let poisoner = a.clone();
+        let _ = thread::spawn(move || {
+            let _value = poisoner.0.value.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+        let Err(poisoned) = a.try_get() else {
+            panic!("expected a poisoned lock");
+        };
+        assert_eq!(poisoned.into_inner(), 1);
+        assert_eq!(a.get(), 1);
+        assert_eq!(a.peek(|x| x + 1), 2);
+    }
+
+    #[test]
+    fn test_filter_sync() {
+        let a = SignalSync::new(0);
+        let even = a.filter(|x| x % 2 == 0);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let log_clone = log.clone();
+        let _observer = even.map(move |x| log_clone.lock().unwrap().push(*x));
+        for x in 1..=5 {
+            a.send(x);
+        }
+        assert_eq!(*log.lock().unwrap(), vec![0, 2, 4]);
+        assert_eq!(*even.0.value.lock().unwrap(), 4);
+        assert_eq!(even.reaction_count(), 2);
+    }
+
+    #[test]
+    fn test_distinct_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(10);
+        let distinct = a.distinct();
+        let sum = distinct.combine_map(&b, |x, y| x + y);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let log_clone = log.clone();
+        let _observer = sum.map(move |x| log_clone.lock().unwrap().push(*x));
+        a.send(1);
+        (a.send(1), b.send(20));
+        a.send(2);
+        a.send(2);
+        assert_eq!(*log.lock().unwrap(), vec![11, 21, 22]);
+        assert_eq!(distinct.reaction_count(), 1);
+        assert_eq!(sum.get_dirty(), 0);
+    }
+
+    #[test]
+    fn test_distinct_by_sync() {
+        let a = SignalSync::new(1.0_f64);
+        let rounded = a.distinct_by(|x, y| x.round() == y.round());
+        a.send(1.2);
+        assert_eq!(*rounded.0.value.lock().unwrap(), 1.0);
+        a.send(1.6);
+        assert_eq!(*rounded.0.value.lock().unwrap(), 1.6);
+        assert_eq!(rounded.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_pairwise_sync() {
+        let a = SignalSync::new(1);
+        let pairs = a.pairwise();
+        assert_eq!(*pairs.0.value.lock().unwrap(), (1, 1));
+        a.send(2);
+        assert_eq!(*pairs.0.value.lock().unwrap(), (1, 2));
+        (a.send(3), a.send(4));
+        assert_eq!(*pairs.0.value.lock().unwrap(), (2, 4));
+        a.send(5);
+        assert_eq!(*pairs.0.value.lock().unwrap(), (4, 5));
+    }
+
+    #[test]
+    fn test_flatten_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(10);
+        let current = SignalSync::new(a.clone());
+        let flat = current.flatten();
+        assert_eq!(*flat.0.value.lock().unwrap(), 1);
+        a.send(2);
+        assert_eq!(*flat.0.value.lock().unwrap(), 2);
+        current.send(b.clone());
+        assert_eq!(*flat.0.value.lock().unwrap(), 10);
+        a.send(3);
+        assert_eq!(*flat.0.value.lock().unwrap(), 10);
+        assert_eq!(a.0.successors.read().unwrap().len(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().snapshot().len(), 0);
+        b.send(11);
+        assert_eq!(*flat.0.value.lock().unwrap(), 11);
+        assert_eq!(flat.reaction_count(), 3);
+    }
+
+    #[test]
+    fn test_switch_map_sync() {
+        let selected = SignalSync::new(1);
+        let shown = selected.switch_map(|x| SignalSync::new(x * 100));
+        assert_eq!(*shown.0.value.lock().unwrap(), 100);
+        let inner = SignalSync::new(7);
+        let inner_clone = inner.clone();
+        let chosen = selected.switch_map(move |x| {
+            if *x == 2 {
+                inner_clone.clone()
+            } else {
+                SignalSync::new(0)
+            }
+        });
+        selected.send(2);
+        assert_eq!(*shown.0.value.lock().unwrap(), 200);
+        assert_eq!(*chosen.0.value.lock().unwrap(), 7);
+        inner.send(8);
+        assert_eq!(*chosen.0.value.lock().unwrap(), 8);
+        selected.send(3);
+        inner.send(9);
+        assert_eq!(*chosen.0.value.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_combine_with_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(2);
+        let sum = a.combine_with(&b, |x, y| x + y);
+        let product = a.combine_with(b.clone(), |x, y| x * y);
+        assert_eq!(*sum.0.value.lock().unwrap(), 3);
+        (a.send(3), b.send(4));
+        assert_eq!(*sum.0.value.lock().unwrap(), 7);
+        assert_eq!(*product.0.value.lock().unwrap(), 12);
+        assert_eq!(sum.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_extend_tracked_sync() {
+        let a = SignalSync::new(1);
+        let others = [SignalSync::new(2), SignalSync::new(3)];
+        let list = a.extend_tracked(others.iter());
+        assert_eq!(*list.0.value.lock().unwrap(), (0, vec![1, 2, 3]));
+        others[1].send(30);
+        assert_eq!(*list.0.value.lock().unwrap(), (2, vec![1, 2, 30]));
+        a.send(10);
+        assert_eq!(*list.0.value.lock().unwrap(), (0, vec![10, 2, 30]));
+        assert_eq!(*a.0.value.lock().unwrap(), 10);
+        (others[0].send(20), others[1].send(300));
+        assert_eq!(list.0.value.lock().unwrap().1, vec![10, 20, 300]);
+        assert_eq!(list.reaction_count(), 3);
+    }
+
+    #[test]
+    fn test_subscribe_sync() {
+        let a = SignalSync::new(0);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let subscription = a.subscribe(move |x| seen_clone.lock().unwrap().push(*x));
+        assert!(seen.lock().unwrap().is_empty());
+        a.send(1);
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+        drop(subscription);
+        a.send(2);
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+        assert_eq!(a.0.react_fns.read().unwrap().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_subscribe_now_sync() {
+        let a = SignalSync::new(5);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let subscription = a.subscribe_now(move |x| seen_clone.lock().unwrap().push(*x));
+        assert_eq!(*seen.lock().unwrap(), vec![5]);
+        a.send(6);
+        drop(subscription);
+        a.send(7);
+        assert_eq!(*seen.lock().unwrap(), vec![5, 6]);
+    }
+
+    #[test]
+    fn test_subscribe_now_races_send_sync() {
+        let a = SignalSync::new(0);
+        let sender = a.clone();
+        let handle = thread::spawn(move || {
+            for i in 1..=100 {
+                sender.send(i);
+            }
+        });
+        let last = Arc::new(Mutex::new(None));
+        let last_clone = last.clone();
+        let _subscription = a.subscribe_now(move |x| *last_clone.lock().unwrap() = Some(*x));
+        handle.join().unwrap();
+        assert_eq!(*last.lock().unwrap(), Some(100));
+    }
+
+    #[test]
+    fn test_diamond_reacts_once_per_send_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x + 1);
+        let c = a.map(|x| x * 10);
+        let d = b.combine_map(&c, |b, c| b + c);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = d.observe_weak(move |x| seen_clone.lock().unwrap().push(*x));
+        a.send(2);
+        a.send(3);
+        // Each send settles `d` once, after both `b` and `c` have updated
+        assert_eq!(*seen.lock().unwrap(), vec![23, 34]);
+        assert_eq!(d.reaction_count(), 2);
+        // The bottom of the diamond is collected and marked dirty once
+        let mut guards = Vec::new();
+        a.collect_guards(&mut guards);
+        assert_eq!(guards.len(), 4);
+        assert_eq!(d.get_dirty(), 1);
+    }
+
+    #[test]
+    fn test_lift_clones_non_trivial_values_sync() {
+        // `combine`, `extend` and `lift_from_array` start from the values of their sources,
+        // which keep their own values through later sends
+        let a = SignalSync::new(vec![String::from("a")]);
+        let b = SignalSync::new(vec![String::from("b"), String::from("c")]);
+        let pair = a.combine(&b);
+        let list = a.extend([&b]);
+        let array = SignalSync::<Vec<String>>::lift_from_array([&a, &b]);
+        assert_eq!(
+            pair.get(),
+            (
+                vec!["a".to_string()],
+                vec!["b".to_string(), "c".to_string()]
+            )
+        );
+        assert_eq!(list.get(), vec![vec!["a"], vec!["b", "c"]]);
+        assert_eq!(array.get(), [vec!["a"], vec!["b", "c"]]);
+
+        a.send(vec![String::from("d")]);
+        assert_eq!(*a.0.value.lock().unwrap(), vec!["d"]);
+        assert_eq!(*b.0.value.lock().unwrap(), vec!["b", "c"]);
+        assert_eq!(
+            pair.get(),
+            (
+                vec!["d".to_string()],
+                vec!["b".to_string(), "c".to_string()]
+            )
+        );
+        assert_eq!(list.get(), vec![vec!["d"], vec!["b", "c"]]);
+        assert_eq!(array.get(), [vec!["d"], vec!["b", "c"]]);
+        a.send(vec![String::from("e")]);
+        assert_eq!(*a.0.value.lock().unwrap(), vec!["e"]);
+        assert_eq!(pair.get().0, vec!["e"]);
+    }
+
+    #[test]
+    fn test_cycle_settles_after_one_pass_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(2);
+        // Each signal mirrors the other, which used to recurse until the stack overflowed
+        a.mirror_into(&b);
+        b.mirror_into(&a);
+        a.send(5);
+        assert_eq!(*a.0.value.lock().unwrap(), 5);
+        assert_eq!(*b.0.value.lock().unwrap(), 5);
+        assert_eq!(a.reaction_count(), 1);
+        assert_eq!(b.reaction_count(), 1);
+        b.send(7);
+        assert_eq!(*a.0.value.lock().unwrap(), 7);
+        assert_eq!(b.reaction_count(), 2);
+    }
+
+    #[test]
+    fn test_panicking_reaction_is_isolated_sync() {
+        let a = SignalSync::new(0);
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors_clone = errors.clone();
+        a.on_reaction_error(move |e| errors_clone.lock().unwrap().push(e.message().to_string()));
+        let _panicking = a.observe_weak(|x| {
+            if *x == 1 {
+                panic!("boom");
+            }
+        });
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _sibling = a.observe_weak(move |x| seen_clone.lock().unwrap().push(*x));
+        let doubled = a.map(|x| x * 2);
+        a.send(1);
+        // The panic happened while the value was locked, which must not poison the signal
+        a.send(2);
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+        assert_eq!(a.try_get().ok(), Some(2));
+        assert_eq!(*doubled.0.value.lock().unwrap(), 4);
+        assert_eq!(*errors.lock().unwrap(), vec!["reaction panicked: boom"]);
+    }
+
+    #[test]
+    fn test_panicking_reaction_without_handler_surfaces_sync() {
+        let a = SignalSync::new(0);
+        let _panicking = a.observe_weak(|x| {
+            if *x == 1 {
+                panic!("boom");
+            }
+        });
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _sibling = a.observe_weak(move |x| seen_clone.lock().unwrap().push(*x));
+        let doubled = a.map(|x| x * 2);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(a.send(1))));
+        assert_eq!(panic_message(&*result.unwrap_err()), "boom");
+        // The panic surfaced only once the graph had settled
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+        assert_eq!(*doubled.0.value.lock().unwrap(), 2);
+        assert_eq!(a.get_dirty(), 0);
+        a.send(2);
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_panicking_eager_reaction_is_isolated_sync() {
+        let a = SignalSync::new(0);
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors_clone = errors.clone();
+        a.on_reaction_error(move |e| errors_clone.lock().unwrap().push(e.message().to_string()));
+        let eager = a.map_eager(|x| {
+            if *x == 1 {
+                panic!("eager boom");
+            }
+            *x
+        });
+        let doubled = eager.map(|x| x * 2);
+        a.send(1);
+        a.send(2);
+        assert_eq!(
+            *errors.lock().unwrap(),
+            vec!["reaction panicked: eager boom"]
+        );
+        assert_eq!(
+            (
+                *eager.0.value.lock().unwrap(),
+                *doubled.0.value.lock().unwrap()
+            ),
+            (2, 4)
+        );
+        assert_eq!((a.get_dirty(), eager.get_dirty()), (0, 0));
+    }
+
+    #[test]
+    fn test_panicking_write_back_clears_predecessor_poison_sync() {
+        // Replacing 13 panics in its drop, while the reaction holds the lock it writes to
+        struct Fragile(i32);
+        impl Drop for Fragile {
+            fn drop(&mut self) {
+                if self.0 == 13 {
+                    panic!("fragile");
+                }
+            }
+        }
+        let result = SignalSync::new(Fragile(13));
+        let source = result.contramap_with(0, |x| Fragile(*x));
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors_clone = errors.clone();
+        source
+            .on_reaction_error(move |e| errors_clone.lock().unwrap().push(e.message().to_string()));
+        source.send(1);
+        assert_eq!(*errors.lock().unwrap(), vec!["reaction panicked: fragile"]);
+        assert!(!result.0.value.is_poisoned());
+        source.send(2);
+        assert_eq!(result.0.value.lock().unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_derived_values_correct_before_send_sync() {
+        struct Meters(i32);
+
+        let centimeters = SignalSync::new(300);
+        let meters = centimeters.promap(|cm| Meters(cm / 100), |m| m.0 * 100);
+        assert_eq!(meters.0.value.lock().unwrap().0, 3);
+        let source = centimeters.contramap_with(Meters(3), |m| m.0 * 100);
+        assert_eq!(source.0.value.lock().unwrap().0, 3);
+        assert_eq!(*centimeters.0.value.lock().unwrap(), 300);
+    }
+
+    #[test]
+    fn test_try_map_filter_ok_sync() {
+        let input = SignalSync::new(String::from("1"));
+        let parsed = input.try_map(|s| s.parse::<i32>());
+        let valid = parsed.filter_ok();
+        let errors = Arc::new(Mutex::new(0));
+        let errors_clone = errors.clone();
+        input.on_reaction_error(move |_| *errors_clone.lock().unwrap() += 1);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = valid.observe_weak(move |v| seen_clone.lock().unwrap().push(*v));
+        input.send(String::from("x"));
+        assert!(parsed.0.value.lock().unwrap().is_err());
+        assert_eq!(*valid.0.value.lock().unwrap(), Some(1));
+        input.send(String::from("2"));
+        assert_eq!(*seen.lock().unwrap(), vec![Some(2)]);
+        assert_eq!(*errors.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_arithmetic_operators_sync() {
+        let a = SignalSync::new(6);
+        let b = SignalSync::new(3);
+        let sum = &a + &b;
+        let difference = &a - &b;
+        let product = &a * &b;
+        let quotient = &a / &b;
+        assert_eq!(*sum.0.value.lock().unwrap(), 9);
+        (a.send(12), b.send(4));
+        assert_eq!(*sum.0.value.lock().unwrap(), 16);
+        assert_eq!(*difference.0.value.lock().unwrap(), 8);
+        assert_eq!(*product.0.value.lock().unwrap(), 48);
+        assert_eq!(*quotient.0.value.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_chained_arithmetic_operators_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(2);
+        let c = SignalSync::new(3);
+        // The intermediate sum is dropped at the end of the statement
+        let result = &(&a + &b) * &c;
+        assert_eq!(*result.0.value.lock().unwrap(), 9);
+        a.send(4);
+        assert_eq!(*result.0.value.lock().unwrap(), 18);
+        c.send(10);
+        assert_eq!(*result.0.value.lock().unwrap(), 60);
+    }
+
+    #[test]
+    fn test_debug_display_sync() {
+        let a = SignalSync::new(1).with_label("a");
+        let b = a.map(|x| x + 1);
+        assert_eq!(a.to_string(), "1");
+        assert_eq!(
+            format!("{:?}", a),
+            "SignalSync { label: \"a\", value: 1, successors: Some(1), predecessors: Some(0), dirty: 0 }"
+        );
+        assert_eq!(
+            format!("{:?}", b),
+            "SignalSync { value: 2, successors: Some(0), predecessors: Some(0), dirty: 0 }"
+        );
+        // Formatting while the value is locked must not deadlock
+        let value = a.0.value.lock().unwrap();
+        assert_eq!(a.to_string(), "<locked>");
+        assert!(format!("{:?}", a).contains("value: <locked>, "));
+        drop(value);
+    }
+
+    #[test]
+    fn test_lift_three_and_four_tuples_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new("b".to_string());
+        let c = SignalSync::new(false);
+        let d = SignalSync::new(0.5);
+
+        let abc = (&a, &b, &c).lift();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = abc.map(move |(x, y, z)| {
+            seen_clone.lock().unwrap().push((*x, y.clone(), *z));
+        });
+        (a.send(2), b.send("c".to_string()));
+        c.send(true);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (1, "b".to_string(), false),
+                (2, "c".to_string(), false),
+                (2, "c".to_string(), true),
+            ]
+        );
+
+        // Sources that are never sent to keep their initial value in the lifted signal
+        let (e, f, g) = (
+            SignalSync::new(7),
+            SignalSync::new("f".to_string()),
+            SignalSync::new(true),
+        );
+        let efgd = (&e, &f, &g, &d).lift();
+        assert_eq!(efgd.get(), (7, "f".to_string(), true, 0.5));
+        (e.send(3), d.send(1.5));
+        assert_eq!(efgd.get(), (3, "f".to_string(), true, 1.5));
+        assert_eq!(
+            (e.get(), f.get(), g.get(), d.get()),
+            (3, "f".to_string(), true, 1.5)
+        );
+    }
+
+    #[test]
+    fn test_combine_latest_waits_for_both_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new("".to_string());
+        let latest = a.combine_latest(&b);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = latest.map(move |pair| seen_clone.lock().unwrap().push(pair.clone()));
+        seen.lock().unwrap().clear();
+
+        a.send(1);
+        a.send(2);
+        assert!(seen.lock().unwrap().is_empty());
+
+        b.send("x".to_string());
+        a.send(3);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![(2, "x".to_string()), (3, "x".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_sample_on_trigger_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(false);
+        let sampled = a.sample(b.clone());
+        let observed = sampled.map(|x| *x);
+        a.send(1);
+        a.send(2);
+        a.send(3);
+        assert_eq!(sampled.reaction_count(), 0);
+        assert_eq!(observed.get(), 0);
+
+        b.send(true);
+        assert_eq!(sampled.reaction_count(), 1);
+        assert_eq!(observed.get(), 3);
+    }
+
+    #[test]
+    fn test_disconnect_derived_sync() {
+        use std::sync::atomic::AtomicUsize;
+
+        let source = SignalSync::new(1);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        let derived = source.map(move |x| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        });
+        let other = source.map(|x| x + 1);
+        source.send(2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        assert!(source.disconnect(&derived));
+        assert!(!source.disconnect(&derived));
+        source.send(3);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(derived.get(), 4);
+        assert_eq!(other.get(), 4);
+        assert_eq!(source.0.successors.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_disconnect_filter_and_zip_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let even = a.filter(|x| x % 2 == 0);
+        let pairs = a.zip(&b);
+        (a.send(2), b.send(3));
+        assert_eq!(even.get(), 2);
+        assert_eq!(pairs.get(), (2, 3));
+
+        assert!(a.disconnect(&even));
+        assert!(a.disconnect(&pairs));
+        assert!(b.disconnect(&pairs));
+        assert!(!a.disconnect(&even));
+        (a.send(4), b.send(5));
+        assert_eq!(even.get(), 2);
+        assert_eq!(pairs.get(), (2, 3));
+        assert_eq!(even.reaction_count(), 1);
+        assert_eq!(pairs.reaction_count(), 1);
+    }
+
+    #[test]
+    fn test_dropped_derived_reactions_are_pruned_on_send_sync() {
+        let source = SignalSync::new(0);
+        let _kept = source.map(|x| x + 1);
+        for i in 0..1000 {
+            let _short_lived = source.map(move |x| x * i);
+            let _eager = source.map_eager(move |x| x + i);
+            let _computed = SignalSync::computed_from(&[&source], move || i);
+            source.send(i);
+            assert!(source.0.react_fns.read().unwrap().entries.len() <= 3);
+            assert!(source.0.eager_react_fns.read().unwrap().entries.len() <= 1);
+        }
+        source.send(0);
+        assert_eq!(source.0.react_fns.read().unwrap().entries.len(), 1);
+        assert_eq!(source.0.eager_react_fns.read().unwrap().entries.len(), 0);
+        assert_eq!(source.0.successors.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_dropped_forwarding_reactions_are_pruned_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let _kept = a.filter(|x| x % 2 == 0);
+        let filtered = a.filter(|x| x % 2 == 1);
+        let zipped = a.zip(&b);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 3);
+        assert_eq!(b.0.react_fns.read().unwrap().entries.len(), 1);
+        drop((filtered, zipped));
+        a.prune_dead();
+        b.prune_dead();
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 1);
+        assert_eq!(b.0.react_fns.read().unwrap().entries.len(), 0);
+    }
+
+    #[test]
+    fn test_map_memo_runs_once_per_change_sync() {
+        let a = SignalSync::new(1);
+        let calls = Arc::new(Mutex::new(0));
+        let calls_clone = calls.clone();
+        let doubled = a.map_memo(move |x| {
+            *calls_clone.lock().unwrap() += 1;
+            x * 2
+        });
+        assert_eq!(*calls.lock().unwrap(), 1);
+
+        a.send(1);
+        a.send(2);
+        a.send(2);
+        (a.send(3), a.send(2));
+        a.send(2);
+        assert_eq!(*calls.lock().unwrap(), 2);
+        assert_eq!(doubled.get(), 4);
+
+        a.send(5);
+        assert_eq!(*calls.lock().unwrap(), 3);
+        assert_eq!(doubled.get(), 10);
+    }
+
+    #[test]
+    fn test_asymmetric_diamond_is_glitch_free_sync() {
+        // A long branch a -> b -> c -> long and a short one a -> short join at `pair`
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x + 1);
+        let c = b.map(|x| x * 2);
+        let long = c.map(|x| x - 2);
+        let short = a.map(|x| x * 2);
+        let pair = long.combine_map(&short, |l, s| (*l, *s));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = pair.map(move |(c, d)| seen_clone.lock().unwrap().push((*c, *d)));
+
+        for value in 2..6 {
+            a.send(value);
+        }
+        (a.send(10), a.send(11));
+
+        // Both branches compute 2 * a, an observer never sees one updated without the other
+        assert!(seen.lock().unwrap().iter().all(|(l, s)| l == s));
+        assert_eq!(seen.lock().unwrap().len(), 6);
+        assert_eq!(pair.reaction_count(), 5);
+    }
+
+    #[test]
+    fn test_batch_reacts_once_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let sum = a.combine_map(&b, |x, y| x + y);
+
+        // The tuple idiom batches the sends of a single expression
+        (a.send(1), b.send(2));
+        assert_eq!(sum.reaction_count(), 1);
+
+        // Separate statements react once per send
+        a.send(3);
+        b.send(4);
+        assert_eq!(sum.reaction_count(), 3);
+
+        // `batch_sync` batches separate statements, including those of the functions given the scope
+        fn send_b<'a>(scope: &mut BatchScopeSync<'a>, b: &SignalSync<'a, i32>) {
+            scope.send(b, 6);
+        }
+        let result = batch_sync(|scope| {
+            scope.send(&a, 5);
+            send_b(scope, &b);
+            scope.send(&a, 7);
+            assert_eq!(sum.reaction_count(), 3);
+            "done"
+        });
+        assert_eq!(result, "done");
+        assert_eq!(sum.reaction_count(), 4);
+        assert_eq!(sum.get(), 13);
+
+        // Sends after the batch react right away again
+        a.send(8);
+        assert_eq!(sum.reaction_count(), 5);
+    }
+
+    #[test]
+    fn test_effect_stops_after_drop_sync() {
+        let a = SignalSync::new(0);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let effect = a.effect(move |x| seen_clone.lock().unwrap().push(*x));
+        a.send(1);
+        a.send(2);
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+        drop(effect);
+        a.send(3);
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+        assert_eq!(a.0.react_fns.read().unwrap().snapshot().len(), 0);
+    }
+
+    #[test]
+    fn test_effect_cleanup_sync() {
+        let a = SignalSync::new(0);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let log = events.clone();
+        let effect = a.effect(move |x| {
+            log.lock().unwrap().push(format!("open {x}"));
+            let (log, x) = (log.clone(), *x);
+            move || log.lock().unwrap().push(format!("close {x}"))
+        });
+        a.send(1);
+        a.send(2);
+        assert_eq!(*events.lock().unwrap(), vec!["open 1", "close 1", "open 2"]);
+        drop(effect);
+        a.send(3);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["open 1", "close 1", "open 2", "close 2"]
+        );
+    }
+
+    #[test]
+    fn test_default_and_from_sync() {
+        fn make<'a, T: Default + From<u8> + Send + Sync + 'a>()
+        -> (SignalSync<'a, T>, SignalSync<'a, T>) {
+            (SignalSync::default(), T::from(7).into())
+        }
+        let s: SignalSync<i32> = 5.into();
+        assert_eq!(s.get(), 5);
+        let d: SignalSync<String> = SignalSync::default();
+        assert_eq!(d.get(), "");
+        let (d, f) = make::<u32>();
+        assert_eq!((d.get(), f.get()), (0, 7));
+        let doubled = f.map(|x| x * 2);
+        f.send(8);
+        assert_eq!(doubled.get(), 16);
+    }
+
+    #[test]
+    fn test_partial_eq_compares_values_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(1);
+        let c = SignalSync::new(2);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a, a);
+        c.send(1);
+        assert_eq!(a, c);
+        assert_eq!(c, a);
+    }
 
-        let react_fn = Box::new(move || {
-            if let Some(dep) = dependency_weak.upgrade() {
-                if let Some(target) = self_weak.upgrade() {
-                    if !target.explicitly_modified.load(Ordering::Acquire) {
-                        // Swap values instead of cloning
-                        std::mem::swap(
-                            &mut *target.value.lock().unwrap(),
-                            &mut *dep.value.lock().unwrap(),
-                        );
-                    }
-                }
+    #[test]
+    fn test_map_cloned_passes_ownership_sync() {
+        let name = SignalSync::new(String::from("ab"));
+        let len = name.map_cloned(|s: String| s.into_bytes().len());
+        assert_eq!(len.get(), 2);
+        name.send(String::from("abcd"));
+        assert_eq!(len.get(), 4);
+        assert_eq!(name.get(), "abcd");
+    }
+
+    #[test]
+    fn test_watch_across_threads_sync() {
+        let signal = SignalSync::new(0);
+        let values = signal.watch();
+        let consumer = thread::spawn(move || values.collect::<Vec<_>>());
+        let producer = thread::spawn(move || {
+            for i in 1..=5 {
+                signal.send(i);
             }
         });
+        producer.join().unwrap();
+        assert_eq!(consumer.join().unwrap(), vec![1, 2, 3, 4, 5]);
+    }
 
-        dependency.0.react_fns.write().unwrap().push(react_fn);
-        dependency
-            .0
-            .successors
-            .write()
-            .unwrap()
-            .push(WeakSignalRefSync::new(self));
-        dependency
+    #[test]
+    fn test_try_send_and_send_timeout_under_contention_sync() {
+        use std::sync::Barrier;
+
+        let signal = SignalSync::new(0);
+        let doubled = signal.map(|x| x * 2);
+
+        let held = signal.0.value.lock().unwrap();
+        assert_eq!(signal.try_send(1).err().unwrap().into_inner(), 1);
+        let start = Instant::now();
+        let err = signal
+            .send_timeout(2, Duration::from_millis(20))
+            .err()
+            .unwrap();
+        assert_eq!(err, TrySendError(2));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        drop(held);
+        assert_eq!((signal.get(), doubled.get()), (0, 0));
+
+        let barrier = Arc::new(Barrier::new(2));
+        let holder = {
+            let signal = signal.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let _held = signal.0.value.lock().unwrap();
+                barrier.wait();
+                thread::sleep(Duration::from_millis(20));
+            })
+        };
+        barrier.wait();
+        assert!(signal.try_send(3).is_err());
+        signal.send_timeout(4, Duration::from_secs(5)).unwrap();
+        holder.join().unwrap();
+        assert_eq!((signal.get(), doubled.get()), (4, 8));
     }
 
-    /// Apply a modification function to the stored value (thread-safe)
-    pub(crate) fn modify(&self, f: impl FnOnce(&mut T)) {
-        let mut value = self.0.value.lock().unwrap();
-        f(&mut value);
+    #[test]
+    fn test_graph_counts_sync() {
+        let a = SignalSync::new(1);
+        let b = SignalSync::new(2);
+        assert_eq!((a.successor_count(), a.predecessor_count()), (0, 0));
+        let doubled = a.map(|x| x * 2);
+        let sum = a.combine_map(&b, |x, y| x + y);
+        let shifted = a.promap(|x| x + 1, |y| y - 1);
+        assert_eq!(a.successor_count(), 3);
+        assert_eq!(b.successor_count(), 1);
+        assert_eq!(
+            (doubled.predecessor_count(), shifted.predecessor_count()),
+            (0, 1)
+        );
+        drop(doubled);
+        assert_eq!(a.successor_count(), 3);
+        assert_eq!(a.live_successor_count(), 2);
+        assert_eq!(a.successor_count(), 2);
+        drop(sum);
+        assert_eq!(b.live_successor_count(), 0);
+        assert_eq!(a.live_successor_count(), 1);
+        drop(shifted);
+        assert_eq!(a.live_successor_count(), 0);
     }
 
-    fn mark_dirty(&self) {
-        self.0.dirty.fetch_add(1, Ordering::SeqCst);
+    #[test]
+    fn test_to_dot_sync() {
+        let a = SignalSync::new(1).with_label("a");
+        let b = SignalSync::new(2);
+        let left = a.map(|x| x + 1);
+        let right = a.promap(|x| x * 2, |y| y / 2);
+        let sum = left.combine_map(&right, |x, y| x + y);
+        let _product = sum.combine_map(&b, |x, y| x * y);
+        let dot = a.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("n0 [label=\"#0 a\"];"));
+        // a -> left, a -> right, right -> a (dashed), left -> sum, right -> sum, sum -> product
+        assert_eq!(dot.matches(" -> ").count(), 6);
+        assert_eq!(dot.matches("[style=dashed]").count(), 1);
+        assert_eq!(dot.matches("[label=").count(), 5);
     }
 
-    fn collect_and_iterate<F>(&self, refs: &RwLock<Vec<WeakSignalRefSync<'a>>>, mut callback: F)
-    where
-        F: FnMut(&dyn SignalExtSync<'a>),
-    {
-        let signals_to_process: Vec<_> = {
-            let mut refs_write = refs.write().unwrap();
-            refs_write.retain(|s| s.is_alive());
-            refs_write.iter().filter_map(|s| s.upgrade()).collect()
-        };
+    #[test]
+    fn test_trace_hook_events_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x * 2);
+        let ids = [a.trace_id(), b.trace_id()];
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        // The hook is global, so keep only the events of this test's signals
+        set_trace_hook_sync(move |event| {
+            let (TraceEvent::Propagate(id) | TraceEvent::MarkDirty(id) | TraceEvent::React(id)) =
+                event;
+            if ids.contains(&id) {
+                events_clone.lock().unwrap().push(event);
+            }
+        });
+        a.send(2);
+        clear_trace_hook_sync();
+        a.send(3);
+        let [a, b] = ids;
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                TraceEvent::Propagate(a),
+                TraceEvent::MarkDirty(a),
+                TraceEvent::MarkDirty(b),
+                TraceEvent::React(a),
+                TraceEvent::React(b),
+            ]
+        );
+    }
 
-        for signal in signals_to_process {
-            callback(&*signal);
-        }
+    #[test]
+    fn test_replace_and_take_sync() {
+        let a = SignalSync::new(vec![1, 2]);
+        let len = a.map(|v| v.len());
+        let (previous, guard) = a.replace(vec![3]);
+        drop(guard);
+        assert_eq!(previous, vec![1, 2]);
+        assert_eq!(len.get(), 1);
+        let (taken, guard) = a.take();
+        drop(guard);
+        assert_eq!(taken, vec![3]);
+        assert_eq!((a.get(), len.get()), (vec![], 0));
     }
 
-    fn collect_guards(&self, result: &mut Vec<SignalGuardInnerSync<'a>>) {
-        self.mark_dirty();
-        result.push(SignalGuardInnerSync(self.clone_box()));
-        self.collect_and_iterate(&self.0.successors, |signal| {
-            signal.collect_guards_recursive(result);
+    #[test]
+    fn test_replace_overrides_derived_sync() {
+        let a = SignalSync::new(1);
+        let b = a.map(|x| x * 10);
+        let c = b.map(|x| x + 1);
+        let (previous, _) = b.replace(5);
+        assert_eq!((previous, c.get()), (10, 6));
+        a.send(2);
+        assert_eq!((b.get(), c.get()), (20, 21));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_subscribe_spawn_sync() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let signal = SignalSync::new(0);
+        let subscription = signal.subscribe_spawn(runtime.handle().clone(), move |value| {
+            let sender = sender.clone();
+            async move {
+                sender.send(value).unwrap();
+            }
         });
-        self.collect_and_iterate(&self.0.predecessors, |signal| {
-            signal.collect_predecessors_recursive(result);
+        for i in 1..=3 {
+            signal.send(i);
+        }
+        drop(subscription);
+        signal.send(4);
+        let mut seen = runtime.block_on(async {
+            let mut seen = Vec::new();
+            while let Some(value) = receiver.recv().await {
+                seen.push(value);
+            }
+            seen
         });
+        seen.sort();
+        assert_eq!(seen, vec![1, 2, 3]);
     }
 
-    /// Lift an array of liftable items into a signal of an array
-    ///
-    /// This creates a new signal that depends on the provided liftable items.
-    /// Changes to any of the source signals will propagate to the new signal.
-    ///
-    /// # Example
-    /// ```rust
-    /// let a = SignalSync::new(1);
-    /// let b = SignalSync::new(2);
-    /// let c = SignalSync::new(3);
-    /// let abc = SignalSync::lift_from_array([a, b, c]);
-    /// abc.with(|values| println!("abc changed: {:?}", values));
-    /// (a.send(10), b.send(20), c.send(30)); // prints "abc changed: [10, 20, 30]"
-    /// ```
-    pub fn lift_from_array<S, const N: usize>(items: [S; N]) -> SignalSync<'a, [S::Inner; N]>
-    where
-        S: LiftableSync<'a>,
-        S::Inner: Send + Sync + 'a,
-    {
-        let signals: [SignalSync<'a, S::Inner>; N] =
-            std::array::from_fn(|i| items[i].as_ref().clone());
+    #[test]
+    fn test_from_receiver_sync() {
+        let (sender, receiver) = mpsc::channel();
+        let latest = SignalSync::from_receiver(receiver, 0);
+        let (seen_sender, seen) = mpsc::channel();
+        let _observer = latest.map(move |x| seen_sender.send(*x * 10).unwrap());
+        for i in 1..=3 {
+            sender.send(i).unwrap();
+        }
+        let received: Vec<_> = seen.iter().take(4).collect();
+        assert_eq!(received, vec![0, 10, 20, 30]);
+        assert_eq!(latest.get(), 3);
 
-        // Take values using helper - no cloning!
-        let initial: [S::Inner; N] = std::array::from_fn(|i| Self::take_value(&signals[i].0.value));
-        let new_signal: SignalSync<'a, [S::Inner; N]> = SignalSync::new(initial);
+        // Once the signal is gone, the worker stops at the next value and drops the receiver
+        drop((_observer, latest));
+        let stopped = (0..1000).any(|i| {
+            thread::sleep(Duration::from_millis(1));
+            sender.send(i).is_err()
+        });
+        assert!(stopped);
+    }
 
-        // Restore original values by swapping back
-        for (index, signal) in signals.iter().enumerate() {
-            std::mem::swap(
-                &mut *signal.0.value.lock().unwrap(),
-                &mut new_signal.0.value.lock().unwrap()[index],
-            );
+    #[test]
+    fn test_accumulate_sync() {
+        let a = SignalSync::new(0);
+        let history = a.accumulate();
+        let recent = a.accumulate_bounded(3);
+        for i in 1..=5 {
+            a.send(i);
         }
+        assert_eq!(history.get(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(recent.get(), vec![3, 4, 5]);
+    }
 
-        let result_new_signal = new_signal.clone();
-
-        for (index, signal) in signals.iter().enumerate() {
-            let new_signal_weak = Arc::downgrade(&new_signal.0);
-            let source_for_closure = Arc::downgrade(&signal.0);
+    #[test]
+    #[should_panic(expected = "the accumulate capacity must not be zero")]
+    fn test_accumulate_bounded_zero_cap_sync() {
+        SignalSync::new(0).accumulate_bounded(0);
+    }
 
-            let react_fn = Box::new(move || {
-                if let Some(new_sig) = new_signal_weak.upgrade() {
-                    if !new_sig.explicitly_modified.load(Ordering::Acquire) {
-                        if let Some(source) = source_for_closure.upgrade() {
-                            // Swap instead of cloning (during reaction only)
-                            std::mem::swap(
-                                &mut new_sig.value.lock().unwrap()[index],
-                                &mut *source.value.lock().unwrap(),
-                            );
-                        }
-                    }
-                }
-            });
+    #[test]
+    fn test_zip_pairs_in_order_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new('-');
+        let zipped = a.zip(&b);
+        let pairs = zipped.accumulate();
+        a.send(1);
+        a.send(2);
+        b.send('x');
+        a.send(3);
+        b.send('y');
+        b.send('z');
+        b.send('w');
+        a.send(4);
+        assert_eq!(
+            pairs.get(),
+            vec![(0, '-'), (1, 'x'), (2, 'y'), (3, 'z'), (4, 'w')]
+        );
+        (a.send(5), b.send('v'));
+        assert_eq!(zipped.get(), (5, 'v'));
+    }
 
-            signal.0.react_fns.write().unwrap().push(react_fn);
-            signal
-                .0
-                .successors
-                .write()
-                .unwrap()
-                .push(WeakSignalRefSync::new(&new_signal));
-        }
+    #[test]
+    fn test_combined_guard_reacts_predecessors_last_sync() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let result = SignalSync::new(0);
+        let source = result.contramap(|x: &i32| x * 2);
+        let log_result = log.clone();
+        let _result_observer =
+            result.subscribe(move |x| log_result.lock().unwrap().push(("result", *x)));
+        let other = SignalSync::new(0);
+        let doubled = other.map(|x| x * 2);
+        let log_doubled = log.clone();
+        let _doubled_observer =
+            doubled.subscribe(move |x| log_doubled.lock().unwrap().push(("doubled", *x)));
 
-        result_new_signal
+        drop(source.send(1).and(other.send(1)));
+        assert_eq!(*log.lock().unwrap(), vec![("doubled", 2), ("result", 2)]);
+        log.lock().unwrap().clear();
+        drop(other.send(2).and(source.send(2)));
+        assert_eq!(*log.lock().unwrap(), vec![("doubled", 4), ("result", 4)]);
     }
-}
 
-impl<'a, T: Send + Sync + 'a> SignalExtSync<'a> for SignalSync<'a, T> {
-    fn react(&self) {
-        self.0
-            .react_fns
-            .read()
-            .unwrap()
-            .iter()
-            .for_each(|react_fn| {
-                react_fn();
-            });
+    #[test]
+    fn test_map_into_reuses_allocation_sync() {
+        let source = SignalSync::new(vec![1u8; 16]);
+        let copy = source.map_into(Vec::with_capacity(4096), |input, output: &mut Vec<u8>| {
+            output.clear();
+            output.extend_from_slice(input);
+        });
+        let buffer = copy.0.value.lock().unwrap().as_ptr();
+        for len in [64, 1024, 8] {
+            source.send(vec![len as u8; len]);
+            let output = copy.0.value.lock().unwrap();
+            assert_eq!(output.len(), len);
+            assert_eq!(output.capacity(), 4096);
+            assert_eq!(output.as_ptr(), buffer);
+        }
     }
-    fn guard(&self) -> SignalGuardSync<'a> {
-        let mut result = vec![];
-        self.collect_guards(&mut result);
-        SignalGuardSync(result)
+
+    #[test]
+    fn test_map_parallel_keeps_order_sync() {
+        let numbers = SignalSync::new((0..4).collect::<Vec<u64>>());
+        let slow_square = |x: &u64| {
+            // Later elements finish first, so out-of-order collection would show
+            thread::sleep(Duration::from_millis(20 - *x % 20));
+            x * x
+        };
+        let squares = numbers.map_parallel(slow_square);
+        assert_eq!(squares.get(), vec![0, 1, 4, 9]);
+        numbers.send((0..40).rev().collect());
+        assert_eq!(
+            squares.get(),
+            (0..40).rev().map(|x| x * x).collect::<Vec<u64>>()
+        );
+        numbers.send(Vec::new());
+        assert!(squares.get().is_empty());
+
+        // Whatever the number of cores of the machine running the test
+        let items: Vec<u64> = (0..10).collect();
+        for chunks in [1, 3, 4, 16] {
+            assert_eq!(
+                map_in_chunks(&items, chunks, &slow_square),
+                (0..10).map(|x| x * x).collect::<Vec<u64>>()
+            );
+        }
     }
-    fn clone_box(&self) -> Box<dyn SignalExtSync<'a> + 'a> {
-        Box::new(SignalSync(Arc::clone(&self.0)))
+
+    #[test]
+    fn test_freeze_holds_back_promap_write_back_sync() {
+        let a = SignalSync::new(1);
+        let b = a.promap(|x| x * 10, |y| y / 10);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = a.subscribe(move |x| seen_clone.lock().unwrap().push(*x));
+
+        let frozen = b.freeze();
+        b.send(20);
+        b.send(30);
+        assert_eq!(a.get(), 1);
+        assert!(seen.lock().unwrap().is_empty());
+        drop(frozen);
+        assert_eq!((a.get(), b.get()), (3, 30));
+        assert_eq!(*seen.lock().unwrap(), vec![3]);
+
+        // A frozen target still follows its source, and thaws without writing back
+        let frozen = b.freeze();
+        a.send(4);
+        drop(frozen);
+        assert_eq!((a.get(), b.get()), (4, 40));
+        assert_eq!(*seen.lock().unwrap(), vec![3, 4]);
     }
-    fn decrease_dirty(&self) {
-        self.0.dirty.fetch_sub(1, Ordering::SeqCst);
+
+    #[test]
+    fn test_freeze_reacts_once_on_thaw_sync() {
+        let a = SignalSync::new(0);
+        let doubled = a.map(|x| x * 2);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = doubled.subscribe(move |x| seen_clone.lock().unwrap().push(*x));
+
+        let frozen = a.freeze();
+        a.send(1);
+        a.send(2);
+        assert_eq!(a.get(), 2);
+        assert_eq!(doubled.get(), 0);
+        assert!(seen.lock().unwrap().is_empty());
+        drop(frozen);
+        assert_eq!(doubled.get(), 4);
+        assert_eq!(*seen.lock().unwrap(), vec![4]);
+
+        // Nested freezes thaw with the last guard, and thawing without a send does nothing
+        let outer = a.freeze();
+        let inner = a.freeze();
+        a.send(3);
+        drop(inner);
+        assert_eq!(*seen.lock().unwrap(), vec![4]);
+        drop(outer);
+        assert_eq!(*seen.lock().unwrap(), vec![4, 6]);
+        drop(a.freeze());
+        assert_eq!(*seen.lock().unwrap(), vec![4, 6]);
     }
-    fn get_dirty(&self) -> isize {
-        self.0.dirty.load(Ordering::SeqCst)
+
+    #[test]
+    fn test_promap_with_initial_value_sync() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Celsius(f64);
+
+        let fahrenheit = SignalSync::new(212.0);
+        let celsius = fahrenheit.promap_with(
+            Celsius(100.0),
+            |f| Celsius((f - 32.0) * 5.0 / 9.0),
+            |c| c.0 * 9.0 / 5.0 + 32.0,
+        );
+        // Correct before any send, without a `Default` for `Celsius`
+        assert_eq!(celsius.get(), Celsius(100.0));
+
+        let writer = celsius.clone();
+        thread::spawn(move || {
+            writer.send(Celsius(0.0));
+        })
+        .join()
+        .unwrap();
+        assert_eq!(fahrenheit.get(), 32.0);
+        fahrenheit.send(50.0);
+        assert_eq!(celsius.get(), Celsius(10.0));
     }
-    fn reset_explicitly_modified(&self) {
-        self.0.explicitly_modified.store(false, Ordering::Release);
+
+    #[test]
+    fn test_depend_without_default_sync() {
+        #[derive(Debug, PartialEq)]
+        struct Name(String);
+
+        let display = SignalSync::new(Name(String::from("guest")));
+        let input = display.depend(SignalSync::new(Name(String::from("ada"))));
+        let greeting = display.map(|name| format!("hello, {}", name.0));
+        input.send(Name(String::from("grace")));
+        assert_eq!(
+            *display.0.value.lock().unwrap(),
+            Name(String::from("grace"))
+        );
+        assert_eq!(greeting.get(), "hello, grace");
     }
-    fn collect_guards_recursive(&self, result: &mut Vec<SignalGuardInnerSync<'a>>) {
-        self.mark_dirty();
-        result.push(SignalGuardInnerSync(self.clone_box()));
-        self.collect_and_iterate(&self.0.successors, |signal| {
-            signal.collect_guards_recursive(result);
-        });
+
+    #[test]
+    fn test_map_keyed_remaps_only_changes_sync() {
+        let items = SignalSync::new(vec![(1, "a"), (2, "b"), (3, "c")]);
+        let mapped = Arc::new(Mutex::new(Vec::new()));
+        let mapped_clone = mapped.clone();
+        let upper = items.map_keyed(
+            |(id, _)| *id,
+            move |(id, text)| {
+                mapped_clone.lock().unwrap().push(*id);
+                text.to_uppercase()
+            },
+        );
+        assert_eq!(upper.get(), vec!["A", "B", "C"]);
+        assert_eq!(*mapped.lock().unwrap(), vec![1, 2, 3]);
+
+        mapped.lock().unwrap().clear();
+        items.send(vec![(3, "c"), (1, "a"), (2, "bee"), (4, "d")]);
+        assert_eq!(upper.get(), vec!["C", "A", "BEE", "D"]);
+        assert_eq!(*mapped.lock().unwrap(), vec![2, 4]);
+
+        // A key dropped and added again is mapped again
+        mapped.lock().unwrap().clear();
+        items.send(vec![(1, "a")]);
+        items.send(vec![(1, "a"), (3, "c")]);
+        assert_eq!(upper.get(), vec!["A", "C"]);
+        assert_eq!(*mapped.lock().unwrap(), vec![3]);
     }
-    fn collect_predecessors_recursive(&self, result: &mut Vec<SignalGuardInnerSync<'a>>) {
-        self.mark_dirty();
-        result.push(SignalGuardInnerSync(self.clone_box()));
-        // Collect predecessors last so they drop last (react last)
-        self.collect_and_iterate(&self.0.predecessors, |signal| {
-            signal.collect_predecessors_recursive(result);
-        });
+
+    #[test]
+    fn test_debounce_emits_after_quiet_sync() {
+        let source = SignalSync::new(0);
+        let settled = source.debounce(Duration::from_millis(100));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = settled.subscribe(move |x| seen_clone.lock().unwrap().push(*x));
+
+        for i in 1..=10 {
+            source.send(i);
+        }
+        assert!(seen.lock().unwrap().is_empty());
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(*seen.lock().unwrap(), vec![10]);
+
+        source.send(11);
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(*seen.lock().unwrap(), vec![10, 11]);
+        assert_eq!(settled.get(), 11);
     }
-}
 
-impl<T> Clone for SignalSync<'_, T> {
-    fn clone(&self) -> Self {
-        Self(self.0.clone())
+    #[test]
+    fn test_throttle_limits_emissions_sync() {
+        let source = SignalSync::new(0);
+        let sampled = source.throttle(Duration::from_millis(50));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = sampled.subscribe(move |x| seen_clone.lock().unwrap().push(*x));
+
+        for i in 1..=30 {
+            source.send(i);
+            thread::sleep(Duration::from_millis(5));
+        }
+        thread::sleep(Duration::from_millis(200));
+        let seen = seen.lock().unwrap();
+        // About 150ms of sends at most once per 50ms, plus the trailing emission
+        assert!(seen.len() >= 2 && seen.len() <= 6, "{:?}", *seen);
+        assert!(seen.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(seen.last(), Some(&30));
     }
-}
 
-impl<'a, T> AsRef<SignalSync<'a, T>> for SignalSync<'a, T> {
-    fn as_ref(&self) -> &SignalSync<'a, T> {
-        self
+    #[test]
+    fn test_split_sync() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let pair = SignalSync::new((1, String::from("one")));
+        let (number, name) = pair.split();
+        let log = seen.clone();
+        let _number = number.map(move |n| log.lock().unwrap().push(format!("number: {}", n)));
+        let log = seen.clone();
+        let _name = name.map(move |s| log.lock().unwrap().push(format!("name: {}", s)));
+        seen.lock().unwrap().clear();
+
+        pair.send((2, String::from("two")));
+        assert_eq!(*number.0.value.lock().unwrap(), 2);
+        assert_eq!(*name.0.value.lock().unwrap(), "two");
+        assert_eq!(*seen.lock().unwrap(), vec!["number: 2", "name: two"]);
+
+        drop(name);
+        pair.send((3, String::from("three")));
+        assert_eq!(*number.0.value.lock().unwrap(), 3);
+        assert_eq!(seen.lock().unwrap().last().unwrap(), "number: 3");
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn test_project_field_sync() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let point = SignalSync::new(Point { x: 1, y: 2 });
+        let x = point.project(|p| p.x, |p, x| p.x = x);
+        let log = seen.clone();
+        let _observer = point.map(move |p| log.lock().unwrap().push((p.x, p.y)));
+        seen.lock().unwrap().clear();
+        assert_eq!(*x.0.value.lock().unwrap(), 1);
 
-    use super::*;
+        point.send(Point { x: 3, y: 4 });
+        assert_eq!(*x.0.value.lock().unwrap(), 3);
+
+        x.send(5);
+        assert_eq!(*point.0.value.lock().unwrap(), Point { x: 5, y: 4 });
+        assert_eq!(*seen.lock().unwrap(), vec![(3, 4), (5, 4)]);
+
+        point.send(Point { x: 6, y: 7 });
+        assert_eq!(*x.0.value.lock().unwrap(), 6);
+    }
 
     #[test]
-    fn test_signal_sync_basic() {
-        let a = SignalSync::new(0);
-        let _a = a.map(|x| println!("a changed: {}", x));
-        (a.send(100), a.send(5));
+    fn test_map_lazy_defers_f_sync() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let a = SignalSync::new(10);
+        let count = calls.clone();
+        let b = a.map_lazy(move |x| {
+            count.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(*b.0.value.lock().unwrap(), None);
+
+        a.send(5);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(*b.0.value.lock().unwrap(), Some(10));
+
+        let count = calls.clone();
+        let _eager = a.map(move |x| {
+            count.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
     #[test]
-    fn test_signal_sync_map() {
+    fn test_filter_registers_successor_sync() {
         let a = SignalSync::new(0);
-        let b = a.map(|x| x * 2);
-        let _b = b.map(|x| println!("b changed: {}", x));
-        drop(b);
-        a.send(100);
+        let even = a.filter(|x| x % 2 == 0);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(even.reaction_count(), 0);
+        drop(even);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
     }
 
     #[test]
-    fn test_signal_sync_combine() {
+    fn test_distinct_by_registers_successor_sync() {
         let a = SignalSync::new(0);
-        let b = a.map(|x| x * 2);
-        let _ab = a
-            .and(&b)
-            .map(|(x, y)| println!("c changed: {} + {} = {}", x, y, x + y));
-        (a.send(5), a.send(100));
+        let distinct = a.distinct_by(|x, y| x % 10 == y % 10);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(10);
+        assert_eq!(distinct.reaction_count(), 0);
+        drop(distinct);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
     }
 
     #[test]
-    fn test_signal5() {
-        let result = SignalSync::new(42);
-        let source1 = result.contramap(|x| x + 1);
-        let source2 = source1.contramap(|x| x * 2);
-
-        let _observer_1 = result.map(|x| println!("result changed: {}", x));
-        let _observer_2 = source1.map(|x| println!("source1 changed: {}", x));
-        let _observer_3 = source2.map(|x| println!("source2 changed: {}", x));
+    fn test_dedupe_registers_successor_sync() {
+        let a = SignalSync::new(1).with_eq(PartialEq::eq);
+        let deduped = a.dedupe();
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(deduped.reaction_count(), 0);
+        drop(deduped);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
+    }
 
-        println!("--- Sending to source1 ---");
-        source1.send(100);
-        println!("--- Sending to source2 ---");
-        source2.send(200);
-        println!("--- Sending to source1 and source2 ---");
-        (source1.send(300), source2.send(400));
+    #[test]
+    fn test_validated_registers_successor_sync() {
+        let a = SignalSync::new(1.0_f64);
+        let valid = a.validated(|x| x.is_finite());
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(f64::NAN);
+        assert_eq!(valid.reaction_count(), 0);
+        drop(valid);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
     }
 
     #[test]
-    fn test_depend_sync() {
-        let mut a = SignalSync::new(10);
-        let mut b = SignalSync::new(10);
-        let c = SignalSync::new(10);
+    fn test_map_forward_registers_successor_sync() {
+        let a = SignalSync::new(0);
+        let tens = a.map_forward(|x| (x * 10, None));
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        // Unlike the forwarding combinators, propagation follows this edge
+        a.send(1);
+        assert_eq!((tens.get(), tens.reaction_count()), (10, 1));
+        drop(tens);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
+    }
 
-        let _observer_a = a.map(|x| println!("a changed: {}", x));
-        let _observer_b = b.map(|x| println!("b changed: {}", x));
-        let _observer_c = c.map(|x| println!("c changed: {}", x));
+    #[test]
+    fn test_combine_latest_registers_successor_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let latest = a.combine_latest(&b);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        assert_eq!(b.successor_count(), 1);
+        assert!(b.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(latest.reaction_count(), 0);
+        drop(latest);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
+        assert_eq!(b.live_successor_count(), 0);
+        assert_eq!(b.0.react_fns.read().unwrap().entries.len(), 0);
+    }
 
-        b = c.depend(b);
-        a = b.depend(a);
+    #[test]
+    fn test_zip_registers_successor_sync() {
+        let a = SignalSync::new(0);
+        let b = SignalSync::new(0);
+        let zipped = a.zip(&b);
+        assert_eq!(a.successor_count(), 1);
+        assert!(a.to_dot().contains("n0 -> n1;"));
+        assert_eq!(b.successor_count(), 1);
+        assert!(b.to_dot().contains("n0 -> n1;"));
+        // Propagation does not follow the edge, the update is still skipped
+        a.send(1);
+        assert_eq!(zipped.reaction_count(), 0);
+        drop(zipped);
+        assert_eq!(a.live_successor_count(), 0);
+        assert_eq!(a.0.react_fns.read().unwrap().entries.len(), 0);
+        assert_eq!(b.live_successor_count(), 0);
+        assert_eq!(b.0.react_fns.read().unwrap().entries.len(), 0);
+    }
 
-        (a.send(42), b.send(88));
+    #[test]
+    fn test_map_variants_share_error_sink_sync() {
+        let a = SignalSync::new(1);
+        let sink = a.error_sink();
+        let shares =
+            |mapped: Option<&Arc<_>>| mapped.is_some_and(|mapped| Arc::ptr_eq(mapped, &sink));
+        assert!(shares(
+            a.map(|x| x + 1).0.error_sink.read().unwrap().as_ref()
+        ));
+        assert!(shares(
+            a.with(|x| x + 1).0.error_sink.read().unwrap().as_ref()
+        ));
+        assert!(shares(
+            a.map_copied(|x| x + 1)
+                .0
+                .error_sink
+                .read()
+                .unwrap()
+                .as_ref()
+        ));
+        assert!(shares(
+            a.map_cloned(|x| x + 1)
+                .0
+                .error_sink
+                .read()
+                .unwrap()
+                .as_ref()
+        ));
+        assert!(shares(
+            a.map_into(0, |x, y| *y = *x)
+                .0
+                .error_sink
+                .read()
+                .unwrap()
+                .as_ref()
+        ));
+        assert!(shares(
+            a.map_lazy(|x| x + 1).0.error_sink.read().unwrap().as_ref()
+        ));
+        assert!(shares(
+            a.map_memo(|x| x + 1).0.error_sink.read().unwrap().as_ref()
+        ));
+        assert!(shares(
+            a.map_forward(|x| (*x, None))
+                .0
+                .error_sink
+                .read()
+                .unwrap()
+                .as_ref()
+        ));
+        assert!(shares(
+            a.map_eager(|x| x + 1).0.error_sink.read().unwrap().as_ref()
+        ));
     }
 }
@@ -1,30 +1,152 @@
-use std::{thread, time::Duration};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
 
 use crate::signal_sync::SignalSync;
 
-pub fn clock_tick(gap: Duration, total: Duration) -> SignalSync<'static, u64> {
+/// Handle to stop the thread of a clock started by `clock_tick`
+///
+/// Dropping the handle leaves the clock running until its total duration elapses.
+pub struct ClockHandle {
+    stopped: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl ClockHandle {
+    /// Stop the clock, waiting for its thread to exit
+    ///
+    /// The clock is woken up if it is waiting for the next tick, so this returns promptly,
+    /// and no tick is sent once it has returned.
+    pub fn stop(self) {
+        self.stopped.store(true, Ordering::Release);
+        self.thread.thread().unpark();
+        let _ = self.thread.join();
+    }
+}
+
+/// Create a signal counting ticks, sent every `gap` on a background thread until `total` elapses
+///
+/// The ticks count from 0. The returned handle stops the clock early.
+///
+/// # Example
+/// ```rust
+/// # use agility::signals::clock_tick;
+/// # use std::time::Duration;
+/// let (ticks, clock) = clock_tick(Duration::from_millis(10), Duration::from_secs(1));
+/// let _observer = ticks.map(|tick| println!("tick {}", tick));
+/// std::thread::sleep(Duration::from_millis(35)); // prints "tick 0" to "tick 2"
+/// clock.stop();
+/// ```
+pub fn clock_tick(gap: Duration, total: Duration) -> (SignalSync<'static, u64>, ClockHandle) {
     let signal = SignalSync::new(0u64);
     let signal_clone = signal.clone();
-    thread::spawn(move || {
+    let stopped = Arc::new(AtomicBool::new(false));
+    let stopped_clone = stopped.clone();
+    let thread = thread::spawn(move || {
         let mut elapsed = Duration::from_secs(0);
         let mut ticks = 0;
         while elapsed < total {
-            thread::sleep(gap);
+            // Parking instead of sleeping lets `stop` wake the clock up
+            let deadline = Instant::now() + gap;
+            let mut now = Instant::now();
+            while now < deadline && !stopped_clone.load(Ordering::Acquire) {
+                thread::park_timeout(deadline - now);
+                now = Instant::now();
+            }
+            if stopped_clone.load(Ordering::Acquire) {
+                break;
+            }
             elapsed += gap;
             signal_clone.send(ticks);
             ticks += 1;
         }
     });
+    (signal, ClockHandle { stopped, thread })
+}
+
+/// Create a signal holding values generated every `gap` on a background thread
+///
+/// The signal starts with `f(0)`, and after every `gap` the thread sends `f` of the number of
+/// gaps elapsed so far. This generalizes `clock_tick` to any generated value, like timestamps or
+/// states of a cycle. The thread only holds a weak reference to the signal and exits once every
+/// clone of the returned signal is dropped.
+///
+/// # Example
+/// ```rust
+/// # use agility::signals::interval;
+/// # use std::time::{Duration, Instant};
+/// let timestamps = interval(Duration::from_millis(10), |_| Instant::now());
+/// let _observer = timestamps.map(|t| println!("now: {:?}", t));
+/// std::thread::sleep(Duration::from_millis(25)); // prints two timestamps
+/// ```
+pub fn interval<T, F>(gap: Duration, mut f: F) -> SignalSync<'static, T>
+where
+    T: Send + Sync + 'static,
+    F: FnMut(u64) -> T + Send + 'static,
+{
+    let signal = SignalSync::new(f(0));
+    let signal_weak = Arc::downgrade(&signal.0);
+    thread::spawn(move || {
+        let mut ticks = 0;
+        loop {
+            thread::sleep(gap);
+            let Some(inner) = signal_weak.upgrade() else {
+                break;
+            };
+            ticks += 1;
+            SignalSync(inner).send(f(ticks));
+        }
+    });
+    signal
+}
+
+/// Create a signal replaying the items of an iterator on a background thread
+///
+/// The signal starts with `T::default()`, then the thread sends each item after waiting
+/// `gap`, and exits after the last one. This is useful to replay recorded data into a reactive
+/// graph, for example in tests. Like `interval`, the thread only holds a weak reference to the
+/// signal and stops pulling items once every clone of the returned signal is dropped.
+///
+/// # Example
+/// ```rust
+/// # use agility::signals::from_iter;
+/// # use std::time::Duration;
+/// let readings = from_iter(vec![1, 2, 3], Duration::from_millis(10));
+/// let _observer = readings.map(|r| println!("reading: {}", r));
+/// std::thread::sleep(Duration::from_millis(50)); // prints "reading: 1" to "reading: 3"
+/// ```
+pub fn from_iter<T, I>(iter: I, gap: Duration) -> SignalSync<'static, T>
+where
+    T: Default + Send + Sync + 'static,
+    I: IntoIterator<Item = T> + Send + 'static,
+{
+    let signal = SignalSync::new(T::default());
+    let signal_weak = Arc::downgrade(&signal.0);
+    thread::spawn(move || {
+        for item in iter {
+            thread::sleep(gap);
+            let Some(inner) = signal_weak.upgrade() else {
+                break;
+            };
+            SignalSync(inner).send(item);
+        }
+    });
     signal
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Mutex, atomic::AtomicUsize};
 
     #[test]
     fn test_clock_tick() {
-        let tick_signal = clock_tick(Duration::from_millis(100), Duration::from_secs(1));
+        let (tick_signal, _clock) = clock_tick(Duration::from_millis(100), Duration::from_secs(1));
         let observer = tick_signal.map(|tick| {
             println!("Tick: {}", tick);
         });
@@ -32,4 +154,80 @@ mod tests {
         thread::sleep(Duration::from_secs(2));
         drop(observer);
     }
+
+    #[test]
+    fn test_clock_stop() {
+        let (tick_signal, clock) = clock_tick(Duration::from_millis(10), Duration::from_secs(10));
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let ticks_clone = ticks.clone();
+        let _observer =
+            tick_signal.observe_weak(move |tick| ticks_clone.lock().unwrap().push(*tick));
+        while ticks.lock().unwrap().len() < 2 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        clock.stop();
+        let seen = ticks.lock().unwrap().clone();
+        assert!(seen.len() >= 2);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(*ticks.lock().unwrap(), seen);
+    }
+
+    #[test]
+    fn test_interval_timestamps() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        let start = Instant::now();
+        let timestamps = interval(Duration::from_millis(10), move |_| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            Instant::now()
+        });
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let observer = timestamps.observe_weak(move |t| seen_clone.lock().unwrap().push(*t));
+        while seen.lock().unwrap().len() < 3 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        let seen_now = seen.lock().unwrap().clone();
+        assert!(seen_now[0] >= start + Duration::from_millis(10));
+        assert!(
+            seen_now
+                .windows(2)
+                .all(|w| w[1] >= w[0] + Duration::from_millis(10))
+        );
+
+        // The thread exits once the signal is dropped
+        drop((observer, timestamps));
+        thread::sleep(Duration::from_millis(30));
+        let calls_after_drop = calls.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(calls.load(Ordering::SeqCst), calls_after_drop);
+    }
+
+    #[test]
+    fn test_from_iter_replays_items() {
+        let replay = from_iter(vec![1, 2, 3], Duration::from_millis(20));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _observer = replay.observe_weak(move |x| seen_clone.lock().unwrap().push(*x));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while seen.lock().unwrap().len() < 3 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(replay.get(), 3);
+    }
+
+    #[test]
+    fn test_from_iter_stops_when_dropped() {
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let pulled_clone = pulled.clone();
+        let items = (0..).inspect(move |_| {
+            pulled_clone.fetch_add(1, Ordering::SeqCst);
+        });
+        drop(from_iter(items, Duration::from_millis(5)));
+        thread::sleep(Duration::from_millis(30));
+        let pulled_after_drop = pulled.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(pulled.load(Ordering::SeqCst), pulled_after_drop);
+    }
 }
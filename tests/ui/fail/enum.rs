@@ -0,0 +1,9 @@
+use agility::{Lift, Signal};
+
+#[derive(Lift)]
+enum Choice<'a> {
+    Left(Signal<'a, i32>),
+    Right(Signal<'a, i32>),
+}
+
+fn main() {}
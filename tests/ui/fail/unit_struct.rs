@@ -0,0 +1,6 @@
+use agility::LiftSync;
+
+#[derive(LiftSync)]
+struct Empty;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use agility::{Lift, Signal};
+
+#[derive(Lift)]
+#[lift(shared)]
+struct Point<'a> {
+    x: Signal<'a, i32>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use agility::{Lift, Signal};
+
+#[derive(Lift)]
+struct Point<'a> {
+    #[lift(ignore)]
+    x: Signal<'a, i32>,
+}
+
+fn main() {}
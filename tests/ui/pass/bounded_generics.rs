@@ -0,0 +1,32 @@
+use std::fmt::Debug;
+
+use agility::{Lift, Signal};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tagged<T: Clone + Debug> {
+    value: T,
+}
+
+#[derive(Lift)]
+struct Labeled<'a, T: Clone + Debug + 'a>
+where
+    T: PartialEq,
+{
+    current: Signal<'a, Tagged<T>>,
+    fallback: Tagged<T>,
+}
+
+fn main() {
+    let current = Signal::new(Tagged { value: 1 });
+    let lifted: Signal<'_, _Labeled<i32>> = Labeled {
+        current: current.clone(),
+        fallback: Tagged { value: 0 },
+    }
+    .lift();
+
+    current.send(Tagged { value: 2 });
+    lifted.with(|inner| {
+        assert_eq!(inner.current, Tagged { value: 2 });
+        assert_ne!(inner.current, inner.fallback);
+    });
+}
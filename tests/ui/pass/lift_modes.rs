@@ -0,0 +1,39 @@
+use agility::signal_sync::SignalSync;
+use agility::{Lift, Signal};
+
+#[derive(Lift)]
+struct Local<'a> {
+    count: Signal<'a, i32>,
+    name: String,
+}
+
+#[derive(Lift)]
+#[lift(sync)]
+struct Shared<'a> {
+    count: SignalSync<'a, i32>,
+    name: String,
+}
+
+fn main() {
+    let count = Signal::new(1);
+    let local: Signal<'_, _Local> = Local {
+        count: count.clone(),
+        name: "local".to_string(),
+    }
+    .lift();
+    count.send(2);
+    local.with(|inner| assert_eq!((inner.count, inner.name.as_str()), (2, "local")));
+
+    let count = SignalSync::new(1);
+    let shared: SignalSync<'_, _Shared> = Shared {
+        count: count.clone(),
+        name: "shared".to_string(),
+    }
+    .lift();
+    count.send(2);
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            shared.with(|inner| assert_eq!((inner.count, inner.name.as_str()), (2, "shared")));
+        });
+    });
+}
@@ -0,0 +1,35 @@
+use agility::api::{LiftInto, LiftIntoSync};
+use agility::signal_sync::SignalSync;
+use agility::Signal;
+
+// `Clone` but not `Copy`: lifted values are cloned from their sources, which keep their own
+#[derive(Clone, Debug, PartialEq)]
+struct Log {
+    lines: Vec<String>,
+}
+
+fn log(lines: &[&str]) -> Log {
+    Log {
+        lines: lines.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+fn main() {
+    let first = Signal::new(log(&["start"]));
+    let count = Signal::new(7);
+    let other = Signal::new(log(&["other"]));
+    let lifted = (&first, &count, &other).lift();
+    assert_eq!(lifted.get(), (log(&["start"]), 7, log(&["other"])));
+    first.send(log(&["start", "more"]));
+    assert_eq!(lifted.get(), (log(&["start", "more"]), 7, log(&["other"])));
+    assert_eq!(first.get(), log(&["start", "more"]));
+    assert_eq!(other.get(), log(&["other"]));
+
+    let first = SignalSync::new(log(&["start"]));
+    let count = SignalSync::new(7);
+    let lifted = (&first, &count).lift();
+    assert_eq!(lifted.get(), (log(&["start"]), 7));
+    first.send(log(&["sync"]));
+    assert_eq!(lifted.get(), (log(&["sync"]), 7));
+    assert_eq!((first.get(), count.get()), (log(&["sync"]), 7));
+}
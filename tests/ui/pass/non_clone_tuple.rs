@@ -0,0 +1,40 @@
+use std::fs::File;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use agility::api::{LiftInto, LiftIntoSync};
+use agility::signal_sync::SignalSync;
+use agility::Signal;
+
+// Not `Clone`: a value owning a file is lifted through a shared handle to it
+struct Journal {
+    file: File,
+    name: &'static str,
+}
+
+fn open(name: &'static str) -> Journal {
+    Journal {
+        file: File::open(std::env::current_exe().unwrap()).unwrap(),
+        name,
+    }
+}
+
+fn main() {
+    let journal = Signal::new(Rc::new(open("first")));
+    let count = Signal::new(0);
+    let other = Signal::new(Rc::new(open("other")));
+    let lifted = (&journal, &count, &other).lift();
+    let (first, _, _) = lifted.get();
+    assert!(Rc::ptr_eq(&first, &journal.get()));
+    journal.send(Rc::new(open("second")));
+    let (second, count, other_journal) = lifted.get();
+    assert_eq!((second.name, count, other_journal.name), ("second", 0, "other"));
+    assert!(second.file.metadata().unwrap().is_file());
+
+    let journal = SignalSync::new(Arc::new(open("first")));
+    let count = SignalSync::new(0);
+    let lifted = (&journal, &count).lift();
+    (journal.send(Arc::new(open("second"))), count.send(1));
+    let (journal, count) = lifted.get();
+    assert_eq!((journal.name, count), ("second", 1));
+}
@@ -0,0 +1,32 @@
+use agility::{Lift, Signal};
+
+#[derive(Lift)]
+struct Form<'a> {
+    value: Signal<'a, i32>,
+    #[lift(skip)]
+    history: Signal<'a, Vec<i32>>,
+}
+
+fn main() {
+    let runs = std::cell::Cell::new(0);
+    let value = Signal::new(1);
+    let history = Signal::new(vec![1]);
+    let form = Form {
+        value: value.clone(),
+        history: history.clone(),
+    }
+    .lift();
+
+    // The skipped field keeps its signal type and the lifted signal does not react to it
+    let _subscription = form.subscribe(|_| runs.set(runs.get() + 1));
+    history.send(vec![1, 2]);
+    assert_eq!(runs.get(), 0);
+    form.with(|inner| {
+        let kept: &Signal<'_, Vec<i32>> = &inner.history;
+        assert_eq!(kept.get(), vec![1, 2]);
+    });
+
+    value.send(2);
+    assert_eq!(runs.get(), 1);
+    form.with(|inner| assert_eq!(inner.value, 2));
+}
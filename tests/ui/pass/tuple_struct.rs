@@ -0,0 +1,18 @@
+use agility::{Lift, Signal};
+
+#[derive(Lift)]
+struct Pair<'a>(Signal<'a, i32>, Signal<'a, i32>);
+
+fn main() {
+    let left = Signal::new(1);
+    let right = Signal::new(2);
+    let pair = Pair(left.clone(), right.clone());
+
+    let lifted: Signal<'_, _Pair> = pair.lift();
+    let _Pair(a, b) = lifted.get();
+    assert_eq!((a, b), (1, 2));
+
+    left.send(10);
+    right.send(20);
+    lifted.with(|_Pair(a, b)| assert_eq!((*a, *b), (10, 20)));
+}